@@ -9,16 +9,6 @@ enum RispListNode<T> {
     Nil,
 }
 
-impl<T> RispListNode<T> {
-    fn nth(&self, idx: usize) -> Option<&T> {
-        match (self, idx) {
-            (Self::Cons(v, _), 0) => Some(v),
-            (Self::Nil, _) => None,
-            (Self::Cons(_, tail), _) => tail.nth(idx - 1),
-        }
-    }
-}
-
 pub struct RispList<T> {
     head: Rc<RispListNode<T>>,
     length: usize,
@@ -42,6 +32,20 @@ impl<T> Clone for RispList<T> {
     }
 }
 
+// The default drop would recurse once per node and overflow on long lists,
+// so nodes nobody else shares are unlinked in a loop instead
+impl<T> Drop for RispList<T> {
+    fn drop(&mut self) {
+        if Rc::strong_count(&self.head) > 1 {
+            return;
+        }
+        let mut next = std::mem::replace(&mut self.head, Rc::new(RispListNode::Nil));
+        while let Ok(RispListNode::Cons(_, tail)) = Rc::try_unwrap(next) {
+            next = tail;
+        }
+    }
+}
+
 impl<T> RispList<T> {
     pub fn empty() -> Self {
         Self::default()
@@ -65,7 +69,7 @@ impl<T> RispList<T> {
     }
 
     pub fn last(&self) -> Option<&T> {
-        self.nth(self.length).unwrap()
+        self.iter().last()
     }
 
     pub fn rest(&self) -> RispList<T> {
@@ -91,7 +95,7 @@ impl<T> RispList<T> {
             return Err(CollectionError::IndexOutOfBounds { value: idx });
         }
 
-        Ok(self.head.nth(idx))
+        Ok(self.iter().nth(idx))
     }
 
     pub fn get(&self, idx: usize) -> Result<Option<&T>, CollectionError> {
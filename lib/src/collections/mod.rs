@@ -1,7 +1,15 @@
 mod errors;
 #[allow(dead_code)]
 mod list;
+mod queue;
+mod sorted;
 #[cfg(test)]
 mod test_list;
+#[cfg(test)]
+mod test_queue;
+#[cfg(test)]
+mod test_sorted;
 
 pub use list::RispList;
+pub use queue::RispQueue;
+pub use sorted::SortedTree;
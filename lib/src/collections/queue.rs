@@ -0,0 +1,113 @@
+use std::fmt::{Debug, Display};
+
+use super::RispList;
+
+/// FIFO queue built from two lists: items are taken from `front` and added
+/// to `rear`, which is reversed into `front` once `front` runs out. Each item
+/// is moved at most once, so `conj` and `pop` are amortised O(1).
+pub struct RispQueue<T> {
+    front: RispList<T>,
+    rear: RispList<T>,
+}
+
+impl<T> Default for RispQueue<T> {
+    fn default() -> Self {
+        Self {
+            front: RispList::empty(),
+            rear: RispList::empty(),
+        }
+    }
+}
+
+impl<T> Clone for RispQueue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            front: self.front.clone(),
+            rear: self.rear.clone(),
+        }
+    }
+}
+
+impl<T: Clone> RispQueue<T> {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    // `front` is only empty when the whole queue is
+    fn balanced(front: RispList<T>, rear: RispList<T>) -> Self {
+        if front.is_empty() {
+            Self {
+                front: rear
+                    .iter()
+                    .cloned()
+                    .fold(RispList::empty(), |acc, v| RispList::cons(v, &acc)),
+                rear: RispList::empty(),
+            }
+        } else {
+            Self { front, rear }
+        }
+    }
+
+    pub fn conj(&self, value: T) -> Self {
+        Self::balanced(self.front.clone(), RispList::cons(value, &self.rear))
+    }
+
+    /// The oldest item
+    pub fn peek(&self) -> Option<&T> {
+        self.front.first()
+    }
+
+    /// The queue without its oldest item; popping an empty queue is a no-op
+    pub fn pop(&self) -> Self {
+        Self::balanced(self.front.rest(), self.rear.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.rear.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty()
+    }
+
+    /// Items from oldest to newest
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let rear: Vec<&T> = self.rear.iter().collect();
+        self.front.iter().chain(rear.into_iter().rev())
+    }
+}
+
+impl<T: Clone> FromIterator<T> for RispQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            front: iter.into_iter().collect(),
+            rear: RispList::empty(),
+        }
+    }
+}
+
+impl<T: Clone + Display> Display for RispQueue<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#queue [")?;
+        let mut iter = self.iter().peekable();
+        while let Some(val) = iter.next() {
+            write!(f, "{val}")?;
+            if iter.peek().is_some() {
+                write!(f, " ")?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+impl<T: Clone + Debug> Debug for RispQueue<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone + PartialEq> PartialEq for RispQueue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
@@ -0,0 +1,331 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+type Link<K, V> = Option<Rc<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    height: u8,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+/// Persistent AVL tree. Updates copy the path from the root and share every
+/// other node with the previous version. The ordering is supplied on each call
+/// and may fail (a user comparator can raise an error), so every operation
+/// that compares keys returns a `Result`.
+pub struct SortedTree<K, V> {
+    root: Link<K, V>,
+    length: usize,
+}
+
+impl<K, V> Default for SortedTree<K, V> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            length: 0,
+        }
+    }
+}
+
+impl<K, V> Clone for SortedTree<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            length: self.length,
+        }
+    }
+}
+
+fn height<K, V>(link: &Link<K, V>) -> u8 {
+    link.as_ref().map_or(0, |n| n.height)
+}
+
+fn node<K, V>(key: K, value: V, left: Link<K, V>, right: Link<K, V>) -> Rc<Node<K, V>> {
+    Rc::new(Node {
+        height: height(&left).max(height(&right)) + 1,
+        key,
+        value,
+        left,
+        right,
+    })
+}
+
+// Builds a node whose subtrees differ in height by at most 2, rotating it back
+// into AVL shape
+fn balance<K: Clone, V: Clone>(
+    key: K,
+    value: V,
+    left: Link<K, V>,
+    right: Link<K, V>,
+) -> Rc<Node<K, V>> {
+    let (hl, hr) = (height(&left), height(&right));
+    if hl > hr + 1 {
+        let l = left.unwrap();
+        if height(&l.left) >= height(&l.right) {
+            let r = node(key, value, l.right.clone(), right);
+            return node(l.key.clone(), l.value.clone(), l.left.clone(), Some(r));
+        }
+        let lr = l.right.clone().unwrap();
+        let new_l = node(
+            l.key.clone(),
+            l.value.clone(),
+            l.left.clone(),
+            lr.left.clone(),
+        );
+        let new_r = node(key, value, lr.right.clone(), right);
+        return node(lr.key.clone(), lr.value.clone(), Some(new_l), Some(new_r));
+    }
+    if hr > hl + 1 {
+        let r = right.unwrap();
+        if height(&r.right) >= height(&r.left) {
+            let l = node(key, value, left, r.left.clone());
+            return node(r.key.clone(), r.value.clone(), Some(l), r.right.clone());
+        }
+        let rl = r.left.clone().unwrap();
+        let new_l = node(key, value, left, rl.left.clone());
+        let new_r = node(
+            r.key.clone(),
+            r.value.clone(),
+            rl.right.clone(),
+            r.right.clone(),
+        );
+        return node(rl.key.clone(), rl.value.clone(), Some(new_l), Some(new_r));
+    }
+    node(key, value, left, right)
+}
+
+// Returns the new subtree and whether the key was not there before
+fn insert<K: Clone, V: Clone, E>(
+    link: &Link<K, V>,
+    key: K,
+    value: V,
+    cmp: &mut impl FnMut(&K, &K) -> Result<Ordering, E>,
+) -> Result<(Rc<Node<K, V>>, bool), E> {
+    let Some(n) = link else {
+        return Ok((node(key, value, None, None), true));
+    };
+    match cmp(&key, &n.key)? {
+        Ordering::Less => {
+            let (left, added) = insert(&n.left, key, value, cmp)?;
+            let rebuilt = balance(n.key.clone(), n.value.clone(), Some(left), n.right.clone());
+            Ok((rebuilt, added))
+        }
+        Ordering::Greater => {
+            let (right, added) = insert(&n.right, key, value, cmp)?;
+            let rebuilt = balance(n.key.clone(), n.value.clone(), n.left.clone(), Some(right));
+            Ok((rebuilt, added))
+        }
+        // Like Clojure, the key already in the tree is kept
+        Ordering::Equal => Ok((
+            node(n.key.clone(), value, n.left.clone(), n.right.clone()),
+            false,
+        )),
+    }
+}
+
+fn remove_min<K: Clone, V: Clone>(n: &Rc<Node<K, V>>) -> (K, V, Link<K, V>) {
+    match &n.left {
+        None => (n.key.clone(), n.value.clone(), n.right.clone()),
+        Some(left) => {
+            let (k, v, new_left) = remove_min(left);
+            let rebuilt = balance(n.key.clone(), n.value.clone(), new_left, n.right.clone());
+            (k, v, Some(rebuilt))
+        }
+    }
+}
+
+// Returns the new subtree, or `None` when the key was not found
+fn remove<K: Clone, V: Clone, E>(
+    link: &Link<K, V>,
+    key: &K,
+    cmp: &mut impl FnMut(&K, &K) -> Result<Ordering, E>,
+) -> Result<Option<Link<K, V>>, E> {
+    let Some(n) = link else {
+        return Ok(None);
+    };
+    let rebuilt = match cmp(key, &n.key)? {
+        Ordering::Less => remove(&n.left, key, cmp)?.map(|left| {
+            Some(balance(
+                n.key.clone(),
+                n.value.clone(),
+                left,
+                n.right.clone(),
+            ))
+        }),
+        Ordering::Greater => remove(&n.right, key, cmp)?.map(|right| {
+            Some(balance(
+                n.key.clone(),
+                n.value.clone(),
+                n.left.clone(),
+                right,
+            ))
+        }),
+        Ordering::Equal => Some(match (&n.left, &n.right) {
+            (None, right) => right.clone(),
+            (left, None) => left.clone(),
+            (left, Some(right)) => {
+                let (k, v, new_right) = remove_min(right);
+                Some(balance(k, v, left.clone(), new_right))
+            }
+        }),
+    };
+    Ok(rebuilt)
+}
+
+/// In-order walk, ascending or descending, holding the path still to visit.
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+    forward: bool,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn descend(&mut self, mut link: &'a Link<K, V>) {
+        while let Some(n) = link {
+            self.stack.push(n);
+            link = if self.forward { &n.left } else { &n.right };
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.stack.pop()?;
+        self.descend(if self.forward { &n.right } else { &n.left });
+        Some((&n.key, &n.value))
+    }
+}
+
+impl<K: Clone, V: Clone> SortedTree<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn get<E>(
+        &self,
+        key: &K,
+        cmp: &mut impl FnMut(&K, &K) -> Result<Ordering, E>,
+    ) -> Result<Option<(&K, &V)>, E> {
+        let mut link = &self.root;
+        while let Some(n) = link {
+            link = match cmp(key, &n.key)? {
+                Ordering::Less => &n.left,
+                Ordering::Greater => &n.right,
+                Ordering::Equal => return Ok(Some((&n.key, &n.value))),
+            };
+        }
+        Ok(None)
+    }
+
+    /// Adds `key`, replacing the value of an equal key already present.
+    pub fn insert<E>(
+        &self,
+        key: K,
+        value: V,
+        cmp: &mut impl FnMut(&K, &K) -> Result<Ordering, E>,
+    ) -> Result<Self, E> {
+        let (root, added) = insert(&self.root, key, value, cmp)?;
+        Ok(Self {
+            root: Some(root),
+            length: self.length + usize::from(added),
+        })
+    }
+
+    pub fn remove<E>(
+        &self,
+        key: &K,
+        cmp: &mut impl FnMut(&K, &K) -> Result<Ordering, E>,
+    ) -> Result<Self, E> {
+        Ok(match remove(&self.root, key, cmp)? {
+            Some(root) => Self {
+                root,
+                length: self.length - 1,
+            },
+            None => self.clone(),
+        })
+    }
+
+    pub fn first(&self) -> Option<(&K, &V)> {
+        let mut n = self.root.as_ref()?;
+        while let Some(left) = &n.left {
+            n = left;
+        }
+        Some((&n.key, &n.value))
+    }
+
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let mut n = self.root.as_ref()?;
+        while let Some(right) = &n.right {
+            n = right;
+        }
+        Some((&n.key, &n.value))
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.walk(true)
+    }
+
+    pub fn iter_rev(&self) -> Iter<'_, K, V> {
+        self.walk(false)
+    }
+
+    fn walk(&self, forward: bool) -> Iter<'_, K, V> {
+        let mut iter = Iter {
+            stack: vec![],
+            forward,
+        };
+        iter.descend(&self.root);
+        iter
+    }
+
+    /// Walks from `key` onwards: ascending over keys after it when `forward`,
+    /// descending over keys before it otherwise. `key` itself is included when
+    /// `inclusive` and present.
+    pub fn seek<E>(
+        &self,
+        key: &K,
+        inclusive: bool,
+        forward: bool,
+        cmp: &mut impl FnMut(&K, &K) -> Result<Ordering, E>,
+    ) -> Result<Iter<'_, K, V>, E> {
+        let mut iter = Iter {
+            stack: vec![],
+            forward,
+        };
+        let mut link = &self.root;
+        while let Some(n) = link {
+            let ord = cmp(&n.key, key)?;
+            let wanted = match (ord, forward) {
+                (Ordering::Equal, _) => inclusive,
+                (Ordering::Greater, true) | (Ordering::Less, false) => true,
+                _ => false,
+            };
+            // A wanted node is visited after everything wanted below it on
+            // the near side, so it goes on the stack before descending there
+            link = match (wanted, forward) {
+                (true, true) => {
+                    iter.stack.push(n);
+                    &n.left
+                }
+                (true, false) => {
+                    iter.stack.push(n);
+                    &n.right
+                }
+                (false, true) => &n.right,
+                (false, false) => &n.left,
+            };
+        }
+        Ok(iter)
+    }
+}
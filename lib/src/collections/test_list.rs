@@ -161,4 +161,20 @@ mod tests {
         let list: RispList<i32> = vec![42].into_iter().collect();
         assert_eq!(format!("{list}"), "(42)");
     }
+
+    #[test]
+    fn long_list_drops_without_overflow() {
+        let list: RispList<usize> = (0..1_000_000).collect();
+        assert_eq!(list.nth(999_999).unwrap(), Some(&999_999));
+        let shared = list.rest();
+        drop(list);
+        assert_eq!(shared.len(), 999_999);
+    }
+
+    #[test]
+    fn last_of_list() {
+        let list: RispList<i32> = vec![1, 2, 3].into_iter().collect();
+        assert_eq!(list.last(), Some(&3));
+        assert_eq!(RispList::<i32>::empty().last(), None);
+    }
 }
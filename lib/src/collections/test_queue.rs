@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::collections::RispQueue;
+
+    #[test]
+    fn empty_has_len_zero() {
+        let queue: RispQueue<i32> = RispQueue::empty();
+        assert_eq!(queue.len(), 0);
+        assert!(queue.is_empty());
+        assert!(queue.peek().is_none());
+    }
+
+    #[test]
+    fn conj_adds_to_back() {
+        let queue = RispQueue::empty().conj(1).conj(2).conj(3);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.peek(), Some(&1));
+    }
+
+    #[test]
+    fn pop_removes_from_front() {
+        let queue = RispQueue::empty().conj(1).conj(2).conj(3).pop();
+        assert_eq!(queue.peek(), Some(&2));
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn pop_empty_is_empty() {
+        let queue: RispQueue<i32> = RispQueue::empty().pop();
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn iter_oldest_first_across_halves() {
+        let queue = RispQueue::empty().conj(1).conj(2).pop().conj(3).conj(4);
+        let got: Vec<&i32> = queue.iter().collect();
+        assert_eq!(got, vec![&2, &3, &4]);
+    }
+
+    #[test]
+    fn older_versions_are_unchanged() {
+        let one = RispQueue::empty().conj(1);
+        let two = one.conj(2);
+        let popped = two.pop();
+        assert_eq!(one.iter().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(two.iter().collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(popped.iter().collect::<Vec<_>>(), vec![&2]);
+    }
+
+    #[test]
+    fn drains_in_insertion_order() {
+        let mut queue: RispQueue<usize> = (0..5).collect();
+        for i in 5..10 {
+            queue = queue.conj(i);
+        }
+        let mut got = vec![];
+        while let Some(v) = queue.peek() {
+            got.push(*v);
+            queue = queue.pop();
+        }
+        assert_eq!(got, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn partial_eq_ignores_internal_layout() {
+        let a = RispQueue::empty().conj(0).conj(1).conj(2).pop();
+        let b: RispQueue<i32> = vec![1, 2].into_iter().collect();
+        assert_eq!(a, b);
+        assert_ne!(a, RispQueue::empty().conj(1));
+    }
+
+    #[test]
+    fn display_like_literal() {
+        let queue: RispQueue<i32> = vec![1, 2].into_iter().collect();
+        assert_eq!(format!("{queue}"), "#queue [1 2]");
+        assert_eq!(format!("{}", RispQueue::<i32>::empty()), "#queue []");
+    }
+}
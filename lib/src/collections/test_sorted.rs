@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::collections::SortedTree;
+
+    fn ord(a: &i64, b: &i64) -> Result<Ordering, ()> {
+        Ok(a.cmp(b))
+    }
+
+    fn tree(keys: &[i64]) -> SortedTree<i64, i64> {
+        keys.iter().fold(SortedTree::new(), |t, k| {
+            t.insert(*k, k * 10, &mut ord).unwrap()
+        })
+    }
+
+    fn keys(t: &SortedTree<i64, i64>) -> Vec<i64> {
+        t.iter().map(|(k, _)| *k).collect()
+    }
+
+    #[test]
+    fn empty_tree() {
+        let t: SortedTree<i64, i64> = SortedTree::new();
+        assert!(t.is_empty());
+        assert_eq!(t.first(), None);
+        assert_eq!(t.iter().count(), 0);
+    }
+
+    #[test]
+    fn iterates_in_order() {
+        let t = tree(&[5, 3, 8, 1, 4, 9, 2]);
+        assert_eq!(keys(&t), vec![1, 2, 3, 4, 5, 8, 9]);
+        assert_eq!(
+            t.iter_rev().map(|(k, _)| *k).collect::<Vec<_>>(),
+            vec![9, 8, 5, 4, 3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn insert_replaces_value_of_equal_key() {
+        let t = tree(&[1, 2]).insert(1, 99, &mut ord).unwrap();
+        assert_eq!(t.len(), 2);
+        assert_eq!(t.get(&1, &mut ord).unwrap(), Some((&1, &99)));
+    }
+
+    #[test]
+    fn updates_are_persistent() {
+        let before = tree(&[1, 2, 3]);
+        let after = before.insert(4, 40, &mut ord).unwrap();
+        let removed = before.remove(&2, &mut ord).unwrap();
+        assert_eq!(keys(&before), vec![1, 2, 3]);
+        assert_eq!(keys(&after), vec![1, 2, 3, 4]);
+        assert_eq!(keys(&removed), vec![1, 3]);
+    }
+
+    #[test]
+    fn remove_missing_key_is_a_no_op() {
+        let t = tree(&[1, 2]).remove(&7, &mut ord).unwrap();
+        assert_eq!(t.len(), 2);
+    }
+
+    #[test]
+    fn first_and_last() {
+        let t = tree(&[5, 3, 8]);
+        assert_eq!(t.first(), Some((&3, &30)));
+        assert_eq!(t.last(), Some((&8, &80)));
+    }
+
+    #[test]
+    fn stays_balanced_and_ordered_under_churn() {
+        let mut t = SortedTree::new();
+        for i in 0..1000 {
+            t = t.insert((i * 7919) % 1000, i, &mut ord).unwrap();
+        }
+        for i in (0..1000).step_by(2) {
+            t = t.remove(&i, &mut ord).unwrap();
+        }
+        assert_eq!(t.len(), 500);
+        assert_eq!(
+            keys(&t),
+            (0..1000).filter(|i| i % 2 == 1).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn seek_forward() {
+        let t = tree(&[1, 3, 5, 7]);
+        let from = |k, inclusive| {
+            t.seek(&k, inclusive, true, &mut ord)
+                .unwrap()
+                .map(|(k, _)| *k)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(from(3, true), vec![3, 5, 7]);
+        assert_eq!(from(3, false), vec![5, 7]);
+        assert_eq!(from(4, true), vec![5, 7]);
+        assert_eq!(from(8, true), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn seek_backward() {
+        let t = tree(&[1, 3, 5, 7]);
+        let from = |k, inclusive| {
+            t.seek(&k, inclusive, false, &mut ord)
+                .unwrap()
+                .map(|(k, _)| *k)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(from(5, true), vec![5, 3, 1]);
+        assert_eq!(from(5, false), vec![3, 1]);
+        assert_eq!(from(0, true), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn comparator_errors_propagate() {
+        let mut failing = |_: &i64, _: &i64| Err("boom");
+        let t = tree(&[1]);
+        assert_eq!(t.insert(2, 0, &mut failing).err(), Some("boom"));
+        assert_eq!(t.get(&1, &mut failing).err(), Some("boom"));
+    }
+}
@@ -0,0 +1,334 @@
+#[cfg(test)]
+mod test_inst;
+
+use std::fmt::{Display, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// A point in time with millisecond precision. The offset (in minutes east of
+/// UTC) only affects how the instant is shown, two insts at the same moment
+/// are equal whatever their offsets.
+#[derive(Debug, Clone, Copy)]
+pub struct Inst {
+    pub millis: i64,
+    pub offset: i32,
+}
+
+impl PartialEq for Inst {
+    fn eq(&self, other: &Self) -> bool {
+        self.millis == other.millis
+    }
+}
+
+/// Broken-down local time of an inst.
+struct Civil {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    milli: u32,
+    // 0 is Monday
+    weekday: usize,
+}
+
+// Howard Hinnant's days_from_civil / civil_from_days, proleptic Gregorian
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Reads `Z`, `+hh:mm`, `-hh:mm` or `+hhmm` into minutes east of UTC.
+pub fn parse_offset(s: &str) -> Option<i32> {
+    if s == "Z" || s == "z" {
+        return Some(0);
+    }
+    let sign = match s.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let rest = &s[1..];
+    if !rest.is_ascii() {
+        return None;
+    }
+    let (h, m) = match rest.len() {
+        5 if rest.as_bytes()[2] == b':' => (&rest[..2], &rest[3..]),
+        4 => (&rest[..2], &rest[2..]),
+        2 => (rest, "00"),
+        _ => return None,
+    };
+    let hours = digits(h)?;
+    let minutes = digits(m)?;
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 60 + minutes) as i32)
+}
+
+fn digits(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn format_offset(offset: i32, colon: bool) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let (h, m) = (offset.abs() / 60, offset.abs() % 60);
+    if colon {
+        format!("{sign}{h:02}:{m:02}")
+    } else {
+        format!("{sign}{h:02}{m:02}")
+    }
+}
+
+impl Inst {
+    pub fn from_millis(millis: i64) -> Self {
+        Self { millis, offset: 0 }
+    }
+
+    pub fn now() -> Self {
+        let millis = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_millis() as i64,
+            Err(e) => -(e.duration().as_millis() as i64),
+        };
+        Self::from_millis(millis)
+    }
+
+    pub fn with_offset(self, offset: i32) -> Self {
+        Self { offset, ..self }
+    }
+
+    pub fn checked_add(self, millis: i64) -> Option<Self> {
+        Some(Self {
+            millis: self.millis.checked_add(millis)?,
+            ..self
+        })
+    }
+
+    /// Parses RFC 3339 timestamps. Like `#inst` in Clojure, trailing parts may
+    /// be left out: `2026`, `2026-03`, `2026-03-01T10:30` are all fine and a
+    /// missing offset means UTC.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (date, time) = match s.find(['T', 't']) {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+
+        let mut parts = date.split('-');
+        let year_part = parts.next()?;
+        if year_part.len() != 4 {
+            return None;
+        }
+        let year = digits(year_part)? as i64;
+        let month = parts.next().map(two_digits).unwrap_or(Some(1))?;
+        let day = parts.next().map(two_digits).unwrap_or(Some(1))?;
+        if parts.next().is_some() || !(1..=12).contains(&month) {
+            return None;
+        }
+        if day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        let (mut hour, mut minute, mut second, mut milli, mut offset) = (0, 0, 0, 0, 0);
+        if let Some(time) = time {
+            let split = time.find(['Z', 'z', '+', '-']).unwrap_or(time.len());
+            let (clock, zone) = time.split_at(split);
+            if !zone.is_empty() {
+                offset = parse_offset(zone)?;
+            }
+            let (clock, fraction) = match clock.split_once('.') {
+                Some((c, f)) => (c, Some(f)),
+                None => (clock, None),
+            };
+            let mut fields = clock.split(':');
+            hour = two_digits(fields.next()?)?;
+            minute = fields.next().map(two_digits).unwrap_or(Some(0))?;
+            second = fields.next().map(two_digits).unwrap_or(Some(0))?;
+            if fields.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+                return None;
+            }
+            if let Some(fraction) = fraction {
+                if fraction.len() > 9 {
+                    return None;
+                }
+                digits(fraction)?;
+                // Anything past milliseconds is dropped
+                let padded = format!("{fraction:0<3}");
+                milli = digits(&padded[..3])?;
+            }
+        }
+
+        let local = days_from_civil(year, month, day) * MS_PER_DAY
+            + (hour as i64 * 3600 + minute as i64 * 60 + second as i64) * 1000
+            + milli as i64;
+        Some(Self {
+            millis: local - offset as i64 * 60_000,
+            offset,
+        })
+    }
+
+    fn civil(&self) -> Civil {
+        let local = self.millis + self.offset as i64 * 60_000;
+        let days = local.div_euclid(MS_PER_DAY);
+        let ms = local.rem_euclid(MS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        Civil {
+            year,
+            month,
+            day,
+            hour: (ms / 3_600_000) as u32,
+            minute: (ms / 60_000 % 60) as u32,
+            second: (ms / 1000 % 60) as u32,
+            milli: (ms % 1000) as u32,
+            // 1970-01-01 was a Thursday
+            weekday: (days + 3).rem_euclid(7) as usize,
+        }
+    }
+
+    /// Formats with a subset of Java's `DateTimeFormatter` letters: `yyyy yy
+    /// M MM MMM MMMM d dd H HH h hh m mm s ss SSS a E EEEE X XXX Z`. Text in
+    /// single quotes is copied as is (`''` is a quote). Unknown letters are an
+    /// error, anything else is copied.
+    pub fn format(&self, pattern: &str) -> Result<String, String> {
+        let c = self.civil();
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut out = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let ch = chars[i];
+            if ch == '\'' {
+                if chars.get(i + 1) == Some(&'\'') {
+                    out.push('\'');
+                    i += 2;
+                    continue;
+                }
+                i += 1;
+                loop {
+                    match (chars.get(i), chars.get(i + 1)) {
+                        (Some('\''), Some('\'')) => {
+                            out.push('\'');
+                            i += 2;
+                        }
+                        (Some('\''), _) => break,
+                        (Some(c), _) => {
+                            out.push(*c);
+                            i += 1;
+                        }
+                        (None, _) => return Err("unterminated quote".to_string()),
+                    }
+                }
+                i += 1;
+                continue;
+            }
+            if !ch.is_ascii_alphabetic() {
+                out.push(ch);
+                i += 1;
+                continue;
+            }
+            let run = chars[i..].iter().take_while(|c| **c == ch).count();
+            let hour12 = match c.hour % 12 {
+                0 => 12,
+                h => h,
+            };
+            match (ch, run) {
+                ('y', 2) => out.push_str(&format!("{:02}", c.year.rem_euclid(100))),
+                ('y', _) => out.push_str(&format!("{:0run$}", c.year)),
+                ('M', 1 | 2) => out.push_str(&format!("{:0run$}", c.month)),
+                ('M', 3) => out.push_str(&MONTHS[c.month as usize - 1][..3]),
+                ('M', _) => out.push_str(MONTHS[c.month as usize - 1]),
+                ('d', 1 | 2) => out.push_str(&format!("{:0run$}", c.day)),
+                ('H', 1 | 2) => out.push_str(&format!("{:0run$}", c.hour)),
+                ('h', 1 | 2) => out.push_str(&format!("{hour12:0run$}")),
+                ('m', 1 | 2) => out.push_str(&format!("{:0run$}", c.minute)),
+                ('s', 1 | 2) => out.push_str(&format!("{:0run$}", c.second)),
+                ('S', 1..=3) => out.push_str(&format!("{:03}", c.milli)[..run]),
+                ('a', 1) => out.push_str(if c.hour < 12 { "AM" } else { "PM" }),
+                ('E', 1..=3) => out.push_str(&WEEKDAYS[c.weekday][..3]),
+                ('E', _) => out.push_str(WEEKDAYS[c.weekday]),
+                ('X', 1..=3) if self.offset == 0 => out.push('Z'),
+                ('X', 1 | 2) => out.push_str(&format_offset(self.offset, false)),
+                ('X', 3) => out.push_str(&format_offset(self.offset, true)),
+                ('Z', 1..=3) => out.push_str(&format_offset(self.offset, false)),
+                _ => return Err(format!("unknown pattern {}", ch.to_string().repeat(run))),
+            }
+            i += run;
+        }
+        Ok(out)
+    }
+}
+
+fn two_digits(s: &str) -> Option<u32> {
+    if s.len() != 2 {
+        return None;
+    }
+    digits(s)
+}
+
+impl Display for Inst {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = self
+            .format("yyyy-MM-dd'T'HH:mm:ss.SSSXXX")
+            .map_err(|_| std::fmt::Error)?;
+        write!(f, "{s}")
+    }
+}
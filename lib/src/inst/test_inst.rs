@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use crate::inst::{parse_offset, Inst};
+
+    fn parse(s: &str) -> Inst {
+        Inst::parse(s).unwrap()
+    }
+
+    #[test]
+    fn epoch() {
+        assert_eq!(parse("1970-01-01T00:00:00Z").millis, 0);
+    }
+
+    #[test]
+    fn partial_timestamps() {
+        assert_eq!(parse("1970").millis, 0);
+        assert_eq!(parse("1970-01-02").millis, 86_400_000);
+        assert_eq!(parse("1970-01-01T01:30").millis, 5_400_000);
+    }
+
+    #[test]
+    fn fractions_are_truncated_to_millis() {
+        assert_eq!(parse("1970-01-01T00:00:00.5Z").millis, 500);
+        assert_eq!(parse("1970-01-01T00:00:00.123999Z").millis, 123);
+    }
+
+    #[test]
+    fn offsets_shift_the_instant() {
+        let inst = parse("1970-01-01T02:00:00+02:00");
+        assert_eq!(inst.millis, 0);
+        assert_eq!(inst.offset, 120);
+        assert_eq!(parse("1969-12-31T19:00:00-05:00").millis, 0);
+    }
+
+    #[test]
+    fn before_the_epoch() {
+        assert_eq!(parse("1969-12-31T23:59:59.999Z").millis, -1);
+        assert_eq!(
+            Inst::from_millis(-1).to_string(),
+            "1969-12-31T23:59:59.999Z"
+        );
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(Inst::parse("2024-02-29").is_some());
+        assert!(Inst::parse("2000-02-29").is_some());
+        assert!(Inst::parse("2100-02-29").is_none());
+        assert!(Inst::parse("2023-02-29").is_none());
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        for s in [
+            "",
+            "26-01-01",
+            "2026-1-01",
+            "2026-00-01",
+            "2026-04-31",
+            "2026-01-01T24:00",
+            "2026-01-01T10:60",
+            "2026-01-01T10:00:00+25:00",
+            "2026-01-01T10:00:00.",
+            "2026-01-01 10:00",
+            "2026-01-01T00:00:00.éé",
+            "2026-01-01T00:00:00+1é2",
+        ] {
+            assert!(Inst::parse(s).is_none(), "{s} should not parse");
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in [
+            "2026-01-01T00:00:00.000Z",
+            "2024-02-29T23:59:59.999+05:30",
+            "1900-03-01T12:00:00.000-08:00",
+        ] {
+            assert_eq!(parse(s).to_string(), s);
+        }
+    }
+
+    #[test]
+    fn equality_ignores_offset() {
+        assert_eq!(parse("2026-01-01T00:00Z"), parse("2026-01-01T01:00+01:00"));
+    }
+
+    #[test]
+    fn format_patterns() {
+        let inst = parse("2024-03-05T14:07:09.042+01:00");
+        assert_eq!(
+            inst.format("yyyy-MM-dd HH:mm:ss.SSS").unwrap(),
+            "2024-03-05 14:07:09.042"
+        );
+        assert_eq!(inst.format("d/M/yy h:m a").unwrap(), "5/3/24 2:7 PM");
+        assert_eq!(inst.format("EEE, dd MMM yyyy").unwrap(), "Tue, 05 Mar 2024");
+        assert_eq!(inst.format("EEEE MMMM").unwrap(), "Tuesday March");
+        assert_eq!(inst.format("XXX Z").unwrap(), "+01:00 +0100");
+        assert_eq!(inst.format("'T''s' HH").unwrap(), "T's 14");
+    }
+
+    #[test]
+    fn format_errors() {
+        let inst = Inst::from_millis(0);
+        assert!(inst.format("yyyy-QQ").is_err());
+        assert!(inst.format("'open").is_err());
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(parse_offset("Z"), Some(0));
+        assert_eq!(parse_offset("+05:30"), Some(330));
+        assert_eq!(parse_offset("-0800"), Some(-480));
+        assert_eq!(parse_offset("+02"), Some(120));
+        assert_eq!(parse_offset("05:30"), None);
+        assert_eq!(parse_offset("+24:00"), None);
+        assert_eq!(parse_offset("+1é2"), None);
+    }
+}
@@ -0,0 +1,222 @@
+use num_bigint::BigInt;
+
+use super::expect_arity;
+use super::math::{big, to_bigint};
+use crate::interpreter::{RuntimeError, Value};
+use crate::lexer::Span;
+
+fn integer_arg(arg: &(Value, Span)) -> Result<&Value, RuntimeError> {
+    match arg {
+        (v @ (Value::Long(_) | Value::BigInt(_)), _) => Ok(v),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "integer",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn bit_index_arg(arg: &(Value, Span)) -> Result<i64, RuntimeError> {
+    match arg {
+        (Value::Long(n), _) => Ok(*n),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "long",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+// Bigints have no width to wrap around, so their bit index must be >= 0
+fn big_index_arg(arg: &(Value, Span)) -> Result<u64, RuntimeError> {
+    match bit_index_arg(arg)? {
+        n if n < 0 => Err(RuntimeError::TypeError {
+            expected: "non-negative long",
+            got: "negative long",
+            span: arg.1,
+        }),
+        n => Ok(n as u64),
+    }
+}
+
+// Like the JVM, a long is shifted by the low 6 bits of the index only
+fn long_bit(n: i64) -> u32 {
+    (n & 63) as u32
+}
+
+fn fold_bits(
+    args: &[(Value, Span)],
+    span: Span,
+    on_longs: fn(i64, i64) -> i64,
+    on_bigs: fn(BigInt, BigInt) -> BigInt,
+) -> Result<Value, RuntimeError> {
+    if args.len() < 2 {
+        return Err(RuntimeError::WrongArity {
+            expected: 2,
+            got: args.len(),
+            span,
+        });
+    }
+    let first = integer_arg(&args[0])?.clone();
+    args[1..].iter().try_fold(first, |acc, arg| {
+        Ok(match (acc, integer_arg(arg)?) {
+            (Value::Long(a), Value::Long(b)) => Value::Long(on_longs(a, *b)),
+            (a, b) => big(on_bigs(to_bigint(&a), to_bigint(b))),
+        })
+    })
+}
+
+fn bit_and(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    fold_bits(args, span, |a, b| a & b, |a, b| a & b)
+}
+
+fn bit_or(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    fold_bits(args, span, |a, b| a | b, |a, b| a | b)
+}
+
+fn bit_xor(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    fold_bits(args, span, |a, b| a ^ b, |a, b| a ^ b)
+}
+
+fn bit_and_not(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    fold_bits(args, span, |a, b| a & !b, |a, b| a & !b)
+}
+
+fn bit_not(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match integer_arg(&args[0])? {
+        Value::Long(n) => Ok(Value::Long(!n)),
+        n => Ok(big(!to_bigint(n))),
+    }
+}
+
+fn bit_shift_left(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    match integer_arg(&args[0])? {
+        Value::Long(n) => Ok(Value::Long(
+            n.wrapping_shl(long_bit(bit_index_arg(&args[1])?)),
+        )),
+        n => Ok(big(to_bigint(n) << big_index_arg(&args[1])?)),
+    }
+}
+
+// Arithmetic shift: the sign bit is copied in
+fn bit_shift_right(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    match integer_arg(&args[0])? {
+        Value::Long(n) => Ok(Value::Long(
+            n.wrapping_shr(long_bit(bit_index_arg(&args[1])?)),
+        )),
+        n => Ok(big(to_bigint(n) >> big_index_arg(&args[1])?)),
+    }
+}
+
+// Logical shift: zeros are shifted in, so only fixed-width longs make sense
+fn unsigned_bit_shift_right(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    match &args[0] {
+        (Value::Long(n), _) => {
+            let shift = long_bit(bit_index_arg(&args[1])?);
+            Ok(Value::Long((*n as u64).wrapping_shr(shift) as i64))
+        }
+        (v, v_span) => Err(RuntimeError::TypeError {
+            expected: "long",
+            got: v.type_name(),
+            span: *v_span,
+        }),
+    }
+}
+
+fn bit_test(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    match integer_arg(&args[0])? {
+        Value::Long(n) => Ok(Value::Bool(
+            (n >> long_bit(bit_index_arg(&args[1])?)) & 1 == 1,
+        )),
+        n => Ok(Value::Bool(to_bigint(n).bit(big_index_arg(&args[1])?))),
+    }
+}
+
+fn change_bit(
+    args: &[(Value, Span)],
+    span: Span,
+    change: fn(bool) -> bool,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    match integer_arg(&args[0])? {
+        Value::Long(n) => {
+            let mask = 1i64 << long_bit(bit_index_arg(&args[1])?);
+            Ok(Value::Long(if change(n & mask != 0) {
+                n | mask
+            } else {
+                n & !mask
+            }))
+        }
+        n => {
+            let bit = big_index_arg(&args[1])?;
+            let mut n = to_bigint(n);
+            n.set_bit(bit, change(n.bit(bit)));
+            Ok(big(n))
+        }
+    }
+}
+
+fn bit_set(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    change_bit(args, span, |_| true)
+}
+
+fn bit_clear(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    change_bit(args, span, |_| false)
+}
+
+fn bit_flip(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    change_bit(args, span, |bit| !bit)
+}
+
+// Set bits of a long's 64-bit two's complement; for a negative bigint, the
+// bits that differ from the sign bit
+fn bit_count(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match integer_arg(&args[0])? {
+        Value::Long(n) => Ok(Value::Long(n.count_ones() as i64)),
+        n => {
+            let n = to_bigint(n);
+            let magnitude = if n.sign() == num_bigint::Sign::Minus {
+                (!n).magnitude().clone()
+            } else {
+                n.magnitude().clone()
+            };
+            Ok(Value::Long(magnitude.count_ones() as i64))
+        }
+    }
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("bit-and", Value::new_builtin("bit-and", bit_and)),
+        ("bit-or", Value::new_builtin("bit-or", bit_or)),
+        ("bit-xor", Value::new_builtin("bit-xor", bit_xor)),
+        (
+            "bit-and-not",
+            Value::new_builtin("bit-and-not", bit_and_not),
+        ),
+        ("bit-not", Value::new_builtin("bit-not", bit_not)),
+        (
+            "bit-shift-left",
+            Value::new_builtin("bit-shift-left", bit_shift_left),
+        ),
+        (
+            "bit-shift-right",
+            Value::new_builtin("bit-shift-right", bit_shift_right),
+        ),
+        (
+            "unsigned-bit-shift-right",
+            Value::new_builtin("unsigned-bit-shift-right", unsigned_bit_shift_right),
+        ),
+        ("bit-test", Value::new_builtin("bit-test", bit_test)),
+        ("bit-set", Value::new_builtin("bit-set", bit_set)),
+        ("bit-clear", Value::new_builtin("bit-clear", bit_clear)),
+        ("bit-flip", Value::new_builtin("bit-flip", bit_flip)),
+        ("bit-count", Value::new_builtin("bit-count", bit_count)),
+    ]
+}
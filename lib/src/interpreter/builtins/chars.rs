@@ -0,0 +1,62 @@
+use num_traits::ToPrimitive;
+
+use super::expect_arity;
+use crate::interpreter::{RuntimeError, Value};
+use crate::lexer::Span;
+
+fn char_conv(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match &args[0] {
+        (Value::Char(c), _) => Ok(Value::Char(*c)),
+        (Value::Long(n), n_span) => u32::try_from(*n)
+            .ok()
+            .and_then(char::from_u32)
+            .map(Value::Char)
+            .ok_or_else(|| RuntimeError::UnsupportedType {
+                t: format!("code point {n}"),
+                span: *n_span,
+            }),
+        (v, v_span) => Err(RuntimeError::TypeError {
+            expected: "char or long",
+            got: v.type_name(),
+            span: *v_span,
+        }),
+    }
+}
+
+fn int_conv(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match &args[0] {
+        (Value::Char(c), _) => Ok(Value::Long(*c as i64)),
+        (Value::Long(n), _) => Ok(Value::Long(*n)),
+        (Value::BigInt(n), n_span) => n
+            .to_i64()
+            .map(Value::Long)
+            .ok_or(RuntimeError::ArithmeticOverflow { span: *n_span }),
+        (Value::Ratio(r), r_span) => r
+            .trunc()
+            .to_integer()
+            .to_i64()
+            .map(Value::Long)
+            .ok_or(RuntimeError::ArithmeticOverflow { span: *r_span }),
+        (Value::Double(n), _) => Ok(Value::Long(*n as i64)),
+        (v, v_span) => Err(RuntimeError::TypeError {
+            expected: "char or number",
+            got: v.type_name(),
+            span: *v_span,
+        }),
+    }
+}
+
+fn is_char(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(Value::Bool(matches!(args[0].0, Value::Char(_))))
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("char", Value::new_builtin("char", char_conv)),
+        ("int", Value::new_builtin("int", int_conv)),
+        ("char?", Value::new_builtin("char?", is_char)),
+    ]
+}
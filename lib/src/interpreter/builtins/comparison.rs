@@ -1,3 +1,7 @@
+use std::cmp::Ordering;
+
+use super::expect_arity;
+use super::math::{compare_numbers, is_number};
 use crate::interpreter::{RuntimeError, Value};
 use crate::lexer::Span;
 
@@ -25,19 +29,67 @@ fn neq(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
 }
 
 fn num_cmp(a: &Value, b: &Value, span: Span) -> Result<std::cmp::Ordering, RuntimeError> {
-    match (a, b) {
-        (Value::Long(x), Value::Long(y)) => Ok(x.cmp(y)),
-        (Value::Long(x), Value::Double(y)) => Ok((*x as f64).total_cmp(y)),
-        (Value::Double(x), Value::Long(y)) => Ok(x.total_cmp(&(*y as f64))),
-        (Value::Double(x), Value::Double(y)) => Ok(x.total_cmp(y)),
-        (v, _) => Err(RuntimeError::TypeError {
+    compare_numbers(a, b).ok_or_else(|| {
+        let v = if is_number(a) { b } else { a };
+        RuntimeError::TypeError {
             expected: "number",
             got: v.type_name(),
             span,
-        }),
+        }
+    })
+}
+
+fn sequential(v: &Value) -> Option<Vec<&Value>> {
+    match v {
+        Value::List(l) => Some(l.iter().collect()),
+        Value::Vector(v) => Some(v.iter().collect()),
+        _ => None,
     }
 }
 
+/// Total order used by `compare`, `sort` and the sorted collections: nil sorts
+/// first, numbers compare across the tower, vectors and lists element by
+/// element. Values of unrelated types cannot be ordered.
+pub(super) fn compare_values(a: &Value, b: &Value, span: Span) -> Result<Ordering, RuntimeError> {
+    if let Some(ord) = compare_numbers(a, b) {
+        return Ok(ord);
+    }
+    match (a, b) {
+        (Value::Nil, Value::Nil) => Ok(Ordering::Equal),
+        (Value::Nil, _) => Ok(Ordering::Less),
+        (_, Value::Nil) => Ok(Ordering::Greater),
+        (Value::Bool(x), Value::Bool(y)) => Ok(x.cmp(y)),
+        (Value::Char(x), Value::Char(y)) => Ok(x.cmp(y)),
+        (Value::String(x), Value::String(y)) => Ok(x.cmp(y)),
+        (Value::Keyword(x), Value::Keyword(y)) => Ok(x.cmp(y)),
+        (Value::Symbol(x), Value::Symbol(y)) => Ok(x.cmp(y)),
+        (Value::Inst(x), Value::Inst(y)) => Ok(x.millis.cmp(&y.millis)),
+        _ => match (sequential(a), sequential(b)) {
+            (Some(xs), Some(ys)) => {
+                for (x, y) in xs.iter().zip(ys.iter()) {
+                    let ord = compare_values(x, y, span)?;
+                    if ord.is_ne() {
+                        return Ok(ord);
+                    }
+                }
+                Ok(xs.len().cmp(&ys.len()))
+            }
+            _ => Err(RuntimeError::TypeError {
+                expected: a.type_name(),
+                got: b.type_name(),
+                span,
+            }),
+        },
+    }
+}
+
+// (compare a b) => -1, 0 or 1
+fn compare(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let ord = compare_values(&args[0].0, &args[1].0, args[1].1)?;
+    Ok(Value::Long(ord as i64))
+}
+
 fn is_gt(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
     if args.is_empty() {
         return Err(RuntimeError::WrongArity {
@@ -105,6 +157,7 @@ fn is_ge(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
 pub fn builtins() -> Vec<(&'static str, Value)> {
     vec![
         ("=", Value::new_builtin("=", eq)),
+        ("compare", Value::new_builtin("compare", compare)),
         ("not=", Value::new_builtin("not=", neq)),
         (">", Value::new_builtin(">", is_gt)),
         (">=", Value::new_builtin(">=", is_ge)),
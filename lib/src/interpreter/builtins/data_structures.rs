@@ -1,5 +1,6 @@
 use std::rc::Rc;
 
+use crate::collections::RispQueue;
 use crate::interpreter::{RuntimeError, Value};
 use crate::lexer::Span;
 
@@ -13,6 +14,14 @@ fn vec(args: &[(Value, Span)], _: Span) -> Result<Value, RuntimeError> {
     )))
 }
 
+fn queue(args: &[(Value, Span)], _: Span) -> Result<Value, RuntimeError> {
+    Ok(Value::Queue(
+        args.iter()
+            .map(|t| t.0.clone())
+            .collect::<RispQueue<Value>>(),
+    ))
+}
+
 fn map(args: &[(Value, Span)], _: Span) -> Result<Value, RuntimeError> {
     Ok(Value::Map(Rc::new(
         args.chunks(2)
@@ -26,5 +35,6 @@ pub fn builtins() -> Vec<(&'static str, Value)> {
         ("list", Value::new_builtin("list", list)),
         ("vector", Value::new_builtin("vector", vec)),
         ("hash-map", Value::new_builtin("hash-map", map)),
+        ("queue", Value::new_builtin("queue", queue)),
     ]
 }
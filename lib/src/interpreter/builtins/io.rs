@@ -0,0 +1,240 @@
+use std::fs::{self, File, OpenOptions};
+use std::rc::Rc;
+
+use super::expect_arity;
+use crate::collections::RispList;
+use crate::interpreter::stream::{read_line_from, Stream};
+use crate::interpreter::{RuntimeError, Value};
+use crate::lexer::Span;
+
+fn path_arg(arg: &(Value, Span)) -> Result<&str, RuntimeError> {
+    match arg {
+        (Value::String(s), _) => Ok(s),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "string",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn stream_arg(arg: &(Value, Span)) -> Result<&Rc<Stream>, RuntimeError> {
+    match arg {
+        (Value::Stream(s), _) => Ok(s),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "stream",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+// Trailing `:append true` option shared by `spit` and `writer`
+fn append_opt(opts: &[(Value, Span)], span: Span) -> Result<bool, RuntimeError> {
+    match opts {
+        [] => Ok(false),
+        [(Value::Keyword(k), _), (v, _)] if k.as_ref() == "append" => Ok(v.is_truthy()),
+        [(k, k_span), _] => Err(RuntimeError::TypeError {
+            expected: ":append",
+            got: k.type_name(),
+            span: *k_span,
+        }),
+        _ => Err(RuntimeError::WrongArity {
+            expected: 2,
+            got: opts.len(),
+            span,
+        }),
+    }
+}
+
+fn open_for_write(path: &str, append: bool) -> std::io::Result<File> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+}
+
+fn slurp(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let path = path_arg(&args[0])?;
+    fs::read_to_string(path)
+        .map(|s| Value::String(s.into()))
+        .map_err(|e| RuntimeError::from_io(e, args[0].1))
+}
+
+// (spit path content)
+// (spit path content :append true)
+fn spit(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() < 2 {
+        return Err(RuntimeError::WrongArity {
+            expected: 2,
+            got: args.len(),
+            span,
+        });
+    }
+    let path = path_arg(&args[0])?;
+    let append = append_opt(&args[2..], span)?;
+    let content = args[1].0.to_string();
+
+    open_for_write(path, append)
+        .and_then(|mut f| std::io::Write::write_all(&mut f, content.as_bytes()))
+        .map(|_| Value::Nil)
+        .map_err(|e| RuntimeError::from_io(e, args[0].1))
+}
+
+// (read-line)     ; from stdin
+// (read-line rdr) ; from a reader stream
+fn read_line(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 0, 1, span)?;
+    let line = match args.first() {
+        None => {
+            read_line_from(&mut std::io::stdin().lock()).map_err(|e| RuntimeError::from_io(e, span))
+        }
+        Some(arg) => stream_arg(arg)?
+            .read_line()
+            .map_err(|e| RuntimeError::from_io(e, arg.1)),
+    }?;
+    Ok(line.map(|l| Value::String(l.into())).unwrap_or(Value::Nil))
+}
+
+fn line_seq(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let (arg, arg_span) = &args[0];
+    let stream = match arg {
+        Value::String(path) => File::open(path.as_ref())
+            .map(|f| Rc::new(Stream::reader(path, f)))
+            .map_err(|e| RuntimeError::from_io(e, *arg_span))?,
+        _ => stream_arg(&args[0])?.clone(),
+    };
+
+    let mut lines = vec![];
+    while let Some(line) = stream
+        .read_line()
+        .map_err(|e| RuntimeError::from_io(e, *arg_span))?
+    {
+        lines.push(Value::String(line.into()));
+    }
+    Ok(Value::List(lines.into_iter().collect::<RispList<Value>>()))
+}
+
+fn file_exists(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let path = path_arg(&args[0])?;
+    Ok(Value::Bool(std::path::Path::new(path).exists()))
+}
+
+fn list_dir(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let path = path_arg(&args[0])?;
+    let mut names = fs::read_dir(path)
+        .and_then(|entries| {
+            entries
+                .map(|e| e.map(|e| e.file_name().to_string_lossy().into_owned()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| RuntimeError::from_io(e, args[0].1))?;
+    names.sort();
+    Ok(Value::Vector(Rc::new(
+        names.into_iter().map(|n| Value::String(n.into())).collect(),
+    )))
+}
+
+// (delete-file path)
+// (delete-file path silently)
+fn delete_file(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 2, span)?;
+    let path = path_arg(&args[0])?;
+    let silently = args.get(1).is_some_and(|(v, _)| v.is_truthy());
+    let p = std::path::Path::new(path);
+    let result = if p.is_dir() {
+        fs::remove_dir(p)
+    } else {
+        fs::remove_file(p)
+    };
+    match result {
+        Ok(()) => Ok(Value::Bool(true)),
+        Err(_) if silently => Ok(Value::Bool(false)),
+        Err(e) => Err(RuntimeError::from_io(e, args[0].1)),
+    }
+}
+
+fn make_dirs(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let path = path_arg(&args[0])?;
+    fs::create_dir_all(path)
+        .map(|_| Value::Bool(true))
+        .map_err(|e| RuntimeError::from_io(e, args[0].1))
+}
+
+fn reader(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let path = path_arg(&args[0])?;
+    File::open(path)
+        .map(|f| Value::Stream(Rc::new(Stream::reader(path, f))))
+        .map_err(|e| RuntimeError::from_io(e, args[0].1))
+}
+
+// (writer path)
+// (writer path :append true)
+fn writer(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    if args.is_empty() {
+        return Err(RuntimeError::WrongArity {
+            expected: 1,
+            got: 0,
+            span,
+        });
+    }
+    let path = path_arg(&args[0])?;
+    let append = append_opt(&args[1..], span)?;
+    open_for_write(path, append)
+        .map(|f| Value::Stream(Rc::new(Stream::writer(path, f))))
+        .map_err(|e| RuntimeError::from_io(e, args[0].1))
+}
+
+fn write(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let stream = stream_arg(&args[0])?;
+    stream
+        .write_str(&args[1].0.to_string())
+        .map(|_| Value::Nil)
+        .map_err(|e| RuntimeError::from_io(e, args[0].1))
+}
+
+fn close(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    stream_arg(&args[0])?
+        .close()
+        .map(|_| Value::Nil)
+        .map_err(|e| RuntimeError::from_io(e, args[0].1))
+}
+
+fn is_closed(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(Value::Bool(stream_arg(&args[0])?.is_closed()))
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("slurp", Value::new_builtin("slurp", slurp)),
+        ("spit", Value::new_builtin("spit", spit)),
+        ("read-line", Value::new_builtin("read-line", read_line)),
+        ("line-seq", Value::new_builtin("line-seq", line_seq)),
+        (
+            "file-exists?",
+            Value::new_builtin("file-exists?", file_exists),
+        ),
+        ("list-dir", Value::new_builtin("list-dir", list_dir)),
+        (
+            "delete-file",
+            Value::new_builtin("delete-file", delete_file),
+        ),
+        ("make-dirs", Value::new_builtin("make-dirs", make_dirs)),
+        ("reader", Value::new_builtin("reader", reader)),
+        ("writer", Value::new_builtin("writer", writer)),
+        ("write", Value::new_builtin("write", write)),
+        ("close", Value::new_builtin("close", close)),
+        ("closed?", Value::new_builtin("closed?", is_closed)),
+    ]
+}
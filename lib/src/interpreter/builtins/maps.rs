@@ -0,0 +1,511 @@
+use std::rc::Rc;
+
+use super::expect_arity;
+use super::sequences::{conj_one, seq_items};
+use super::sorted::{entry, sorted_assoc, sorted_get, sorted_remove};
+use super::transients::{self, conj_into};
+use crate::interpreter::record::Record;
+use crate::interpreter::transient::Transient;
+use crate::interpreter::value::map_entries;
+use crate::interpreter::{Interpreter, RuntimeError, Value};
+use crate::lexer::Span;
+
+fn index_in(key: &Value, len: usize) -> Option<usize> {
+    match key {
+        Value::Long(n) if *n >= 0 && (*n as usize) < len => Some(*n as usize),
+        _ => None,
+    }
+}
+
+/// The stored key and value for `key`, `None` when it is missing or `coll`
+/// has no keys at all. Sets answer the element for both.
+pub(crate) fn lookup(
+    interpreter: &mut Interpreter,
+    coll: &Value,
+    key: &Value,
+    span: Span,
+) -> Result<Option<(Value, Value)>, RuntimeError> {
+    Ok(match coll {
+        Value::Map(m) => m.iter().find(|(k, _)| k == key).cloned(),
+        Value::SortedMap(m) => sorted_get(interpreter, m, key, span)?,
+        Value::Vector(v) => index_in(key, v.len()).map(|i| (key.clone(), v[i].clone())),
+        Value::String(s) => match key {
+            Value::Long(n) if *n >= 0 => s
+                .chars()
+                .nth(*n as usize)
+                .map(|c| (key.clone(), Value::Char(c))),
+            _ => None,
+        },
+        Value::Set(s) => s.iter().find(|x| *x == key).map(|x| (x.clone(), x.clone())),
+        Value::SortedSet(s) => sorted_get(interpreter, s, key, span)?.map(|(k, _)| (k.clone(), k)),
+        Value::Record(r) if r.rtype.is_record => r
+            .entries()
+            .find(|(k, _)| *k == key)
+            .map(|(k, v)| (k.clone(), v.clone())),
+        Value::Transient(t) => transients::read(t, span, |t| match t {
+            Transient::Map(m) => m.get(key).cloned(),
+            Transient::Vector(v) => index_in(key, v.len()).map(|i| (key.clone(), v[i].clone())),
+            Transient::Set(s) => s.get(key).map(|x| (x.clone(), x.clone())),
+        })?,
+        _ => None,
+    })
+}
+
+pub(super) fn assoc_one(
+    interpreter: &mut Interpreter,
+    (coll, coll_span): &(Value, Span),
+    key: Value,
+    value: Value,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    match coll {
+        Value::Nil => Ok(Value::Map(Rc::new(vec![(key, value)]))),
+        Value::Map(m) => {
+            let mut result = (**m).clone();
+            match result.iter_mut().find(|(k, _)| *k == key) {
+                Some(existing) => existing.1 = value,
+                None => result.push((key, value)),
+            }
+            Ok(Value::Map(Rc::new(result)))
+        }
+        Value::SortedMap(m) => sorted_assoc(interpreter, m, key, value, span),
+        Value::Record(r) if r.rtype.is_record => {
+            let mut result = Record::clone(r);
+            match r.rtype.fields.iter().position(|f| *f == key) {
+                Some(i) => result.values[i] = value,
+                None => match result.extra.iter_mut().find(|(k, _)| *k == key) {
+                    Some(existing) => existing.1 = value,
+                    None => result.extra.push((key, value)),
+                },
+            }
+            Ok(Value::Record(Rc::new(result)))
+        }
+        Value::Vector(v) => match key {
+            Value::Long(n) if n >= 0 && n as usize <= v.len() => {
+                let mut result = (**v).clone();
+                if n as usize == v.len() {
+                    result.push(value);
+                } else {
+                    result[n as usize] = value;
+                }
+                Ok(Value::Vector(Rc::new(result)))
+            }
+            Value::Long(n) if n >= 0 => Err(RuntimeError::IndexOutOfBounds {
+                max_accessible: v.len(),
+                got: n,
+                span,
+            }),
+            k => Err(RuntimeError::TypeError {
+                expected: "non-negative index",
+                got: k.type_name(),
+                span,
+            }),
+        },
+        v => Err(RuntimeError::TypeError {
+            expected: "map or vector",
+            got: v.type_name(),
+            span: *coll_span,
+        }),
+    }
+}
+
+fn dissoc_one(
+    interpreter: &mut Interpreter,
+    (coll, coll_span): &(Value, Span),
+    key: &Value,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    match coll {
+        Value::Nil => Ok(Value::Nil),
+        Value::Map(m) => Ok(Value::Map(Rc::new(
+            m.iter().filter(|(k, _)| k != key).cloned().collect(),
+        ))),
+        Value::SortedMap(m) => Ok(Value::SortedMap(Rc::new(sorted_remove(
+            interpreter,
+            m,
+            key,
+            span,
+        )?))),
+        // Without one of its fields a record is just a map
+        Value::Record(r) if r.rtype.is_record && r.rtype.fields.contains(key) => {
+            Ok(Value::Map(Rc::new(
+                r.entries()
+                    .filter(|(k, _)| *k != key)
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect(),
+            )))
+        }
+        Value::Record(r) if r.rtype.is_record => {
+            let mut result = Record::clone(r);
+            result.extra.retain(|(k, _)| k != key);
+            Ok(Value::Record(Rc::new(result)))
+        }
+        v => Err(RuntimeError::TypeError {
+            expected: "map",
+            got: v.type_name(),
+            span: *coll_span,
+        }),
+    }
+}
+
+pub(super) fn map_pairs(arg: &(Value, Span)) -> Result<Vec<(Value, Value)>, RuntimeError> {
+    match arg {
+        (Value::Nil, _) => Ok(vec![]),
+        (m @ (Value::Map(_) | Value::SortedMap(_)), _) => Ok(map_entries(m)
+            .into_iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()),
+        (Value::Record(r), _) if r.rtype.is_record => {
+            Ok(r.entries().map(|(k, v)| (k.clone(), v.clone())).collect())
+        }
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "map",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn call(
+    interpreter: &mut Interpreter,
+    f: &(Value, Span),
+    first: Value,
+    rest: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    let args = std::iter::once((first, f.1))
+        .chain(rest.iter().cloned())
+        .collect();
+    interpreter.call_value(&f.0, args, span)
+}
+
+// (get coll key)
+// (get coll key not-found)
+fn get(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 3, span)?;
+    let default = args.get(2).map_or(Value::Nil, |a| a.0.clone());
+    Ok(lookup(interpreter, &args[0].0, &args[1].0, args[1].1)?.map_or(default, |(_, v)| v))
+}
+
+// (get-in coll keys)
+// (get-in coll keys not-found)
+fn get_in(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 3, span)?;
+    let default = args.get(2).map_or(Value::Nil, |a| a.0.clone());
+    let mut current = args[0].0.clone();
+    for key in seq_items(&args[1].0, args[1].1)? {
+        match lookup(interpreter, &current, &key, args[1].1)? {
+            Some((_, v)) => current = v,
+            None => return Ok(default),
+        }
+    }
+    Ok(current)
+}
+
+// (assoc coll k v & kvs)
+fn assoc(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    if args.len() < 3 || args.len().is_multiple_of(2) {
+        return Err(RuntimeError::WrongArity {
+            expected: 3,
+            got: args.len(),
+            span,
+        });
+    }
+    let mut result = args[0].clone();
+    for pair in args[1..].chunks(2) {
+        result.0 = assoc_one(
+            interpreter,
+            &result,
+            pair[0].0.clone(),
+            pair[1].0.clone(),
+            pair[0].1,
+        )?;
+    }
+    Ok(result.0)
+}
+
+fn assoc_in_path(
+    interpreter: &mut Interpreter,
+    coll: &(Value, Span),
+    path: &[Value],
+    value: Value,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    let Some((key, rest)) = path.split_first() else {
+        return assoc_one(interpreter, coll, Value::Nil, value, span);
+    };
+    if rest.is_empty() {
+        return assoc_one(interpreter, coll, key.clone(), value, span);
+    }
+    let inner = lookup(interpreter, &coll.0, key, span)?.map_or(Value::Nil, |(_, v)| v);
+    let updated = assoc_in_path(interpreter, &(inner, coll.1), rest, value, span)?;
+    assoc_one(interpreter, coll, key.clone(), updated, span)
+}
+
+// (assoc-in coll [k & ks] v)
+fn assoc_in(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 3, 3, span)?;
+    let path = seq_items(&args[1].0, args[1].1)?;
+    assoc_in_path(interpreter, &args[0], &path, args[2].0.clone(), args[1].1)
+}
+
+// (dissoc map & keys)
+fn dissoc(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, usize::MAX, span)?;
+    let mut result = args[0].clone();
+    for (key, key_span) in &args[1..] {
+        result.0 = dissoc_one(interpreter, &result, key, *key_span)?;
+    }
+    Ok(result.0)
+}
+
+// (update coll k f & args)
+fn update(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 3, usize::MAX, span)?;
+    let old =
+        lookup(interpreter, &args[0].0, &args[1].0, args[1].1)?.map_or(Value::Nil, |(_, v)| v);
+    let new = call(interpreter, &args[2], old, &args[3..], span)?;
+    assoc_one(interpreter, &args[0], args[1].0.clone(), new, args[1].1)
+}
+
+fn update_in_path(
+    interpreter: &mut Interpreter,
+    coll: &(Value, Span),
+    path: &[Value],
+    f: &(Value, Span),
+    extra: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    let (key, rest) = path.split_first().unwrap_or((&Value::Nil, &[]));
+    let inner = lookup(interpreter, &coll.0, key, span)?.map_or(Value::Nil, |(_, v)| v);
+    let updated = if rest.is_empty() {
+        call(interpreter, f, inner, extra, span)?
+    } else {
+        update_in_path(interpreter, &(inner, coll.1), rest, f, extra, span)?
+    };
+    assoc_one(interpreter, coll, key.clone(), updated, span)
+}
+
+// (update-in coll [k & ks] f & args)
+fn update_in(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 3, usize::MAX, span)?;
+    let path = seq_items(&args[1].0, args[1].1)?;
+    update_in_path(interpreter, &args[0], &path, &args[2], &args[3..], span)
+}
+
+// (merge & maps), later keys win; nil when every map is nil
+fn merge(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    _: Span,
+) -> Result<Value, RuntimeError> {
+    let mut maps = args.iter().filter(|(m, _)| !matches!(m, Value::Nil));
+    let Some(first) = maps.next() else {
+        return Ok(Value::Nil);
+    };
+    let mut result = first.clone();
+    for map in maps {
+        for (k, v) in map_pairs(map)? {
+            result.0 = assoc_one(interpreter, &result, k, v, map.1)?;
+        }
+    }
+    Ok(result.0)
+}
+
+// (merge-with f & maps) combines values of repeated keys with (f old new)
+fn merge_with(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, usize::MAX, span)?;
+    let f = &args[0];
+    let mut maps = args[1..].iter().filter(|(m, _)| !matches!(m, Value::Nil));
+    let Some(first) = maps.next() else {
+        return Ok(Value::Nil);
+    };
+    let mut result = first.clone();
+    for map in maps {
+        for (k, v) in map_pairs(map)? {
+            let merged = match lookup(interpreter, &result.0, &k, map.1)? {
+                Some((_, old)) => call(interpreter, f, old, &[(v, map.1)], span)?,
+                None => v,
+            };
+            result.0 = assoc_one(interpreter, &result, k, merged, map.1)?;
+        }
+    }
+    Ok(result.0)
+}
+
+// (select-keys map keys)
+fn select_keys(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let mut result = vec![];
+    for key in seq_items(&args[1].0, args[1].1)? {
+        if let Some(found) = lookup(interpreter, &args[0].0, &key, args[1].1)? {
+            if !result.iter().any(|(k, _)| *k == found.0) {
+                result.push(found);
+            }
+        }
+    }
+    Ok(Value::Map(Rc::new(result)))
+}
+
+fn keys(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let pairs = map_pairs(&args[0])?;
+    if pairs.is_empty() {
+        return Ok(Value::Nil);
+    }
+    Ok(Value::List(pairs.into_iter().map(|(k, _)| k).collect()))
+}
+
+fn vals(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let pairs = map_pairs(&args[0])?;
+    if pairs.is_empty() {
+        return Ok(Value::Nil);
+    }
+    Ok(Value::List(pairs.into_iter().map(|(_, v)| v).collect()))
+}
+
+// (contains? coll key) asks about keys: indices for vectors, members for sets
+fn contains(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    match &args[0].0 {
+        Value::Nil
+        | Value::Map(_)
+        | Value::SortedMap(_)
+        | Value::Set(_)
+        | Value::SortedSet(_)
+        | Value::Vector(_)
+        | Value::String(_)
+        | Value::Transient(_)
+        | Value::Record(_) => Ok(Value::Bool(
+            lookup(interpreter, &args[0].0, &args[1].0, args[1].1)?.is_some(),
+        )),
+        v => Err(RuntimeError::TypeError {
+            expected: "associative collection or set",
+            got: v.type_name(),
+            span: args[0].1,
+        }),
+    }
+}
+
+// (find map key) => [key value] or nil
+fn find(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    match &args[0].0 {
+        Value::Map(_) | Value::SortedMap(_) | Value::Vector(_) => {
+            Ok(lookup(interpreter, &args[0].0, &args[1].0, args[1].1)?
+                .map_or(Value::Nil, |(k, v)| entry(&k, &v)))
+        }
+        _ => Ok(Value::Nil),
+    }
+}
+
+// (zipmap keys vals)
+fn zipmap(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let mut result: Vec<(Value, Value)> = vec![];
+    let keys = seq_items(&args[0].0, args[0].1)?;
+    let vals = seq_items(&args[1].0, args[1].1)?;
+    for (k, v) in keys.into_iter().zip(vals) {
+        match result.iter_mut().find(|(existing, _)| *existing == k) {
+            Some(e) => e.1 = v,
+            None => result.push((k, v)),
+        }
+    }
+    Ok(Value::Map(Rc::new(result)))
+}
+
+// (into) => []
+// (into to) => to
+// (into to from) conjoins every item of `from` onto `to`
+fn into(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 0, 2, span)?;
+    match args {
+        [] => Ok(Value::Vector(Rc::new(vec![]))),
+        [to] => Ok(to.0.clone()),
+        [to, from] => {
+            // Vectors, maps and sets are filled in place instead of copied per item
+            if let Some(mut transient) = Transient::of(&to.0) {
+                for item in seq_items(&from.0, from.1)? {
+                    conj_into(&mut transient, &(item, from.1))?;
+                }
+                return Ok(transient.persistent());
+            }
+            let mut result = to.clone();
+            for item in seq_items(&from.0, from.1)? {
+                result.0 = conj_one(interpreter, &result, &(item, from.1))?;
+            }
+            Ok(result.0)
+        }
+        _ => unreachable!(),
+    }
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("get", Value::new_native("get", get)),
+        ("get-in", Value::new_native("get-in", get_in)),
+        ("assoc", Value::new_native("assoc", assoc)),
+        ("assoc-in", Value::new_native("assoc-in", assoc_in)),
+        ("dissoc", Value::new_native("dissoc", dissoc)),
+        ("update", Value::new_native("update", update)),
+        ("update-in", Value::new_native("update-in", update_in)),
+        ("merge", Value::new_native("merge", merge)),
+        ("merge-with", Value::new_native("merge-with", merge_with)),
+        ("select-keys", Value::new_native("select-keys", select_keys)),
+        ("keys", Value::new_builtin("keys", keys)),
+        ("vals", Value::new_builtin("vals", vals)),
+        ("contains?", Value::new_native("contains?", contains)),
+        ("find", Value::new_native("find", find)),
+        ("zipmap", Value::new_builtin("zipmap", zipmap)),
+        ("into", Value::new_native("into", into)),
+    ]
+}
@@ -1,69 +1,189 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+
+use super::expect_arity;
 use crate::interpreter::{RuntimeError, Value};
 use crate::lexer::Span;
 
-fn _mod(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
-    match args.len() {
-        2 => {
-            let (num_v, num_span) = &args[0];
-            let (div_v, _) = &args[1];
-            match (num_v, div_v) {
-                (Value::Double(n), Value::Double(d)) => Ok(Value::Double(n % d)),
-                (Value::Double(n), Value::Long(d)) => Ok(Value::Double(n % (*d as f64))),
-                (Value::Long(n), Value::Double(d)) => Ok(Value::Double((*n as f64) % d)),
-                (Value::Long(n), Value::Long(d)) => Ok(Value::Long(n % d)),
-                (n, v) => Err(RuntimeError::UnsupportedType {
-                    t: format!("{a} % {b}", a = n.type_name(), b = v.type_name()),
-                    span: *num_span,
-                }),
-            }
+#[derive(Clone, Copy)]
+pub(super) enum Op {
+    Add,
+    Sub,
+    Mul,
+}
+
+impl Op {
+    fn longs(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Op::Add => a.checked_add(b),
+            Op::Sub => a.checked_sub(b),
+            Op::Mul => a.checked_mul(b),
         }
-        n => Err(RuntimeError::WrongArity {
-            expected: 2,
-            got: n,
-            span,
-        }),
     }
+
+    fn bigs(self, a: &BigInt, b: &BigInt) -> BigInt {
+        match self {
+            Op::Add => a + b,
+            Op::Sub => a - b,
+            Op::Mul => a * b,
+        }
+    }
+
+    fn ratios(self, a: &BigRational, b: &BigRational) -> BigRational {
+        match self {
+            Op::Add => a + b,
+            Op::Sub => a - b,
+            Op::Mul => a * b,
+        }
+    }
+
+    fn doubles(self, a: f64, b: f64) -> f64 {
+        match self {
+            Op::Add => a + b,
+            Op::Sub => a - b,
+            Op::Mul => a * b,
+        }
+    }
+}
+
+pub(super) fn big(n: BigInt) -> Value {
+    Value::BigInt(Rc::new(n))
 }
 
-fn sum(args: &[(Value, Span)], _: Span) -> Result<Value, RuntimeError> {
-    let mut has_float = false;
-    let mut result_int: i64 = 0;
-    let mut result_float: f64 = 0.0;
-
-    for (arg, span) in args {
-        match arg {
-            Value::Long(v) => {
-                if has_float {
-                    result_float += *v as f64;
-                } else {
-                    result_int += *v;
-                }
-                Ok(())
-            }
-            Value::Double(v) => {
-                if !has_float {
-                    has_float = true;
-                    result_float = result_int as f64;
-                }
-                result_float += *v;
-                Ok(())
-            }
-            w => Err(RuntimeError::TypeError {
-                expected: "long or double",
-                got: w.type_name(),
-                span: *span,
-            }),
-        }?;
-    }
-
-    if has_float {
-        Ok(Value::Double(result_float))
+pub(super) fn integer(n: BigInt) -> Value {
+    n.to_i64().map(Value::Long).unwrap_or_else(|| big(n))
+}
+
+// A ratio with denominator 1 is not kept as a ratio but collapses to a long
+// (or a bigint when it does not fit)
+pub(super) fn ratio_value(r: BigRational) -> Value {
+    if r.is_integer() {
+        integer(r.to_integer())
     } else {
-        Ok(Value::Long(result_int))
+        Value::Ratio(Rc::new(r))
     }
 }
 
-fn minus(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+// Callers make sure `v` is an integer (long or bigint)
+pub(super) fn to_bigint(v: &Value) -> BigInt {
+    match v {
+        Value::Long(n) => BigInt::from(*n),
+        Value::BigInt(n) => n.as_ref().clone(),
+        _ => BigInt::zero(),
+    }
+}
+
+// Callers make sure `v` is rational (long, bigint or ratio)
+fn to_ratio(v: &Value) -> BigRational {
+    match v {
+        Value::Ratio(r) => r.as_ref().clone(),
+        v => BigRational::from_integer(to_bigint(v)),
+    }
+}
+
+pub(super) fn to_f64(v: &Value) -> f64 {
+    match v {
+        Value::Long(n) => *n as f64,
+        Value::BigInt(n) => n.to_f64().unwrap_or(f64::NAN),
+        Value::Ratio(r) => r.to_f64().unwrap_or(f64::NAN),
+        Value::Double(n) => *n,
+        _ => f64::NAN,
+    }
+}
+
+pub(super) fn is_number(v: &Value) -> bool {
+    matches!(
+        v,
+        Value::Long(_) | Value::BigInt(_) | Value::Ratio(_) | Value::Double(_)
+    )
+}
+
+pub(super) fn is_negative(v: &Value) -> bool {
+    match v {
+        Value::Long(n) => *n < 0,
+        Value::BigInt(n) => n.is_negative(),
+        Value::Ratio(r) => r.is_negative(),
+        Value::Double(n) => *n < 0.0,
+        _ => false,
+    }
+}
+
+/// Orders two numbers of any type, `None` if either one is not a number.
+pub(super) fn compare_numbers(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        _ if !is_number(a) || !is_number(b) => None,
+        (Value::Long(x), Value::Long(y)) => Some(x.cmp(y)),
+        (Value::Double(_), _) | (_, Value::Double(_)) => Some(to_f64(a).total_cmp(&to_f64(b))),
+        (Value::Ratio(_), _) | (_, Value::Ratio(_)) => Some(to_ratio(a).cmp(&to_ratio(b))),
+        _ => Some(to_bigint(a).cmp(&to_bigint(b))),
+    }
+}
+
+pub(super) fn number_arg(arg: &(Value, Span)) -> Result<&Value, RuntimeError> {
+    match arg {
+        (v, _) if is_number(v) => Ok(v),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "number",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+pub(super) fn is_zero(v: &Value) -> bool {
+    match v {
+        Value::Long(n) => *n == 0,
+        Value::BigInt(n) => n.is_zero(),
+        Value::Ratio(r) => r.is_zero(),
+        Value::Double(n) => *n == 0.0,
+        _ => false,
+    }
+}
+
+// Contagion goes long -> bigint -> ratio -> double: the result takes the
+// widest type of the two operands. A long result that does not fit is
+// promoted to a bigint by `+'` & co. and is an error for the strict operators
+pub(super) fn arith(
+    op: Op,
+    a: &Value,
+    b: &Value,
+    promote: bool,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    match (a, b) {
+        (Value::Long(x), Value::Long(y)) => match op.longs(*x, *y) {
+            Some(n) => Ok(Value::Long(n)),
+            None if promote => Ok(big(op.bigs(&BigInt::from(*x), &BigInt::from(*y)))),
+            None => Err(RuntimeError::ArithmeticOverflow { span }),
+        },
+        (Value::Double(_), _) | (_, Value::Double(_)) => {
+            Ok(Value::Double(op.doubles(to_f64(a), to_f64(b))))
+        }
+        (Value::Ratio(_), _) | (_, Value::Ratio(_)) => {
+            Ok(ratio_value(op.ratios(&to_ratio(a), &to_ratio(b))))
+        }
+        _ => Ok(big(op.bigs(&to_bigint(a), &to_bigint(b)))),
+    }
+}
+
+fn fold(
+    op: Op,
+    init: Value,
+    args: &[(Value, Span)],
+    promote: bool,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    args.iter().try_fold(init, |acc, arg| {
+        arith(op, &acc, number_arg(arg)?, promote, span)
+    })
+}
+
+fn difference(args: &[(Value, Span)], promote: bool, span: Span) -> Result<Value, RuntimeError> {
     if args.is_empty() {
         return Err(RuntimeError::WrongArity {
             expected: 1,
@@ -72,96 +192,38 @@ fn minus(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
         });
     }
 
-    let (first, first_span) = &args[0];
-    let (mut has_float, mut result_int, mut result_float) = match first {
-        Value::Long(v) => (false, *v, 0.0f64),
-        Value::Double(v) => (true, 0i64, *v),
-        w => {
-            return Err(RuntimeError::TypeError {
-                expected: "long or double",
-                got: w.type_name(),
-                span: *first_span,
-            })
-        }
-    };
-
+    let first = number_arg(&args[0])?;
     if args.len() == 1 {
-        return if has_float {
-            Ok(Value::Double(-result_float))
-        } else {
-            Ok(Value::Long(-result_int))
+        return match first {
+            Value::Double(v) => Ok(Value::Double(-v)),
+            v => arith(Op::Sub, &Value::Long(0), v, promote, span),
         };
     }
+    fold(Op::Sub, first.clone(), &args[1..], promote, span)
+}
 
-    for (arg, span) in &args[1..] {
-        match arg {
-            Value::Long(v) => {
-                if has_float {
-                    result_float -= *v as f64;
-                } else {
-                    result_int -= *v;
-                }
-                Ok(())
-            }
-            Value::Double(v) => {
-                if !has_float {
-                    has_float = true;
-                    result_float = result_int as f64;
-                }
-                result_float -= *v;
-                Ok(())
-            }
-            w => Err(RuntimeError::TypeError {
-                expected: "long or double",
-                got: w.type_name(),
-                span: *span,
-            }),
-        }?;
-    }
-
-    if has_float {
-        Ok(Value::Double(result_float))
-    } else {
-        Ok(Value::Long(result_int))
-    }
-}
-
-fn times(args: &[(Value, Span)], _: Span) -> Result<Value, RuntimeError> {
-    let mut has_float = false;
-    let mut result_int: i64 = 1;
-    let mut result_float: f64 = 1.0;
-
-    for (arg, span) in args {
-        match arg {
-            Value::Long(v) => {
-                if has_float {
-                    result_float *= *v as f64;
-                } else {
-                    result_int *= *v;
-                }
-                Ok(())
-            }
-            Value::Double(v) => {
-                if !has_float {
-                    has_float = true;
-                    result_float = result_int as f64;
-                }
-                result_float *= *v;
-                Ok(())
-            }
-            w => Err(RuntimeError::TypeError {
-                expected: "long or double",
-                got: w.type_name(),
-                span: *span,
-            }),
-        }?;
-    }
-
-    if has_float {
-        Ok(Value::Double(result_float))
-    } else {
-        Ok(Value::Long(result_int))
-    }
+fn sum(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    fold(Op::Add, Value::Long(0), args, false, span)
+}
+
+fn sum_promoting(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    fold(Op::Add, Value::Long(0), args, true, span)
+}
+
+fn minus(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    difference(args, false, span)
+}
+
+fn minus_promoting(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    difference(args, true, span)
+}
+
+fn times(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    fold(Op::Mul, Value::Long(1), args, false, span)
+}
+
+fn times_promoting(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    fold(Op::Mul, Value::Long(1), args, true, span)
 }
 
 fn divide(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
@@ -173,21 +235,151 @@ fn divide(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
         });
     }
 
-    let (dividend, dividend_span) = args[0].clone();
-    let (divider, divider_span) = args[1].clone();
+    let dividend = number_arg(&args[0])?;
+    let divider = number_arg(&args[1])?;
 
+    // Integers that divide evenly stay integers, anything else is exact
     match (dividend, divider) {
-        (_, Value::Long(0)) => Err(RuntimeError::DivisionByZero(divider_span)),
-        (_, Value::Double(0.0)) => Err(RuntimeError::DivisionByZero(divider_span)),
-        (Value::Long(a), Value::Long(b)) => Ok(Value::Long(a / b)),
-        (Value::Long(a), Value::Double(b)) => Ok(Value::Double(a as f64 / b)),
-        (Value::Double(a), Value::Long(b)) => Ok(Value::Double(a / b as f64)),
-        (Value::Double(a), Value::Double(b)) => Ok(Value::Double(a / b)),
-        (v, _) => Err(RuntimeError::TypeError {
-            expected: "long or double",
+        (_, d) if is_zero(d) => Err(RuntimeError::DivisionByZero(args[1].1)),
+        (Value::Double(_), _) | (_, Value::Double(_)) => {
+            Ok(Value::Double(to_f64(dividend) / to_f64(divider)))
+        }
+        (Value::Long(a), Value::Long(b)) if a.wrapping_rem(*b) == 0 => a
+            .checked_div(*b)
+            .map(Value::Long)
+            .ok_or(RuntimeError::ArithmeticOverflow { span }),
+        (Value::Long(_) | Value::BigInt(_), Value::Long(_) | Value::BigInt(_))
+            if to_bigint(dividend).is_multiple_of(&to_bigint(divider)) =>
+        {
+            Ok(big(to_bigint(dividend) / to_bigint(divider)))
+        }
+        (a, b) => Ok(ratio_value(to_ratio(a) / to_ratio(b))),
+    }
+}
+
+fn division_args(args: &[(Value, Span)], span: Span) -> Result<(&Value, &Value), RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let num = number_arg(&args[0])?;
+    let div = number_arg(&args[1])?;
+    // A zero divisor is an error for every kind of number, as with `/`
+    if is_zero(div) {
+        return Err(RuntimeError::DivisionByZero(args[1].1));
+    }
+    Ok((num, div))
+}
+
+// Quotient rounded toward zero
+fn quot(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    let (num, div) = division_args(args, span)?;
+    match (num, div) {
+        (Value::Long(a), Value::Long(b)) => a
+            .checked_div(*b)
+            .map(Value::Long)
+            .ok_or(RuntimeError::ArithmeticOverflow { span }),
+        (Value::Double(_), _) | (_, Value::Double(_)) => {
+            Ok(Value::Double((to_f64(num) / to_f64(div)).trunc()))
+        }
+        (Value::Ratio(_), _) | (_, Value::Ratio(_)) => Ok(integer(
+            (to_ratio(num) / to_ratio(div)).trunc().to_integer(),
+        )),
+        (a, b) => Ok(big(to_bigint(a) / to_bigint(b))),
+    }
+}
+
+// Remainder of `quot`, it has the sign of the dividend
+fn remainder(num: &Value, div: &Value) -> Value {
+    match (num, div) {
+        (Value::Long(a), Value::Long(b)) => Value::Long(a.wrapping_rem(*b)),
+        (Value::Double(_), _) | (_, Value::Double(_)) => Value::Double(to_f64(num) % to_f64(div)),
+        (Value::Ratio(_), _) | (_, Value::Ratio(_)) => {
+            let (a, b) = (to_ratio(num), to_ratio(div));
+            let q = (&a / &b).trunc();
+            ratio_value(a - b * q)
+        }
+        (a, b) => big(to_bigint(a) % to_bigint(b)),
+    }
+}
+
+fn rem(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    let (num, div) = division_args(args, span)?;
+    Ok(remainder(num, div))
+}
+
+// Modulus rounded toward negative infinity, it has the sign of the divisor
+fn _mod(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    let (num, div) = division_args(args, span)?;
+    let r = remainder(num, div);
+    if !is_zero(&r) && is_negative(&r) != is_negative(div) {
+        return arith(Op::Add, &r, div, false, span);
+    }
+    Ok(r)
+}
+
+// (numerator 22/7) => 22; an integer is its own numerator
+fn numerator(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match &args[0] {
+        (Value::Ratio(r), _) => Ok(integer(r.numer().clone())),
+        (v @ (Value::Long(_) | Value::BigInt(_)), _) => Ok(v.clone()),
+        (v, v_span) => Err(RuntimeError::TypeError {
+            expected: "rational",
             got: v.type_name(),
-            span: dividend_span,
+            span: *v_span,
+        }),
+    }
+}
+
+fn denominator(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match &args[0] {
+        (Value::Ratio(r), _) => Ok(integer(r.denom().clone())),
+        (Value::Long(_) | Value::BigInt(_), _) => Ok(Value::Long(1)),
+        (v, v_span) => Err(RuntimeError::TypeError {
+            expected: "rational",
+            got: v.type_name(),
+            span: *v_span,
+        }),
+    }
+}
+
+// Exact value of the double as it prints, so (rationalize 0.1) is 1/10
+// rather than the binary fraction actually stored
+fn rationalize(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match number_arg(&args[0])? {
+        Value::Double(d) if !d.is_finite() => Err(RuntimeError::UnsupportedType {
+            t: format!("rationalize {d}"),
+            span: args[0].1,
         }),
+        Value::Double(d) => {
+            let printed = d.to_string();
+            let (int_part, frac_part) = printed.split_once('.').unwrap_or((&printed, ""));
+            let numer: BigInt = format!("{int_part}{frac_part}").parse().unwrap();
+            let denom = num_traits::pow(BigInt::from(10), frac_part.len());
+            Ok(ratio_value(BigRational::new(numer, denom)))
+        }
+        n => Ok(n.clone()),
+    }
+}
+
+fn bigint(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(RuntimeError::WrongArity {
+            expected: 1,
+            got: args.len(),
+            span,
+        });
+    }
+    match number_arg(&args[0])? {
+        Value::Double(d) => {
+            BigInt::from_f64(d.trunc())
+                .map(big)
+                .ok_or_else(|| RuntimeError::UnsupportedType {
+                    t: format!("bigint of {d}"),
+                    span: args[0].1,
+                })
+        }
+        n => Ok(big(to_bigint(n))),
     }
 }
 
@@ -196,7 +388,22 @@ pub fn builtins() -> Vec<(&'static str, Value)> {
         ("+", Value::new_builtin("+", sum)),
         ("-", Value::new_builtin("-", minus)),
         ("*", Value::new_builtin("*", times)),
+        ("+'", Value::new_builtin("+'", sum_promoting)),
+        ("-'", Value::new_builtin("-'", minus_promoting)),
+        ("*'", Value::new_builtin("*'", times_promoting)),
         ("/", Value::new_builtin("/", divide)),
+        ("quot", Value::new_builtin("quot", quot)),
+        ("rem", Value::new_builtin("rem", rem)),
         ("mod", Value::new_builtin("mod", _mod)),
+        ("numerator", Value::new_builtin("numerator", numerator)),
+        (
+            "denominator",
+            Value::new_builtin("denominator", denominator),
+        ),
+        (
+            "rationalize",
+            Value::new_builtin("rationalize", rationalize),
+        ),
+        ("bigint", Value::new_builtin("bigint", bigint)),
     ]
 }
@@ -1,10 +1,29 @@
-use super::value::Value;
+use super::value::{RuntimeError, Value};
+use crate::lexer::Span;
 
+mod bits;
+mod chars;
 mod comparison;
 mod data_structures;
+mod io;
+mod maps;
 mod math;
+mod multimethods;
+mod numeric;
+mod random;
+mod records;
+mod reduced;
+mod regexes;
 mod sequences;
+mod sets;
+mod sorted;
+mod sorting;
 mod stdio;
+mod strings;
+#[cfg(test)]
+mod test_bits;
+#[cfg(test)]
+mod test_chars;
 #[cfg(test)]
 mod test_comparison;
 #[cfg(test)]
@@ -12,16 +31,107 @@ mod test_data_structures;
 #[cfg(test)]
 mod test_hof;
 #[cfg(test)]
+mod test_io;
+#[cfg(test)]
+mod test_maps;
+#[cfg(test)]
 mod test_math;
 #[cfg(test)]
+mod test_multimethods;
+#[cfg(test)]
+mod test_numeric;
+#[cfg(test)]
+mod test_queues;
+#[cfg(test)]
+mod test_random;
+#[cfg(test)]
+mod test_records;
+#[cfg(test)]
+mod test_reduced;
+#[cfg(test)]
+mod test_regexes;
+#[cfg(test)]
+mod test_seq_library;
+#[cfg(test)]
 mod test_sequences;
+#[cfg(test)]
+mod test_sets;
+#[cfg(test)]
+mod test_sorted;
+#[cfg(test)]
+mod test_sorting;
+#[cfg(test)]
+mod test_stdio;
+#[cfg(test)]
+mod test_strings;
+#[cfg(test)]
+mod test_time;
+#[cfg(test)]
+mod test_transducers;
+#[cfg(test)]
+mod test_transients;
+mod time;
+mod transients;
+mod volatile;
+
+pub(crate) use maps::lookup;
+pub(crate) use sequences::seq_items;
+
+pub(crate) fn expect_arity(
+    args: &[(Value, Span)],
+    min: usize,
+    max: usize,
+    span: Span,
+) -> Result<(), RuntimeError> {
+    if args.len() < min || args.len() > max {
+        return Err(RuntimeError::WrongArity {
+            expected: if args.len() > max { max } else { min },
+            got: args.len(),
+            span,
+        });
+    }
+    Ok(())
+}
 
 pub fn builtins() -> Vec<(&'static str, Value)> {
     math::builtins()
         .into_iter()
         .chain(stdio::builtins())
         .chain(data_structures::builtins())
+        .chain(maps::builtins())
+        .chain(sets::builtins())
         .chain(sequences::builtins())
         .chain(comparison::builtins())
+        .chain(sorting::builtins())
+        .chain(sorted::builtins())
+        .chain(bits::builtins())
+        .chain(chars::builtins())
+        .chain(random::builtins())
+        .chain(regexes::builtins())
+        .chain(reduced::builtins())
+        .chain(volatile::builtins())
+        .chain(transients::builtins())
+        .chain(multimethods::builtins())
+        .chain(records::builtins())
         .collect()
 }
+
+pub fn io_builtins() -> Vec<(&'static str, Value)> {
+    io::builtins()
+}
+
+pub fn string_builtins() -> Vec<(&'static str, Value)> {
+    strings::builtins()
+}
+
+pub fn math_builtins() -> Vec<(&'static str, Value)> {
+    numeric::builtins()
+}
+
+pub fn set_builtins() -> Vec<(&'static str, Value)> {
+    sets::set_builtins()
+}
+
+pub fn time_builtins() -> Vec<(&'static str, Value)> {
+    time::builtins()
+}
@@ -0,0 +1,218 @@
+use std::rc::Rc;
+
+use super::expect_arity;
+use crate::interpreter::multimethod::MultiFn;
+use crate::interpreter::{Callable, Interpreter, RuntimeError, Value};
+use crate::lexer::Span;
+
+fn expect_multi((value, span): &(Value, Span)) -> Result<&MultiFn, RuntimeError> {
+    match value {
+        Value::Callable(c) => match c.as_ref() {
+            Callable::Multi(multi) => Ok(multi),
+            _ => Err(RuntimeError::TypeError {
+                expected: "multimethod",
+                got: value.type_name(),
+                span: *span,
+            }),
+        },
+        v => Err(RuntimeError::TypeError {
+            expected: "multimethod",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+// Hierarchy tags are keywords or symbols
+fn expect_tag((value, span): &(Value, Span)) -> Result<Value, RuntimeError> {
+    match value {
+        Value::Keyword(_) | Value::Symbol(_) => Ok(value.clone()),
+        v => Err(RuntimeError::TypeError {
+            expected: "keyword or symbol",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn set_or_nil(items: Vec<Value>) -> Value {
+    if items.is_empty() {
+        Value::Nil
+    } else {
+        Value::Set(Rc::new(items))
+    }
+}
+
+// (multi-fn name dispatch-fn & options), what `defmulti` expands to
+fn multi_fn(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, usize::MAX, span)?;
+    let name = match &elems[0].0 {
+        Value::String(s) => s.to_string(),
+        v => {
+            return Err(RuntimeError::TypeError {
+                expected: "string",
+                got: v.type_name(),
+                span: elems[0].1,
+            })
+        }
+    };
+    let mut default = Value::Keyword(Rc::from("default"));
+    for option in elems[2..].chunks(2) {
+        match option {
+            [(Value::Keyword(k), _), (value, _)] if &**k == "default" => default = value.clone(),
+            [(k, k_span), ..] => {
+                return Err(RuntimeError::TypeError {
+                    expected: ":default option",
+                    got: k.type_name(),
+                    span: *k_span,
+                })
+            }
+            [] => unreachable!(),
+        }
+    }
+    let multi = MultiFn::new(name, elems[1].0.clone(), default);
+    Ok(Value::Callable(Rc::new(Callable::Multi(Rc::new(multi)))))
+}
+
+// (add-method multi dispatch-value f), what `defmethod` expands to
+fn add_method(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 3, 3, span)?;
+    expect_multi(&elems[0])?.add_method(elems[1].0.clone(), elems[2].0.clone());
+    Ok(elems[0].0.clone())
+}
+
+fn remove_method(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    expect_multi(&elems[0])?.remove_method(&elems[1].0);
+    Ok(elems[0].0.clone())
+}
+
+// (prefer-method multi x y) picks x's method when both x and y match
+fn prefer_method(
+    interpreter: &mut Interpreter,
+    elems: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 3, 3, span)?;
+    let multi = expect_multi(&elems[0])?;
+    if !multi.prefer(
+        &interpreter.hierarchy,
+        elems[1].0.clone(),
+        elems[2].0.clone(),
+    ) {
+        return Err(RuntimeError::IllegalState {
+            message: "preference conflict in multimethod",
+            span,
+        });
+    }
+    Ok(elems[0].0.clone())
+}
+
+// Map from dispatch value to method
+fn methods(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(Value::Map(Rc::new(expect_multi(&elems[0])?.methods())))
+}
+
+// The method that would run for a dispatch value, nil when none would
+fn get_method(
+    interpreter: &mut Interpreter,
+    elems: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    let multi = expect_multi(&elems[0])?;
+    Ok(multi
+        .find_method(&interpreter.hierarchy, &elems[1].0)
+        .unwrap_or(Value::Nil))
+}
+
+// (derive tag parent) in the global hierarchy
+fn derive(
+    interpreter: &mut Interpreter,
+    elems: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    let (tag, parent) = (expect_tag(&elems[0])?, expect_tag(&elems[1])?);
+    if !interpreter.hierarchy.derive(tag, parent) {
+        return Err(RuntimeError::IllegalState {
+            message: "cyclic derivation",
+            span,
+        });
+    }
+    Ok(Value::Nil)
+}
+
+fn underive(
+    interpreter: &mut Interpreter,
+    elems: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    interpreter.hierarchy.underive(&elems[0].0, &elems[1].0);
+    Ok(Value::Nil)
+}
+
+fn isa(
+    interpreter: &mut Interpreter,
+    elems: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    Ok(Value::Bool(
+        interpreter.hierarchy.isa(&elems[0].0, &elems[1].0),
+    ))
+}
+
+fn parents(
+    interpreter: &mut Interpreter,
+    elems: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(set_or_nil(
+        interpreter.hierarchy.parents(&elems[0].0).to_vec(),
+    ))
+}
+
+fn ancestors(
+    interpreter: &mut Interpreter,
+    elems: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(set_or_nil(interpreter.hierarchy.ancestors(&elems[0].0)))
+}
+
+fn descendants(
+    interpreter: &mut Interpreter,
+    elems: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(set_or_nil(interpreter.hierarchy.descendants(&elems[0].0)))
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("multi-fn", Value::new_builtin("multi-fn", multi_fn)),
+        ("add-method", Value::new_builtin("add-method", add_method)),
+        (
+            "remove-method",
+            Value::new_builtin("remove-method", remove_method),
+        ),
+        (
+            "prefer-method",
+            Value::new_native("prefer-method", prefer_method),
+        ),
+        ("methods", Value::new_builtin("methods", methods)),
+        ("get-method", Value::new_native("get-method", get_method)),
+        ("derive", Value::new_native("derive", derive)),
+        ("underive", Value::new_native("underive", underive)),
+        ("isa?", Value::new_native("isa?", isa)),
+        ("parents", Value::new_native("parents", parents)),
+        ("ancestors", Value::new_native("ancestors", ancestors)),
+        ("descendants", Value::new_native("descendants", descendants)),
+    ]
+}
@@ -0,0 +1,247 @@
+use std::cmp::Ordering;
+
+use num_rational::BigRational;
+use num_traits::Signed;
+
+use super::expect_arity;
+use super::math::{
+    arith, big, compare_numbers, integer, is_negative, is_zero, number_arg, ratio_value, to_bigint,
+    to_f64, Op,
+};
+use crate::interpreter::{RuntimeError, Value};
+use crate::lexer::Span;
+
+fn double_arg(arg: &(Value, Span)) -> Result<f64, RuntimeError> {
+    number_arg(arg).map(to_f64)
+}
+
+// Functions that always answer a double, like the host's libm
+fn unary_double(
+    args: &[(Value, Span)],
+    span: Span,
+    f: fn(f64) -> f64,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(Value::Double(f(double_arg(&args[0])?)))
+}
+
+fn binary_double(
+    args: &[(Value, Span)],
+    span: Span,
+    f: fn(f64, f64) -> f64,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    Ok(Value::Double(f(
+        double_arg(&args[0])?,
+        double_arg(&args[1])?,
+    )))
+}
+
+fn sqrt(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    unary_double(args, span, f64::sqrt)
+}
+
+fn exp(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    unary_double(args, span, f64::exp)
+}
+
+fn log(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    unary_double(args, span, f64::ln)
+}
+
+fn log10(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    unary_double(args, span, f64::log10)
+}
+
+fn sin(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    unary_double(args, span, f64::sin)
+}
+
+fn cos(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    unary_double(args, span, f64::cos)
+}
+
+fn tan(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    unary_double(args, span, f64::tan)
+}
+
+fn asin(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    unary_double(args, span, f64::asin)
+}
+
+fn acos(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    unary_double(args, span, f64::acos)
+}
+
+fn atan(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    unary_double(args, span, f64::atan)
+}
+
+fn atan2(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    binary_double(args, span, f64::atan2)
+}
+
+fn hypot(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    binary_double(args, span, f64::hypot)
+}
+
+fn abs(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match number_arg(&args[0])? {
+        Value::Long(n) => n
+            .checked_abs()
+            .map(Value::Long)
+            .ok_or(RuntimeError::ArithmeticOverflow { span }),
+        Value::Double(n) => Ok(Value::Double(n.abs())),
+        Value::Ratio(r) => Ok(ratio_value(r.abs())),
+        n => Ok(big(to_bigint(n).abs())),
+    }
+}
+
+fn signum(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match number_arg(&args[0])? {
+        // f64::signum answers 1.0 for zero
+        Value::Double(n) if *n == 0.0 || n.is_nan() => Ok(Value::Double(*n)),
+        Value::Double(n) => Ok(Value::Double(n.signum())),
+        n if is_zero(n) => Ok(Value::Long(0)),
+        n if is_negative(n) => Ok(Value::Long(-1)),
+        _ => Ok(Value::Long(1)),
+    }
+}
+
+fn inc(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    arith(Op::Add, number_arg(&args[0])?, &Value::Long(1), false, span)
+}
+
+fn dec(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    arith(Op::Sub, number_arg(&args[0])?, &Value::Long(1), false, span)
+}
+
+// The argument itself is returned, so its type is kept; NaN wins like in the host
+fn extreme(args: &[(Value, Span)], span: Span, keep: Ordering) -> Result<Value, RuntimeError> {
+    if args.is_empty() {
+        return Err(RuntimeError::WrongArity {
+            expected: 1,
+            got: 0,
+            span,
+        });
+    }
+    let nums = args.iter().map(number_arg).collect::<Result<Vec<_>, _>>()?;
+    if let Some(nan) = nums
+        .iter()
+        .find(|v| matches!(v, Value::Double(n) if n.is_nan()))
+    {
+        return Ok((*nan).clone());
+    }
+    let mut best = nums[0];
+    for v in &nums[1..] {
+        if compare_numbers(v, best) == Some(keep) {
+            best = v;
+        }
+    }
+    Ok(best.clone())
+}
+
+fn min(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    extreme(args, span, Ordering::Less)
+}
+
+fn max(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    extreme(args, span, Ordering::Greater)
+}
+
+// (pow base exponent): exact when the base is rational and the exponent a
+// non-negative long, a double otherwise
+fn pow(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let base = number_arg(&args[0])?;
+    let exponent = number_arg(&args[1])?;
+    let exact = match exponent {
+        Value::Long(e) => u32::try_from(*e).ok(),
+        _ => None,
+    };
+    match (base, exact) {
+        (Value::Long(b), Some(e)) => b
+            .checked_pow(e)
+            .map(Value::Long)
+            .ok_or(RuntimeError::ArithmeticOverflow { span }),
+        (Value::BigInt(b), Some(e)) => Ok(big(num_traits::pow(b.as_ref().clone(), e as usize))),
+        (Value::Ratio(r), Some(e)) => {
+            Ok(ratio_value(num_traits::pow(r.as_ref().clone(), e as usize)))
+        }
+        (b, _) => Ok(Value::Double(to_f64(b).powf(to_f64(exponent)))),
+    }
+}
+
+// floor/ceil/round keep the type: integers come back as they are, doubles
+// stay doubles and ratios become integers
+fn rounding(
+    args: &[(Value, Span)],
+    span: Span,
+    on_double: fn(f64) -> f64,
+    on_ratio: fn(&BigRational) -> BigRational,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match number_arg(&args[0])? {
+        Value::Double(n) => Ok(Value::Double(on_double(*n))),
+        Value::Ratio(r) => Ok(integer(on_ratio(r).to_integer())),
+        n => Ok(n.clone()),
+    }
+}
+
+fn floor(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    rounding(args, span, f64::floor, |r| r.floor())
+}
+
+fn ceil(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    rounding(args, span, f64::ceil, |r| r.ceil())
+}
+
+// Halves round away from zero
+fn round(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    rounding(args, span, f64::round, |r| r.round())
+}
+
+fn is_nan(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(Value::Bool(double_arg(&args[0])?.is_nan()))
+}
+
+fn is_infinite(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(Value::Bool(double_arg(&args[0])?.is_infinite()))
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("PI", Value::Double(std::f64::consts::PI)),
+        ("E", Value::Double(std::f64::consts::E)),
+        ("abs", Value::new_builtin("abs", abs)),
+        ("signum", Value::new_builtin("signum", signum)),
+        ("inc", Value::new_builtin("inc", inc)),
+        ("dec", Value::new_builtin("dec", dec)),
+        ("min", Value::new_builtin("min", min)),
+        ("max", Value::new_builtin("max", max)),
+        ("pow", Value::new_builtin("pow", pow)),
+        ("sqrt", Value::new_builtin("sqrt", sqrt)),
+        ("exp", Value::new_builtin("exp", exp)),
+        ("log", Value::new_builtin("log", log)),
+        ("log10", Value::new_builtin("log10", log10)),
+        ("sin", Value::new_builtin("sin", sin)),
+        ("cos", Value::new_builtin("cos", cos)),
+        ("tan", Value::new_builtin("tan", tan)),
+        ("asin", Value::new_builtin("asin", asin)),
+        ("acos", Value::new_builtin("acos", acos)),
+        ("atan", Value::new_builtin("atan", atan)),
+        ("atan2", Value::new_builtin("atan2", atan2)),
+        ("hypot", Value::new_builtin("hypot", hypot)),
+        ("floor", Value::new_builtin("floor", floor)),
+        ("ceil", Value::new_builtin("ceil", ceil)),
+        ("round", Value::new_builtin("round", round)),
+        ("NaN?", Value::new_builtin("NaN?", is_nan)),
+        ("infinite?", Value::new_builtin("infinite?", is_infinite)),
+    ]
+}
@@ -0,0 +1,170 @@
+use std::rc::Rc;
+
+use super::expect_arity;
+use super::sequences::seq_items;
+use crate::interpreter::{Interpreter, RuntimeError, Value};
+use crate::lexer::Span;
+
+fn positive_long_arg(arg: &(Value, Span)) -> Result<u64, RuntimeError> {
+    match arg {
+        (Value::Long(n), _) if *n > 0 => Ok(*n as u64),
+        (Value::Long(_), span) => Err(RuntimeError::TypeError {
+            expected: "positive long",
+            got: "non-positive long",
+            span: *span,
+        }),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "long",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn set_seed(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match &args[0] {
+        (Value::Long(seed), _) => {
+            interpreter.set_seed(*seed as u64);
+            Ok(Value::Nil)
+        }
+        (v, v_span) => Err(RuntimeError::TypeError {
+            expected: "long",
+            got: v.type_name(),
+            span: *v_span,
+        }),
+    }
+}
+
+// (rand) => [0, 1)
+// (rand n) => [0, n)
+fn rand(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 0, 1, span)?;
+    let scale = match args.first() {
+        None => 1.0,
+        Some((Value::Long(n), _)) => *n as f64,
+        Some((Value::Double(n), _)) => *n,
+        Some((v, v_span)) => {
+            return Err(RuntimeError::TypeError {
+                expected: "long or double",
+                got: v.type_name(),
+                span: *v_span,
+            })
+        }
+    };
+    Ok(Value::Double(interpreter.rng.next_f64() * scale))
+}
+
+fn rand_int(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let n = positive_long_arg(&args[0])?;
+    Ok(Value::Long(interpreter.rng.below(n) as i64))
+}
+
+fn rand_nth(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let items = seq_items(&args[0].0, args[0].1)?;
+    if items.is_empty() {
+        return Err(RuntimeError::IndexOutOfBounds {
+            max_accessible: 0,
+            got: 0,
+            span: args[0].1,
+        });
+    }
+    let idx = interpreter.rng.below(items.len() as u64) as usize;
+    Ok(items[idx].clone())
+}
+
+// Fisher-Yates, always answers a vector
+fn shuffle(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let mut items = seq_items(&args[0].0, args[0].1)?;
+    for i in (1..items.len()).rev() {
+        let j = interpreter.rng.below(i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+    Ok(Value::Vector(Rc::new(items)))
+}
+
+// Keeps each item with probability `prob`, in order
+fn random_sample(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let prob = match &args[0] {
+        (Value::Long(n), _) => *n as f64,
+        (Value::Double(n), _) => *n,
+        (v, v_span) => {
+            return Err(RuntimeError::TypeError {
+                expected: "long or double",
+                got: v.type_name(),
+                span: *v_span,
+            })
+        }
+    };
+    let items = seq_items(&args[1].0, args[1].1)?;
+    Ok(Value::List(
+        items
+            .into_iter()
+            .filter(|_| interpreter.rng.next_f64() < prob)
+            .collect(),
+    ))
+}
+
+// Version 4 UUID as a string, drawn from the interpreter's generator so a
+// seeded run gets the same ids
+fn random_uuid(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 0, 0, span)?;
+    let hi = (interpreter.rng.next_u64() & !0xF000) | 0x4000;
+    let lo = (interpreter.rng.next_u64() & !(0xC << 60)) | (0x8 << 60);
+    let uuid = format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        hi >> 32,
+        (hi >> 16) & 0xFFFF,
+        hi & 0xFFFF,
+        lo >> 48,
+        lo & 0xFFFF_FFFF_FFFF
+    );
+    Ok(Value::String(uuid.into()))
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("set-seed!", Value::new_native("set-seed!", set_seed)),
+        ("rand", Value::new_native("rand", rand)),
+        ("rand-int", Value::new_native("rand-int", rand_int)),
+        ("rand-nth", Value::new_native("rand-nth", rand_nth)),
+        ("shuffle", Value::new_native("shuffle", shuffle)),
+        (
+            "random-sample",
+            Value::new_native("random-sample", random_sample),
+        ),
+        ("random-uuid", Value::new_native("random-uuid", random_uuid)),
+    ]
+}
@@ -0,0 +1,257 @@
+use std::rc::Rc;
+
+use super::expect_arity;
+use super::maps::map_pairs;
+use crate::interpreter::record::{Protocol, Record, RecordType, TypeTag};
+use crate::interpreter::{Callable, RuntimeError, Value};
+use crate::lexer::Span;
+
+fn expect_string((value, span): &(Value, Span)) -> Result<String, RuntimeError> {
+    match value {
+        Value::String(s) => Ok(s.to_string()),
+        v => Err(RuntimeError::TypeError {
+            expected: "string",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn expect_keywords((value, span): &(Value, Span)) -> Result<Vec<Value>, RuntimeError> {
+    match value {
+        Value::Vector(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::Keyword(_) => Ok(item.clone()),
+                v => Err(RuntimeError::TypeError {
+                    expected: "keyword",
+                    got: v.type_name(),
+                    span: *span,
+                }),
+            })
+            .collect(),
+        v => Err(RuntimeError::TypeError {
+            expected: "vector",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn expect_record_type((value, span): &(Value, Span)) -> Result<&Rc<RecordType>, RuntimeError> {
+    match value {
+        Value::Type(TypeTag::Record(t)) => Ok(t),
+        v => Err(RuntimeError::TypeError {
+            expected: "record type",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+// nil stands for its own type, as in `(extend-type nil ...)`
+fn expect_type((value, span): &(Value, Span)) -> Result<TypeTag, RuntimeError> {
+    match value {
+        Value::Type(t) => Ok(t.clone()),
+        Value::Nil => Ok(TypeTag::Builtin("nil")),
+        v => Err(RuntimeError::TypeError {
+            expected: "type",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn expect_protocol((value, span): &(Value, Span)) -> Result<&Rc<Protocol>, RuntimeError> {
+    match value {
+        Value::Protocol(p) => Ok(p),
+        v => Err(RuntimeError::TypeError {
+            expected: "protocol",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+// (record-type "Point" [:x :y] record?), what `defrecord` and `deftype` expand to
+fn record_type(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 3, 3, span)?;
+    Ok(Value::Type(TypeTag::Record(Rc::new(RecordType {
+        name: expect_string(&elems[0])?,
+        fields: expect_keywords(&elems[1])?,
+        is_record: elems[2].0.is_truthy(),
+    }))))
+}
+
+// (new-record Point [1 2]) with one value per field, for `->Point`
+fn new_record(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    let rtype = expect_record_type(&elems[0])?;
+    let values = match &elems[1].0 {
+        Value::Vector(v) if v.len() == rtype.fields.len() => (**v).clone(),
+        Value::Vector(v) => {
+            return Err(RuntimeError::WrongArity {
+                expected: rtype.fields.len(),
+                got: v.len(),
+                span,
+            })
+        }
+        v => {
+            return Err(RuntimeError::TypeError {
+                expected: "vector",
+                got: v.type_name(),
+                span: elems[1].1,
+            })
+        }
+    };
+    Ok(Value::Record(Rc::new(Record {
+        rtype: rtype.clone(),
+        values,
+        extra: vec![],
+    })))
+}
+
+// (map->record Point m): missing fields are nil, other keys are kept
+fn map_to_record(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    let rtype = expect_record_type(&elems[0])?;
+    let pairs = map_pairs(&elems[1])?;
+    let values = rtype
+        .fields
+        .iter()
+        .map(|f| {
+            pairs
+                .iter()
+                .find(|(k, _)| k == f)
+                .map_or(Value::Nil, |(_, v)| v.clone())
+        })
+        .collect();
+    let extra = pairs
+        .into_iter()
+        .filter(|(k, _)| !rtype.fields.contains(k))
+        .collect();
+    Ok(Value::Record(Rc::new(Record {
+        rtype: rtype.clone(),
+        values,
+        extra,
+    })))
+}
+
+// (field obj :x), what `(.-x obj)` expands to
+fn field(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    let found = match &elems[0].0 {
+        Value::Record(r) => r.field(&elems[1].0),
+        v => {
+            return Err(RuntimeError::TypeError {
+                expected: "record or type instance",
+                got: v.type_name(),
+                span: elems[0].1,
+            })
+        }
+    };
+    found.cloned().ok_or_else(|| RuntimeError::NoSuchField {
+        field: elems[1].0.to_string(),
+        type_name: elems[0].0.type_tag().name().to_string(),
+        span,
+    })
+}
+
+// (protocol "Shape" [:area]), what `defprotocol` expands to
+fn protocol(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    Ok(Value::Protocol(Rc::new(Protocol::new(
+        expect_string(&elems[0])?,
+        expect_keywords(&elems[1])?,
+    ))))
+}
+
+fn protocol_method(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    Ok(Value::Callable(Rc::new(Callable::ProtocolMethod {
+        protocol: expect_protocol(&elems[0])?.clone(),
+        method: elems[1].0.clone(),
+    })))
+}
+
+// (extend type proto {:method f} & more-protos-and-maps)
+fn extend(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    if elems.len() < 3 || elems.len().is_multiple_of(2) {
+        return Err(RuntimeError::WrongArity {
+            expected: 3,
+            got: elems.len(),
+            span,
+        });
+    }
+    let tag = expect_type(&elems[0])?;
+    for pair in elems[1..].chunks(2) {
+        let protocol = expect_protocol(&pair[0])?;
+        let methods = map_pairs(&pair[1])?;
+        if let Some((name, _)) = methods.iter().find(|(k, _)| !protocol.methods.contains(k)) {
+            return Err(RuntimeError::NoSuchField {
+                field: name.to_string(),
+                type_name: protocol.name.clone(),
+                span: pair[1].1,
+            });
+        }
+        protocol.extend(tag.clone(), methods);
+    }
+    Ok(Value::Nil)
+}
+
+fn satisfies(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    let protocol = expect_protocol(&elems[0])?;
+    Ok(Value::Bool(protocol.extends(&elems[1].0.type_tag())))
+}
+
+fn extends(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    let protocol = expect_protocol(&elems[0])?;
+    Ok(Value::Bool(protocol.extends(&expect_type(&elems[1])?)))
+}
+
+fn instance(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    Ok(Value::Bool(
+        expect_type(&elems[0])? == elems[1].0.type_tag(),
+    ))
+}
+
+fn type_of(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(Value::Type(elems[0].0.type_tag()))
+}
+
+fn is_record(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(Value::Bool(
+        matches!(&elems[0].0, Value::Record(r) if r.rtype.is_record),
+    ))
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        (
+            "record-type",
+            Value::new_builtin("record-type", record_type),
+        ),
+        ("new-record", Value::new_builtin("new-record", new_record)),
+        (
+            "map->record",
+            Value::new_builtin("map->record", map_to_record),
+        ),
+        ("field", Value::new_builtin("field", field)),
+        ("protocol", Value::new_builtin("protocol", protocol)),
+        (
+            "protocol-method",
+            Value::new_builtin("protocol-method", protocol_method),
+        ),
+        ("extend", Value::new_builtin("extend", extend)),
+        ("satisfies?", Value::new_builtin("satisfies?", satisfies)),
+        ("extends?", Value::new_builtin("extends?", extends)),
+        ("instance?", Value::new_builtin("instance?", instance)),
+        ("type", Value::new_builtin("type", type_of)),
+        ("record?", Value::new_builtin("record?", is_record)),
+    ]
+}
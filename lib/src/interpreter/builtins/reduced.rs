@@ -0,0 +1,32 @@
+use std::rc::Rc;
+
+use super::expect_arity;
+use crate::interpreter::{RuntimeError, Value};
+use crate::lexer::Span;
+
+// (reduced x) wraps x so that reduce stops and returns it
+fn reduced(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(Value::Reduced(Rc::new(elems[0].0.clone())))
+}
+
+fn is_reduced(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(Value::Bool(matches!(elems[0].0, Value::Reduced(_))))
+}
+
+fn unreduced(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    match &elems[0].0 {
+        Value::Reduced(v) => Ok((**v).clone()),
+        v => Ok(v.clone()),
+    }
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("reduced", Value::new_builtin("reduced", reduced)),
+        ("reduced?", Value::new_builtin("reduced?", is_reduced)),
+        ("unreduced", Value::new_builtin("unreduced", unreduced)),
+    ]
+}
@@ -0,0 +1,107 @@
+use std::rc::Rc;
+
+use regex::Captures;
+
+use super::expect_arity;
+use crate::interpreter::{RuntimeError, Value};
+use crate::lexer::Span;
+use crate::pattern::Pattern;
+
+pub(super) fn regex_arg(arg: &(Value, Span)) -> Result<Rc<Pattern>, RuntimeError> {
+    match arg {
+        (Value::Regex(re), _) => Ok(re.clone()),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "regex",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn text_arg(arg: &(Value, Span)) -> Result<&str, RuntimeError> {
+    match arg {
+        (Value::String(s), _) => Ok(s),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "string",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+/// The whole match as a string, or `[whole group1 ...]` when the pattern has
+/// groups; a group that did not take part in the match is nil.
+pub(super) fn match_value(caps: &Captures) -> Value {
+    if caps.len() == 1 {
+        return Value::String(caps[0].into());
+    }
+    let groups = caps
+        .iter()
+        .map(|g| g.map_or(Value::Nil, |m| Value::String(m.as_str().into())))
+        .collect();
+    Value::Vector(Rc::new(groups))
+}
+
+fn re_pattern(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match &args[0] {
+        (Value::Regex(re), _) => Ok(Value::Regex(re.clone())),
+        (Value::String(s), s_span) => {
+            Pattern::new(s)
+                .map(|re| Value::Regex(Rc::new(re)))
+                .map_err(|e| RuntimeError::InvalidRegex {
+                    message: e.to_string(),
+                    span: *s_span,
+                })
+        }
+        (v, v_span) => Err(RuntimeError::TypeError {
+            expected: "string or regex",
+            got: v.type_name(),
+            span: *v_span,
+        }),
+    }
+}
+
+fn re_find(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let re = regex_arg(&args[0])?;
+    let s = text_arg(&args[1])?;
+    Ok(re
+        .captures(s)
+        .map(|caps| match_value(&caps))
+        .unwrap_or(Value::Nil))
+}
+
+fn re_matches(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let re = regex_arg(&args[0])?;
+    let s = text_arg(&args[1])?;
+    let anchored = re.anchored().map_err(|e| RuntimeError::InvalidRegex {
+        message: e.to_string(),
+        span: args[0].1,
+    })?;
+    Ok(anchored
+        .captures(s)
+        .map(|caps| match_value(&caps))
+        .unwrap_or(Value::Nil))
+}
+
+fn re_seq(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let re = regex_arg(&args[0])?;
+    let s = text_arg(&args[1])?;
+    let matches: Vec<Value> = re.captures_iter(s).map(|caps| match_value(&caps)).collect();
+    if matches.is_empty() {
+        return Ok(Value::Nil);
+    }
+    Ok(Value::List(matches.into_iter().collect()))
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("re-pattern", Value::new_builtin("re-pattern", re_pattern)),
+        ("re-find", Value::new_builtin("re-find", re_find)),
+        ("re-matches", Value::new_builtin("re-matches", re_matches)),
+        ("re-seq", Value::new_builtin("re-seq", re_seq)),
+    ]
+}
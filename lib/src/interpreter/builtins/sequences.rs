@@ -1,25 +1,62 @@
 use std::rc::Rc;
 
+use super::expect_arity;
+use super::maps::{assoc_one, map_pairs};
+use super::sorted::{entry, sorted_assoc, sorted_conj};
+use super::transients;
 use crate::collections::RispList;
-use crate::interpreter::{RuntimeError, Value};
+use crate::interpreter::{Interpreter, RuntimeError, Value};
 use crate::lexer::Span;
 
-fn seq_to_list(v: Value) -> Value {
+pub(super) fn seq_to_list(v: Value) -> Value {
     match v {
         Value::Vector(vec) => Value::List(vec.iter().cloned().collect()),
         other => other,
     }
 }
 
-//(concat [1 2] [3 4])
-//(seq [])
+// Strings are sequences of their characters
+pub(super) fn char_value(c: char) -> Value {
+    Value::Char(c)
+}
+
+pub(crate) fn seq_items(col: &Value, span: Span) -> Result<Vec<Value>, RuntimeError> {
+    match col {
+        Value::Nil => Ok(vec![]),
+        Value::List(l) => Ok(l.iter().cloned().collect()),
+        Value::Queue(q) => Ok(q.iter().cloned().collect()),
+        Value::Vector(v) | Value::Set(v) => Ok((**v).clone()),
+        Value::Map(m) => Ok(m
+            .iter()
+            .map(|(k, v)| Value::Vector(Rc::new(vec![k.clone(), v.clone()])))
+            .collect()),
+        Value::SortedMap(m) => Ok(m.tree.iter().map(|(k, v)| entry(k, v)).collect()),
+        Value::SortedSet(s) => Ok(s.tree.iter().map(|(k, _)| k.clone()).collect()),
+        Value::String(s) => Ok(s.chars().map(char_value).collect()),
+        Value::Record(r) if r.rtype.is_record => {
+            Ok(r.entries().map(|(k, v)| entry(k, v)).collect())
+        }
+        v => Err(RuntimeError::TypeError {
+            expected: "seq",
+            got: v.type_name(),
+            span,
+        }),
+    }
+}
 
 fn count(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
     match elems.len() {
         1 => match elems[0].0.clone() {
+            Value::Nil => Ok(Value::Long(0)),
             Value::List(c) => Ok(Value::Long(c.len() as i64)),
+            Value::Queue(q) => Ok(Value::Long(q.len() as i64)),
+            Value::Record(r) if r.rtype.is_record => Ok(Value::Long(r.entries().count() as i64)),
             Value::Vector(c) | Value::Set(c) => Ok(Value::Long(c.len() as i64)),
             Value::Map(c) => Ok(Value::Long(c.len() as i64)),
+            Value::SortedMap(m) => Ok(Value::Long(m.tree.len() as i64)),
+            Value::SortedSet(s) => Ok(Value::Long(s.tree.len() as i64)),
+            Value::String(s) => Ok(Value::Long(s.chars().count() as i64)),
+            Value::Transient(t) => transients::read(&t, span, |t| Value::Long(t.len() as i64)),
             v => Err(RuntimeError::TypeError {
                 expected: "seq",
                 got: v.type_name(),
@@ -37,10 +74,12 @@ fn count(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
 fn first(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
     match elems.len() {
         1 => match (&elems[0].0, elems[0].1) {
+            (Value::Nil, _) => Ok(Value::Nil),
             (Value::List(c), _) => match c.first() {
                 Some(v) => Ok(v.clone()),
                 None => Ok(Value::Nil),
             },
+            (Value::Queue(q), _) => Ok(q.peek().cloned().unwrap_or(Value::Nil)),
             (Value::Set(c), _) | (Value::Vector(c), _) => match c.first() {
                 Some(v) => Ok(v.clone()),
                 None => Ok(Value::Nil),
@@ -49,6 +88,13 @@ fn first(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
                 Some(f) => Ok(Value::Vector(Rc::new(vec![f.0.clone(), f.1.clone()]))),
                 None => Ok(Value::Nil),
             },
+            (Value::SortedMap(m), _) => Ok(m.tree.first().map_or(Value::Nil, |(k, v)| entry(k, v))),
+            (Value::SortedSet(s), _) => Ok(s.tree.first().map_or(Value::Nil, |(k, _)| k.clone())),
+            (Value::String(s), _) => Ok(s.chars().next().map(char_value).unwrap_or(Value::Nil)),
+            (record @ Value::Record(_), span) => Ok(seq_items(record, span)?
+                .into_iter()
+                .next()
+                .unwrap_or(Value::Nil)),
             (value, span) => Err(RuntimeError::TypeError {
                 expected: "seq",
                 got: value.type_name(),
@@ -75,6 +121,7 @@ fn rest(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
     let col = elems.first().unwrap();
 
     match col {
+        (Value::Nil, _) => Ok(Value::List(RispList::empty())),
         (Value::List(c), _) => {
             if !c.is_empty() {
                 Ok(Value::List(c.rest()))
@@ -100,6 +147,11 @@ fn rest(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
                 Ok(Value::List(RispList::empty()))
             }
         }
+        (Value::Queue(q), _) => Ok(Value::List(q.pop().iter().cloned().collect())),
+        (sorted @ (Value::SortedMap(_) | Value::SortedSet(_) | Value::Record(_)), s) => Ok(
+            Value::List(seq_items(sorted, *s)?.into_iter().skip(1).collect()),
+        ),
+        (Value::String(s), _) => Ok(Value::List(s.chars().skip(1).map(char_value).collect())),
         (v, s) => Err(RuntimeError::TypeError {
             expected: "seq",
             got: v.type_name(),
@@ -139,6 +191,11 @@ fn second(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
                 Ok(Value::Vector(Rc::new(vec![k, v])))
             }
         }
+        (sorted @ (Value::SortedMap(_) | Value::SortedSet(_)), s) => Ok(seq_items(sorted, *s)?
+            .into_iter()
+            .nth(1)
+            .unwrap_or(Value::Nil)),
+        (Value::String(s), _) => Ok(s.chars().nth(1).map(char_value).unwrap_or(Value::Nil)),
         (v, s) => Err(RuntimeError::TypeError {
             expected: "seq",
             got: v.type_name(),
@@ -162,6 +219,9 @@ fn last(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
                 Some(f) => Ok(Value::Vector(Rc::new(vec![f.0.clone(), f.1.clone()]))),
                 None => Ok(Value::Nil),
             },
+            (Value::SortedMap(m), _) => Ok(m.tree.last().map_or(Value::Nil, |(k, v)| entry(k, v))),
+            (Value::SortedSet(s), _) => Ok(s.tree.last().map_or(Value::Nil, |(k, _)| k.clone())),
+            (Value::String(s), _) => Ok(s.chars().last().map(char_value).unwrap_or(Value::Nil)),
             (value, span) => Err(RuntimeError::TypeError {
                 expected: "seq",
                 got: value.type_name(),
@@ -198,7 +258,7 @@ fn nth(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
             Ok(Some(v)) => Ok(v.clone()),
             Ok(None) | Err(_) => Err(RuntimeError::IndexOutOfBounds {
                 max_accessible: c.len() - 1,
-                got: *n as usize,
+                got: *n,
                 span: *col_span,
             }),
         },
@@ -207,11 +267,19 @@ fn nth(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
                 Some(v) => Ok(v.clone()),
                 None => Err(RuntimeError::IndexOutOfBounds {
                     max_accessible: c.len() - 1,
-                    got: *n as usize,
+                    got: *n,
                     span: *col_span,
                 }),
             }
         }
+        (Value::String(s), Value::Long(n)) => match s.chars().nth(*n as usize) {
+            Some(c) => Ok(char_value(c)),
+            None => Err(RuntimeError::IndexOutOfBounds {
+                max_accessible: s.chars().count().saturating_sub(1),
+                got: *n,
+                span: *col_span,
+            }),
+        },
         (Value::Map(_), _) => Err(RuntimeError::UnsupportedType {
             t: col.type_name().to_string(),
             span: *col_span,
@@ -223,19 +291,31 @@ fn nth(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
     }
 }
 
-fn conj(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
-    if elems.len() != 2 {
-        return Err(RuntimeError::WrongArity {
+fn conj(
+    interpreter: &mut Interpreter,
+    elems: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    // (conj) and (conj coll) make conj usable as a reducing function
+    match elems {
+        [] => Ok(Value::Vector(Rc::new(vec![]))),
+        [(col, _)] => Ok(col.clone()),
+        [col, val] => conj_one(interpreter, col, val),
+        _ => Err(RuntimeError::WrongArity {
             expected: 2,
             got: elems.len(),
             span,
-        });
+        }),
     }
+}
 
-    let (col, col_span) = &elems[0];
-    let (val, val_span) = &elems[1];
-
+pub(super) fn conj_one(
+    interpreter: &mut Interpreter,
+    (col, col_span): &(Value, Span),
+    (val, val_span): &(Value, Span),
+) -> Result<Value, RuntimeError> {
     match col {
+        Value::Nil => Ok(Value::List(RispList::cons(val.clone(), &RispList::empty()))),
         Value::Vector(v) => {
             let result: Vec<Value> = v
                 .iter()
@@ -245,6 +325,7 @@ fn conj(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
             Ok(Value::Vector(Rc::new(result)))
         }
         Value::List(l) => Ok(Value::List(RispList::cons(val.clone(), l))),
+        Value::Queue(q) => Ok(Value::Queue(q.conj(val.clone()))),
         Value::Set(s) => {
             let val = seq_to_list(val.clone());
             let mut result: Vec<Value> = s.iter().cloned().collect();
@@ -282,6 +363,45 @@ fn conj(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
             }
             Ok(Value::Map(Rc::new(result)))
         }
+        Value::SortedSet(s) => sorted_conj(interpreter, s, val.clone(), *val_span),
+        Value::Record(r) if r.rtype.is_record => {
+            let pairs = match val {
+                Value::Vector(v) if v.len() == 2 => vec![(v[0].clone(), v[1].clone())],
+                _ => map_pairs(&(val.clone(), *val_span))?,
+            };
+            let mut result = (col.clone(), *col_span);
+            for (k, v) in pairs {
+                result.0 = assoc_one(interpreter, &result, k, v, *val_span)?;
+            }
+            Ok(result.0)
+        }
+        Value::SortedMap(m) => {
+            let pairs = match val {
+                Value::Vector(v) if v.len() == 2 => vec![(v[0].clone(), v[1].clone())],
+                Value::Map(_) | Value::SortedMap(_) => seq_items(val, *val_span)?
+                    .into_iter()
+                    .map(|e| match e {
+                        Value::Vector(kv) => (kv[0].clone(), kv[1].clone()),
+                        _ => unreachable!(),
+                    })
+                    .collect(),
+                v => {
+                    return Err(RuntimeError::TypeError {
+                        expected: "vector pair or map",
+                        got: v.type_name(),
+                        span: *val_span,
+                    })
+                }
+            };
+            let mut result = Value::SortedMap(m.clone());
+            for (k, v) in pairs {
+                let Value::SortedMap(current) = &result else {
+                    unreachable!()
+                };
+                result = sorted_assoc(interpreter, current, k, v, *val_span)?;
+            }
+            Ok(result)
+        }
         v => Err(RuntimeError::TypeError {
             expected: "seq",
             got: v.type_name(),
@@ -300,9 +420,15 @@ fn empty(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
     }
 
     match &elems.first().unwrap() {
+        (Value::Nil, _) => Ok(Value::Bool(true)),
         (Value::List(c), _) => Ok(Value::Bool(c.is_empty())),
+        (Value::Queue(q), _) => Ok(Value::Bool(q.is_empty())),
+        (Value::Record(r), _) if r.rtype.is_record => Ok(Value::Bool(r.entries().next().is_none())),
         (Value::Vector(c), _) | (Value::Set(c), _) => Ok(Value::Bool(c.is_empty())),
         (Value::Map(m), _) => Ok(Value::Bool(m.is_empty())),
+        (Value::SortedMap(m), _) => Ok(Value::Bool(m.tree.is_empty())),
+        (Value::SortedSet(s), _) => Ok(Value::Bool(s.tree.is_empty())),
+        (Value::String(s), _) => Ok(Value::Bool(s.is_empty())),
         (v, s) => Err(RuntimeError::TypeError {
             expected: "collection",
             got: v.type_name(),
@@ -324,6 +450,10 @@ fn cons(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
     let (col, col_span) = &elems[1];
 
     match (value, col) {
+        (_, Value::Nil) => Ok(Value::List(RispList::cons(
+            value.clone(),
+            &RispList::empty(),
+        ))),
         (_, Value::List(c)) => Ok(Value::List(RispList::cons(value.clone(), c))),
         (_, Value::Vector(c)) | (_, Value::Set(c)) => Ok(Value::List(
             std::iter::once(value.clone())
@@ -339,6 +469,16 @@ fn cons(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
                 std::iter::once(value.clone()).chain(map_col).collect(),
             ))
         }
+        (_, Value::String(s)) => Ok(Value::List(
+            std::iter::once(value.clone())
+                .chain(s.chars().map(char_value))
+                .collect(),
+        )),
+        (_, Value::SortedMap(_) | Value::SortedSet(_) | Value::Queue(_)) => Ok(Value::List(
+            std::iter::once(value.clone())
+                .chain(seq_items(col, *col_span)?)
+                .collect(),
+        )),
         (_, v) => Err(RuntimeError::TypeError {
             expected: "collection",
             got: v.type_name(),
@@ -347,6 +487,78 @@ fn cons(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
     }
 }
 
+fn reverse(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    let items = seq_items(&elems[0].0, elems[0].1)?;
+    Ok(Value::List(items.into_iter().rev().collect()))
+}
+
+// (seq coll) is nil for an empty collection and a list of its items otherwise
+fn seq(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    match &elems[0] {
+        (Value::List(l), _) if l.is_empty() => Ok(Value::Nil),
+        (list @ Value::List(_), _) => Ok(list.clone()),
+        (col, col_span) => {
+            let items = seq_items(col, *col_span)?;
+            if items.is_empty() {
+                Ok(Value::Nil)
+            } else {
+                Ok(Value::List(items.into_iter().collect()))
+            }
+        }
+    }
+}
+
+fn is_sequential(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(Value::Bool(matches!(
+        elems[0].0,
+        Value::List(_) | Value::Vector(_) | Value::Queue(_)
+    )))
+}
+
+// Lists and queues are used from the front, vectors from the back
+fn peek(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    match &elems[0] {
+        (Value::Nil, _) => Ok(Value::Nil),
+        (Value::List(l), _) => Ok(l.first().cloned().unwrap_or(Value::Nil)),
+        (Value::Vector(v), _) => Ok(v.last().cloned().unwrap_or(Value::Nil)),
+        (Value::Queue(q), _) => Ok(q.peek().cloned().unwrap_or(Value::Nil)),
+        (v, s) => Err(RuntimeError::TypeError {
+            expected: "list, vector or queue",
+            got: v.type_name(),
+            span: *s,
+        }),
+    }
+}
+
+// The counterpart of `peek`; popping an empty list or vector is an error,
+// an empty queue stays empty
+fn pop(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    match &elems[0] {
+        (Value::Nil, _) => Ok(Value::Nil),
+        (Value::List(l), _) if l.is_empty() => Err(RuntimeError::IllegalState {
+            message: "can't pop empty list",
+            span,
+        }),
+        (Value::List(l), _) => Ok(Value::List(l.rest())),
+        (Value::Vector(v), _) if v.is_empty() => Err(RuntimeError::IllegalState {
+            message: "can't pop empty vector",
+            span,
+        }),
+        (Value::Vector(v), _) => Ok(Value::Vector(Rc::new(v[..v.len() - 1].to_vec()))),
+        (Value::Queue(q), _) => Ok(Value::Queue(q.pop())),
+        (v, s) => Err(RuntimeError::TypeError {
+            expected: "list, vector or queue",
+            got: v.type_name(),
+            span: *s,
+        }),
+    }
+}
+
 pub fn builtins() -> Vec<(&'static str, Value)> {
     vec![
         ("count", Value::new_builtin("count", count)),
@@ -355,8 +567,16 @@ pub fn builtins() -> Vec<(&'static str, Value)> {
         ("second", Value::new_builtin("second", second)),
         ("last", Value::new_builtin("last", last)),
         ("nth", Value::new_builtin("nth", nth)),
-        ("conj", Value::new_builtin("conj", conj)),
+        ("conj", Value::new_native("conj", conj)),
         ("empty?", Value::new_builtin("empty?", empty)),
         ("cons", Value::new_builtin("cons", cons)),
+        ("reverse", Value::new_builtin("reverse", reverse)),
+        ("seq", Value::new_builtin("seq", seq)),
+        ("peek", Value::new_builtin("peek", peek)),
+        ("pop", Value::new_builtin("pop", pop)),
+        (
+            "sequential?",
+            Value::new_builtin("sequential?", is_sequential),
+        ),
     ]
 }
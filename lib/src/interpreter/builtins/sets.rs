@@ -0,0 +1,179 @@
+use std::rc::Rc;
+
+use super::expect_arity;
+use super::maps::lookup;
+use super::sequences::{conj_one, seq_items};
+use super::sorted::sorted_remove;
+use crate::interpreter::{Interpreter, RuntimeError, Value};
+use crate::lexer::Span;
+
+fn set_arg(arg: &(Value, Span)) -> Result<&Value, RuntimeError> {
+    match arg {
+        (v @ (Value::Set(_) | Value::SortedSet(_)), _) => Ok(v),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "set",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn member(
+    interpreter: &mut Interpreter,
+    set: &Value,
+    item: &Value,
+    span: Span,
+) -> Result<bool, RuntimeError> {
+    Ok(lookup(interpreter, set, item, span)?.is_some())
+}
+
+fn disj_one(
+    interpreter: &mut Interpreter,
+    set: &Value,
+    item: &Value,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    match set {
+        Value::Set(s) => Ok(Value::Set(Rc::new(
+            s.iter().filter(|x| *x != item).cloned().collect(),
+        ))),
+        Value::SortedSet(s) => Ok(Value::SortedSet(Rc::new(sorted_remove(
+            interpreter,
+            s,
+            item,
+            span,
+        )?))),
+        v => Err(RuntimeError::TypeError {
+            expected: "set",
+            got: v.type_name(),
+            span,
+        }),
+    }
+}
+
+// (disj set & items)
+fn disj(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, usize::MAX, span)?;
+    if matches!(args[0].0, Value::Nil) {
+        return Ok(Value::Nil);
+    }
+    let mut result = set_arg(&args[0])?.clone();
+    for (item, item_span) in &args[1..] {
+        result = disj_one(interpreter, &result, item, *item_span)?;
+    }
+    Ok(result)
+}
+
+// (union & sets) keeps the kind of the first set
+fn union(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    _: Span,
+) -> Result<Value, RuntimeError> {
+    let Some(first) = args.first() else {
+        return Ok(Value::Set(Rc::new(vec![])));
+    };
+    let mut result = (set_arg(first)?.clone(), first.1);
+    for arg in &args[1..] {
+        for item in seq_items(set_arg(arg)?, arg.1)? {
+            result.0 = conj_one(interpreter, &result, &(item, arg.1))?;
+        }
+    }
+    Ok(result.0)
+}
+
+// (intersection s1 & sets)
+fn intersection(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, usize::MAX, span)?;
+    let first = set_arg(&args[0])?;
+    for arg in &args[1..] {
+        set_arg(arg)?;
+    }
+    let mut result = first.clone();
+    for item in seq_items(first, args[0].1)? {
+        for (other, other_span) in &args[1..] {
+            if !member(interpreter, other, &item, *other_span)? {
+                result = disj_one(interpreter, &result, &item, *other_span)?;
+                break;
+            }
+        }
+    }
+    Ok(result)
+}
+
+// (difference s1 & sets)
+fn difference(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, usize::MAX, span)?;
+    let mut result = set_arg(&args[0])?.clone();
+    for arg in &args[1..] {
+        for item in seq_items(set_arg(arg)?, arg.1)? {
+            if member(interpreter, &result, &item, arg.1)? {
+                result = disj_one(interpreter, &result, &item, arg.1)?;
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn is_subset_of(
+    interpreter: &mut Interpreter,
+    small: &(Value, Span),
+    big: &(Value, Span),
+) -> Result<bool, RuntimeError> {
+    let big_set = set_arg(big)?;
+    for item in seq_items(set_arg(small)?, small.1)? {
+        if !member(interpreter, big_set, &item, big.1)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+// (subset? a b) => every item of a is in b
+fn is_subset(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    Ok(Value::Bool(is_subset_of(interpreter, &args[0], &args[1])?))
+}
+
+fn is_superset(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    Ok(Value::Bool(is_subset_of(interpreter, &args[1], &args[0])?))
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![("disj", Value::new_native("disj", disj))]
+}
+
+/// Functions of the `risp.set` namespace.
+pub fn set_builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("union", Value::new_native("union", union)),
+        (
+            "intersection",
+            Value::new_native("intersection", intersection),
+        ),
+        ("difference", Value::new_native("difference", difference)),
+        ("subset?", Value::new_native("subset?", is_subset)),
+        ("superset?", Value::new_native("superset?", is_superset)),
+    ]
+}
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use super::expect_arity;
+use super::sorting::compare_with;
+use crate::interpreter::sorted::Sorted;
+use crate::interpreter::{Callable, Interpreter, RuntimeError, Value};
+use crate::lexer::Span;
+
+pub(super) fn entry(k: &Value, v: &Value) -> Value {
+    Value::Vector(Rc::new(vec![k.clone(), v.clone()]))
+}
+
+pub(super) fn sorted_assoc(
+    interpreter: &mut Interpreter,
+    map: &Sorted<Value>,
+    key: Value,
+    value: Value,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    let comparator = map.comparator.as_ref();
+    let tree = map.tree.insert(key, value, &mut |a, b| {
+        compare_with(interpreter, comparator, a, b, span)
+    })?;
+    Ok(Value::SortedMap(Rc::new(map.with_tree(tree))))
+}
+
+pub(super) fn sorted_conj(
+    interpreter: &mut Interpreter,
+    set: &Sorted<()>,
+    item: Value,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    let comparator = set.comparator.as_ref();
+    let tree = set.tree.insert(item, (), &mut |a, b| {
+        compare_with(interpreter, comparator, a, b, span)
+    })?;
+    Ok(Value::SortedSet(Rc::new(set.with_tree(tree))))
+}
+
+/// The entry of `key` as stored (the key kept may differ from `key` under a
+/// custom comparator), `None` when missing.
+pub(super) fn sorted_get<V: Clone>(
+    interpreter: &mut Interpreter,
+    sorted: &Sorted<V>,
+    key: &Value,
+    span: Span,
+) -> Result<Option<(Value, V)>, RuntimeError> {
+    let comparator = sorted.comparator.as_ref();
+    let found = sorted.tree.get(key, &mut |a, b| {
+        compare_with(interpreter, comparator, a, b, span)
+    })?;
+    Ok(found.map(|(k, v)| (k.clone(), v.clone())))
+}
+
+pub(super) fn sorted_remove<V: Clone>(
+    interpreter: &mut Interpreter,
+    sorted: &Sorted<V>,
+    key: &Value,
+    span: Span,
+) -> Result<Sorted<V>, RuntimeError> {
+    let comparator = sorted.comparator.as_ref();
+    let tree = sorted.tree.remove(key, &mut |a, b| {
+        compare_with(interpreter, comparator, a, b, span)
+    })?;
+    Ok(sorted.with_tree(tree))
+}
+
+fn build_map(
+    interpreter: &mut Interpreter,
+    comparator: Option<Value>,
+    kvs: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    if !kvs.len().is_multiple_of(2) {
+        return Err(RuntimeError::WrongArity {
+            expected: kvs.len() + 1,
+            got: kvs.len(),
+            span,
+        });
+    }
+    let mut map = Value::SortedMap(Rc::new(Sorted::empty(comparator)));
+    for pair in kvs.chunks(2) {
+        let Value::SortedMap(m) = &map else {
+            unreachable!()
+        };
+        map = sorted_assoc(
+            interpreter,
+            m,
+            pair[0].0.clone(),
+            pair[1].0.clone(),
+            pair[0].1,
+        )?;
+    }
+    Ok(map)
+}
+
+fn build_set(
+    interpreter: &mut Interpreter,
+    comparator: Option<Value>,
+    items: &[(Value, Span)],
+) -> Result<Value, RuntimeError> {
+    let mut set = Value::SortedSet(Rc::new(Sorted::empty(comparator)));
+    for (item, item_span) in items {
+        let Value::SortedSet(s) = &set else {
+            unreachable!()
+        };
+        set = sorted_conj(interpreter, s, item.clone(), *item_span)?;
+    }
+    Ok(set)
+}
+
+fn sorted_map(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    build_map(interpreter, None, args, span)
+}
+
+fn sorted_map_by(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, usize::MAX, span)?;
+    build_map(interpreter, Some(args[0].0.clone()), &args[1..], span)
+}
+
+fn sorted_set(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    _: Span,
+) -> Result<Value, RuntimeError> {
+    build_set(interpreter, None, args)
+}
+
+fn sorted_set_by(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, usize::MAX, span)?;
+    build_set(interpreter, Some(args[0].0.clone()), &args[1..])
+}
+
+fn is_sorted(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(Value::Bool(matches!(
+        args[0].0,
+        Value::SortedMap(_) | Value::SortedSet(_)
+    )))
+}
+
+#[derive(Clone, Copy)]
+enum Test {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Test {
+    fn holds(self, ord: Ordering) -> bool {
+        match self {
+            Test::Lt => ord.is_lt(),
+            Test::Le => ord.is_le(),
+            Test::Gt => ord.is_gt(),
+            Test::Ge => ord.is_ge(),
+        }
+    }
+
+    fn is_lower_bound(self) -> bool {
+        matches!(self, Test::Gt | Test::Ge)
+    }
+
+    fn inclusive(self) -> bool {
+        matches!(self, Test::Le | Test::Ge)
+    }
+}
+
+// The test is one of the core `<`, `<=`, `>`, `>=` functions
+fn test_arg(arg: &(Value, Span)) -> Result<Test, RuntimeError> {
+    let name = match &arg.0 {
+        Value::Callable(c) => match c.as_ref() {
+            Callable::Builtin { name, .. } => Some(*name),
+            _ => None,
+        },
+        _ => None,
+    };
+    match name {
+        Some("<") => Ok(Test::Lt),
+        Some("<=") => Ok(Test::Le),
+        Some(">") => Ok(Test::Gt),
+        Some(">=") => Ok(Test::Ge),
+        _ => Err(RuntimeError::TypeError {
+            expected: "one of < <= > >=",
+            got: arg.0.type_name(),
+            span: arg.1,
+        }),
+    }
+}
+
+// Walks `tree` from the start bound (or an end of the tree) while the end bound
+// holds, in ascending order when `ascending`
+fn range<V: Clone>(
+    interpreter: &mut Interpreter,
+    sorted: &Sorted<V>,
+    start: Option<(Test, &Value)>,
+    end: Option<(Test, &Value)>,
+    ascending: bool,
+    item: impl Fn(&Value, &V) -> Value,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    let comparator = sorted.comparator.as_ref();
+    let walk = match start {
+        Some((test, key)) => sorted
+            .tree
+            .seek(key, test.inclusive(), ascending, &mut |a, b| {
+                compare_with(interpreter, comparator, a, b, span)
+            })?,
+        None if ascending => sorted.tree.iter(),
+        None => sorted.tree.iter_rev(),
+    };
+    let mut result = vec![];
+    for (k, v) in walk {
+        if let Some((test, key)) = end {
+            if !test.holds(compare_with(interpreter, comparator, k, key, span)?) {
+                break;
+            }
+        }
+        result.push(item(k, v));
+    }
+    Ok(Value::List(result.into_iter().collect()))
+}
+
+// (subseq sc test key)
+// (subseq sc start-test start-key end-test end-key)
+fn subseq_impl(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+    ascending: bool,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 3 && args.len() != 5 {
+        return Err(RuntimeError::WrongArity {
+            expected: 3,
+            got: args.len(),
+            span,
+        });
+    }
+    let first = (test_arg(&args[1])?, &args[2].0);
+    let bounds = match args.get(3) {
+        Some(test) => Some((test_arg(test)?, &args[4].0)),
+        None => None,
+    };
+    // Walking up, the lower bound is where to start; walking down, the upper
+    let (start, end) = match bounds {
+        Some(second) if ascending => (Some(first), Some(second)),
+        Some(second) => (Some(second), Some(first)),
+        None if first.0.is_lower_bound() == ascending => (Some(first), None),
+        None => (None, Some(first)),
+    };
+
+    match &args[0].0 {
+        Value::SortedMap(m) => range(interpreter, m, start, end, ascending, entry, span),
+        Value::SortedSet(s) => range(
+            interpreter,
+            s,
+            start,
+            end,
+            ascending,
+            |k, _| k.clone(),
+            span,
+        ),
+        v => Err(RuntimeError::TypeError {
+            expected: "sorted collection",
+            got: v.type_name(),
+            span: args[0].1,
+        }),
+    }
+}
+
+fn subseq(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    subseq_impl(interpreter, args, span, true)
+}
+
+fn rsubseq(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    subseq_impl(interpreter, args, span, false)
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("sorted-map", Value::new_native("sorted-map", sorted_map)),
+        (
+            "sorted-map-by",
+            Value::new_native("sorted-map-by", sorted_map_by),
+        ),
+        ("sorted-set", Value::new_native("sorted-set", sorted_set)),
+        (
+            "sorted-set-by",
+            Value::new_native("sorted-set-by", sorted_set_by),
+        ),
+        ("sorted?", Value::new_builtin("sorted?", is_sorted)),
+        ("subseq", Value::new_native("subseq", subseq)),
+        ("rsubseq", Value::new_native("rsubseq", rsubseq)),
+    ]
+}
@@ -0,0 +1,127 @@
+use std::cmp::Ordering;
+
+use super::comparison::compare_values;
+use super::expect_arity;
+use super::math::{compare_numbers, is_number};
+use super::sequences::seq_items;
+use crate::interpreter::{Interpreter, RuntimeError, Value};
+use crate::lexer::Span;
+
+/// Orders `a` and `b` with `comparator`, or with `compare` when there is none.
+/// A comparator may answer a number (negative, zero, positive) or, like `<`,
+/// a bool saying whether `a` goes first.
+pub(super) fn compare_with(
+    interpreter: &mut Interpreter,
+    comparator: Option<&Value>,
+    a: &Value,
+    b: &Value,
+    span: Span,
+) -> Result<Ordering, RuntimeError> {
+    let Some(comparator) = comparator else {
+        return compare_values(a, b, span);
+    };
+    let call = |interpreter: &mut Interpreter, x: &Value, y: &Value| {
+        interpreter.call_value(comparator, vec![(x.clone(), span), (y.clone(), span)], span)
+    };
+    match call(interpreter, a, b)? {
+        Value::Bool(true) => Ok(Ordering::Less),
+        Value::Bool(false) | Value::Nil => Ok(if call(interpreter, b, a)?.is_truthy() {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }),
+        n if is_number(&n) => Ok(compare_numbers(&n, &Value::Long(0)).unwrap()),
+        v => Err(RuntimeError::TypeError {
+            expected: "comparator result (number or bool)",
+            got: v.type_name(),
+            span,
+        }),
+    }
+}
+
+// Stable merge sort. `slice::sort_by` cannot stop on a comparator error and
+// may panic when a user comparator is not a total order.
+fn merge_sort<T: Clone>(
+    items: Vec<T>,
+    cmp: &mut impl FnMut(&T, &T) -> Result<Ordering, RuntimeError>,
+) -> Result<Vec<T>, RuntimeError> {
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+    let mut left = items;
+    let right = left.split_off(left.len() / 2);
+    let left = merge_sort(left, cmp)?;
+    let right = merge_sort(right, cmp)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        // Ties keep the left element first, which makes the sort stable
+        if cmp(&right[j], &left[i])?.is_lt() {
+            merged.push(right[j].clone());
+            j += 1;
+        } else {
+            merged.push(left[i].clone());
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&left[i..]);
+    merged.extend_from_slice(&right[j..]);
+    Ok(merged)
+}
+
+// (sort coll)
+// (sort comparator coll)
+fn sort(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 2, span)?;
+    let (comparator, coll) = match args {
+        [coll] => (None, coll),
+        [comparator, coll] => (Some(&comparator.0), coll),
+        _ => unreachable!(),
+    };
+    let items = seq_items(&coll.0, coll.1)?;
+    let sorted = merge_sort(items, &mut |a, b| {
+        compare_with(interpreter, comparator, a, b, span)
+    })?;
+    Ok(Value::List(sorted.into_iter().collect()))
+}
+
+// (sort-by keyfn coll)
+// (sort-by keyfn comparator coll)
+fn sort_by(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 3, span)?;
+    let (keyfn, comparator, coll) = match args {
+        [keyfn, coll] => (&keyfn.0, None, coll),
+        [keyfn, comparator, coll] => (&keyfn.0, Some(&comparator.0), coll),
+        _ => unreachable!(),
+    };
+    // Each key is computed once, not once per comparison
+    let keyed = seq_items(&coll.0, coll.1)?
+        .into_iter()
+        .map(|item| {
+            let key = interpreter.call_value(keyfn, vec![(item.clone(), coll.1)], span)?;
+            Ok((key, item))
+        })
+        .collect::<Result<Vec<_>, RuntimeError>>()?;
+    let sorted = merge_sort(keyed, &mut |(a, _), (b, _)| {
+        compare_with(interpreter, comparator, a, b, span)
+    })?;
+    Ok(Value::List(
+        sorted.into_iter().map(|(_, item)| item).collect(),
+    ))
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("sort", Value::new_native("sort", sort)),
+        ("sort-by", Value::new_native("sort-by", sort_by)),
+    ]
+}
@@ -1,7 +1,12 @@
-use crate::interpreter::{RuntimeError, Value};
+use crate::interpreter::{Interpreter, Readable, RuntimeError, Value};
 use crate::lexer::Span;
 
-fn write(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+// Writes to whatever `*out*` is currently bound to
+fn write(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
     if args.len() > 1 || args.is_empty() {
         return Err(RuntimeError::WrongArity {
             expected: 1,
@@ -11,8 +16,22 @@ fn write(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
     }
     match &args[0] {
         (Value::String(s), _) => {
-            print!("{}", s);
-            Ok(Value::Nil)
+            let out = interpreter.env.borrow().get_in_ns("risp.core", "*out*");
+            match out {
+                Some(Value::Stream(port)) => port
+                    .write_str(s)
+                    .map(|_| Value::Nil)
+                    .map_err(|e| RuntimeError::from_io(e, span)),
+                Some(v) => Err(RuntimeError::TypeError {
+                    expected: "stream",
+                    got: v.type_name(),
+                    span,
+                }),
+                None => Err(RuntimeError::UndefinedVariable {
+                    name: "*out*".to_string(),
+                    span,
+                }),
+            }
         }
         (v, s) => Err(RuntimeError::UnsupportedType {
             t: v.type_name().to_string(),
@@ -26,9 +45,19 @@ fn str_conv(args: &[(Value, Span)], _: Span) -> Result<Value, RuntimeError> {
     Ok(Value::String(result.into()))
 }
 
+fn pr_str(args: &[(Value, Span)], _: Span) -> Result<Value, RuntimeError> {
+    let result = args
+        .iter()
+        .map(|(v, _)| Readable(v).to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(Value::String(result.into()))
+}
+
 pub fn builtins() -> Vec<(&'static str, Value)> {
     vec![
-        ("write", Value::new_builtin("write", write)),
+        ("write", Value::new_native("write", write)),
         ("str", Value::new_builtin("str", str_conv)),
+        ("pr-str", Value::new_builtin("pr-str", pr_str)),
     ]
 }
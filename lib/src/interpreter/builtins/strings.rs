@@ -0,0 +1,278 @@
+use std::rc::Rc;
+
+use super::expect_arity;
+use super::regexes::match_value;
+use super::sequences::seq_items;
+use crate::interpreter::{Interpreter, RuntimeError, Value};
+use crate::lexer::Span;
+
+fn str_arg(arg: &(Value, Span)) -> Result<&str, RuntimeError> {
+    match arg {
+        (Value::String(s), _) => Ok(s),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "string",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn index_arg(arg: &(Value, Span)) -> Result<usize, RuntimeError> {
+    match arg {
+        (Value::Long(n), span) if *n < 0 => Err(RuntimeError::TypeError {
+            expected: "non-negative index",
+            got: "negative long",
+            span: *span,
+        }),
+        (Value::Long(n), _) => Ok(*n as usize),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "long",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+// Byte offset of the `idx`-th char; `idx == char count` maps to the end of `s`
+fn byte_offset(s: &str, idx: usize) -> Option<usize> {
+    s.char_indices()
+        .map(|(b, _)| b)
+        .chain(std::iter::once(s.len()))
+        .nth(idx)
+}
+
+fn char_index(s: &str, byte: usize) -> usize {
+    s[..byte].chars().count()
+}
+
+fn string(s: impl Into<Rc<str>>) -> Value {
+    Value::String(s.into())
+}
+
+fn strings_vector<'a>(parts: impl Iterator<Item = &'a str>) -> Value {
+    Value::Vector(Rc::new(parts.map(string).collect()))
+}
+
+// (subs s start)
+// (subs s start end)
+fn subs(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 3, span)?;
+    let s = str_arg(&args[0])?;
+    let len = s.chars().count();
+    let start = index_arg(&args[1])?;
+    let end = match args.get(2) {
+        Some(arg) => index_arg(arg)?,
+        None => len,
+    };
+
+    for (idx, idx_span) in [(start, args[1].1), (end, args.get(2).map_or(span, |a| a.1))] {
+        if idx > len {
+            return Err(RuntimeError::IndexOutOfBounds {
+                max_accessible: len,
+                got: idx as i64,
+                span: idx_span,
+            });
+        }
+    }
+    if start > end {
+        return Err(RuntimeError::IndexOutOfBounds {
+            max_accessible: end,
+            got: start as i64,
+            span: args[1].1,
+        });
+    }
+
+    let lo = byte_offset(s, start).unwrap();
+    let hi = byte_offset(s, end).unwrap();
+    Ok(string(&s[lo..hi]))
+}
+
+// (split s sep)
+// (split s sep limit)
+// `sep` is a plain string or a regex
+fn split(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 3, span)?;
+    let s = str_arg(&args[0])?;
+    let limit = args.get(2).map(index_arg).transpose()?.filter(|n| *n > 0);
+
+    let mut parts: Vec<&str> = match (&args[1].0, limit) {
+        (Value::Regex(re), Some(n)) => re.splitn(s, n).collect(),
+        (Value::Regex(re), None) => re.split(s).collect(),
+        (_, limit) => {
+            let sep = str_arg(&args[1])?;
+            match (sep.is_empty(), limit) {
+                (true, _) => s
+                    .char_indices()
+                    .map(|(i, c)| &s[i..i + c.len_utf8()])
+                    .collect(),
+                (false, Some(n)) => s.splitn(n, sep).collect(),
+                (false, None) => s.split(sep).collect(),
+            }
+        }
+    };
+    // Like Java's String.split, trailing empty parts are dropped without a limit
+    if limit.is_none() {
+        while parts.len() > 1 && parts.last() == Some(&"") {
+            parts.pop();
+        }
+    }
+    Ok(strings_vector(parts.into_iter()))
+}
+
+fn split_lines(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(strings_vector(str_arg(&args[0])?.lines()))
+}
+
+// (join coll)
+// (join sep coll)
+fn join(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 2, span)?;
+    let (sep, coll) = match args {
+        [coll] => ("", coll),
+        [sep, coll] => (str_arg(sep)?, coll),
+        _ => unreachable!(),
+    };
+    let parts: Vec<String> = seq_items(&coll.0, coll.1)?
+        .iter()
+        .map(|v| v.to_string())
+        .collect();
+    Ok(string(parts.join(sep)))
+}
+
+// (replace s match replacement)
+// With a regex `match` the replacement is a string that may refer to groups
+// as `$1`, or a function called with each match (as `re-find` returns it)
+fn replace(
+    interpreter: &mut Interpreter,
+    args: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(args, 3, 3, span)?;
+    let s = str_arg(&args[0])?;
+    let Value::Regex(re) = &args[1].0 else {
+        let from = str_arg(&args[1])?;
+        let to = str_arg(&args[2])?;
+        if from.is_empty() {
+            return Ok(string(s));
+        }
+        return Ok(string(s.replace(from, to)));
+    };
+    if let Value::String(to) = &args[2].0 {
+        return Ok(string(re.replace_all(s, to.as_ref())));
+    }
+
+    let mut result = String::new();
+    let mut last = 0;
+    for caps in re.captures_iter(s) {
+        let whole = caps.get(0).unwrap();
+        let replacement =
+            interpreter.call_value(&args[2].0, vec![(match_value(&caps), args[2].1)], span)?;
+        result.push_str(&s[last..whole.start()]);
+        result.push_str(&replacement.to_string());
+        last = whole.end();
+    }
+    result.push_str(&s[last..]);
+    Ok(string(result))
+}
+
+fn trim(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(string(str_arg(&args[0])?.trim()))
+}
+
+fn triml(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(string(str_arg(&args[0])?.trim_start()))
+}
+
+fn trimr(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(string(str_arg(&args[0])?.trim_end()))
+}
+
+fn upper_case(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(string(str_arg(&args[0])?.to_uppercase()))
+}
+
+fn lower_case(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(string(str_arg(&args[0])?.to_lowercase()))
+}
+
+// (index-of s value)
+// (index-of s value from)
+fn index_of(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 3, span)?;
+    let s = str_arg(&args[0])?;
+    let needle = str_arg(&args[1])?;
+    let from = args.get(2).map(index_arg).transpose()?.unwrap_or(0);
+
+    let Some(lo) = byte_offset(s, from) else {
+        return Ok(Value::Nil);
+    };
+    Ok(s[lo..]
+        .find(needle)
+        .map(|b| Value::Long(char_index(s, lo + b) as i64))
+        .unwrap_or(Value::Nil))
+}
+
+fn starts_with(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    Ok(Value::Bool(
+        str_arg(&args[0])?.starts_with(str_arg(&args[1])?),
+    ))
+}
+
+fn ends_with(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    Ok(Value::Bool(
+        str_arg(&args[0])?.ends_with(str_arg(&args[1])?),
+    ))
+}
+
+fn includes(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    Ok(Value::Bool(str_arg(&args[0])?.contains(str_arg(&args[1])?)))
+}
+
+fn is_blank(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    match &args[0] {
+        (Value::Nil, _) => Ok(Value::Bool(true)),
+        arg => Ok(Value::Bool(str_arg(arg)?.trim().is_empty())),
+    }
+}
+
+fn reverse(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(string(str_arg(&args[0])?.chars().rev().collect::<String>()))
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("subs", Value::new_builtin("subs", subs)),
+        ("split", Value::new_builtin("split", split)),
+        (
+            "split-lines",
+            Value::new_builtin("split-lines", split_lines),
+        ),
+        ("join", Value::new_builtin("join", join)),
+        ("replace", Value::new_native("replace", replace)),
+        ("trim", Value::new_builtin("trim", trim)),
+        ("triml", Value::new_builtin("triml", triml)),
+        ("trimr", Value::new_builtin("trimr", trimr)),
+        ("upper-case", Value::new_builtin("upper-case", upper_case)),
+        ("lower-case", Value::new_builtin("lower-case", lower_case)),
+        ("index-of", Value::new_builtin("index-of", index_of)),
+        (
+            "starts-with?",
+            Value::new_builtin("starts-with?", starts_with),
+        ),
+        ("ends-with?", Value::new_builtin("ends-with?", ends_with)),
+        ("includes?", Value::new_builtin("includes?", includes)),
+        ("blank?", Value::new_builtin("blank?", is_blank)),
+        ("reverse", Value::new_builtin("reverse", reverse)),
+    ]
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    #[test]
+    fn and_or_xor() {
+        assert!(matches!(run("(bit-and 0b1100 0b1010)"), Value::Long(8)));
+        assert!(matches!(run("(bit-or 0b1100 0b1010 1)"), Value::Long(15)));
+        assert!(matches!(run("(bit-xor 0b1100 0b1010)"), Value::Long(6)));
+        assert!(matches!(run("(bit-and-not 0b1100 0b1010)"), Value::Long(4)));
+    }
+
+    #[test]
+    fn and_needs_two_args() {
+        assert!(matches!(
+            run_err("(bit-and 1)"),
+            RuntimeError::WrongArity { expected: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn not() {
+        assert!(matches!(run("(bit-not 0)"), Value::Long(-1)));
+        assert_eq!(run("(bit-not 5N)"), run("-6N"));
+    }
+
+    #[test]
+    fn shifts() {
+        assert!(matches!(run("(bit-shift-left 1 10)"), Value::Long(1024)));
+        assert!(matches!(run("(bit-shift-right -16 2)"), Value::Long(-4)));
+        assert!(matches!(
+            run("(unsigned-bit-shift-right -1 60)"),
+            Value::Long(15)
+        ));
+    }
+
+    #[test]
+    fn long_shift_uses_low_six_bits() {
+        assert!(matches!(run("(bit-shift-left 1 65)"), Value::Long(2)));
+        assert!(matches!(
+            run("(bit-shift-left 1 63)"),
+            Value::Long(i64::MIN)
+        ));
+    }
+
+    #[test]
+    fn bigint_shifts_grow() {
+        assert_eq!(
+            run("(bit-shift-left 1N 100)"),
+            run("1267650600228229401496703205376N")
+        );
+        assert_eq!(run("(bit-shift-right -16N 2)"), run("-4N"));
+    }
+
+    #[test]
+    fn unsigned_shift_rejects_bigint() {
+        assert!(matches!(
+            run_err("(unsigned-bit-shift-right 1N 1)"),
+            RuntimeError::TypeError {
+                expected: "long",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_set_clear_flip() {
+        assert_eq!(run("(bit-test 0b100 2)"), Value::Bool(true));
+        assert_eq!(run("(bit-test 0b100 1)"), Value::Bool(false));
+        assert!(matches!(run("(bit-set 0 3)"), Value::Long(8)));
+        assert!(matches!(run("(bit-clear 0b1111 0)"), Value::Long(14)));
+        assert!(matches!(run("(bit-flip 0b1010 1)"), Value::Long(8)));
+        assert_eq!(run("(bit-set 0N 70)"), run("1180591620717411303424N"));
+        assert_eq!(run("(bit-test -1N 500)"), Value::Bool(true));
+    }
+
+    #[test]
+    fn bigint_negative_bit_index() {
+        assert!(matches!(
+            run_err("(bit-set 1N -1)"),
+            RuntimeError::TypeError {
+                expected: "non-negative long",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn count() {
+        assert!(matches!(run("(bit-count 0xFF)"), Value::Long(8)));
+        assert!(matches!(run("(bit-count -1)"), Value::Long(64)));
+        assert!(matches!(run("(bit-count 7N)"), Value::Long(3)));
+        assert!(matches!(run("(bit-count -8N)"), Value::Long(3)));
+    }
+
+    #[test]
+    fn mixed_long_and_bigint() {
+        assert_eq!(run("(bit-or 1 2N)"), run("3N"));
+    }
+
+    #[test]
+    fn type_error_on_double() {
+        assert!(matches!(
+            run_err("(bit-and 1 2.0)"),
+            RuntimeError::TypeError {
+                expected: "integer",
+                got: "double",
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.into())
+    }
+
+    // --- literals ---
+
+    #[test]
+    fn char_literal_evaluates_to_char() {
+        assert_eq!(run(r"\a"), Value::Char('a'));
+        assert_eq!(run(r"\newline"), Value::Char('\n'));
+        assert_eq!(run(r"\é"), Value::Char('é'));
+    }
+
+    #[test]
+    fn chars_compare_by_value() {
+        assert_eq!(run(r#"(= \a (first "abc"))"#), Value::Bool(true));
+        assert_eq!(run(r#"(= \a "a")"#), Value::Bool(false));
+    }
+
+    #[test]
+    fn chars_in_collections() {
+        assert!(matches!(run(r"[\a \space]"),
+            Value::Vector(v) if *v == vec![Value::Char('a'), Value::Char(' ')]));
+    }
+
+    // --- char / int / char? ---
+
+    #[test]
+    fn char_from_code_point() {
+        assert_eq!(run("(char 97)"), Value::Char('a'));
+        assert_eq!(run(r"(char \b)"), Value::Char('b'));
+    }
+
+    #[test]
+    fn char_invalid_code_point() {
+        assert!(matches!(
+            run_err("(char -1)"),
+            RuntimeError::UnsupportedType { .. }
+        ));
+        assert!(matches!(
+            run_err("(char 55296)"),
+            RuntimeError::UnsupportedType { .. }
+        ));
+    }
+
+    #[test]
+    fn char_type_error() {
+        assert!(matches!(
+            run_err("(char \"a\")"),
+            RuntimeError::TypeError {
+                expected: "char or long",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn int_of_char_and_numbers() {
+        assert_eq!(run(r"(int \é)"), Value::Long(233));
+        assert_eq!(run("(int 7)"), Value::Long(7));
+        assert_eq!(run("(int 2.9)"), Value::Long(2));
+    }
+
+    #[test]
+    fn char_predicate() {
+        assert_eq!(run(r"(char? \a)"), Value::Bool(true));
+        assert_eq!(run("(char? \"a\")"), Value::Bool(false));
+    }
+
+    // --- printing ---
+
+    #[test]
+    fn str_of_char_is_raw() {
+        assert_eq!(run(r#"(str \a "b" \c)"#), string("abc"));
+    }
+
+    #[test]
+    fn pr_str_is_readable() {
+        assert_eq!(run(r"(pr-str \a)"), string(r"\a"));
+        assert_eq!(run(r"(pr-str \newline)"), string(r"\newline"));
+        assert_eq!(run(r#"(pr-str "a\"b")"#), string(r#""a\"b""#));
+        assert_eq!(run(r#"(pr-str [\x "y"] 1)"#), string(r#"[\x "y"] 1"#));
+    }
+}
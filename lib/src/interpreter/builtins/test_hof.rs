@@ -41,8 +41,8 @@ mod tests {
     #[test]
     fn map_wrong_arity() {
         assert!(matches!(
-            run_err("(map (fn [x] x))"),
-            RuntimeError::WrongArity { expected: 2, .. }
+            run_err("(map)"),
+            RuntimeError::WrongArity { .. }
         ));
     }
 
@@ -89,8 +89,8 @@ mod tests {
     #[test]
     fn filter_wrong_arity() {
         assert!(matches!(
-            run_err("(filter (fn [x] x))"),
-            RuntimeError::WrongArity { expected: 2, .. }
+            run_err("(filter)"),
+            RuntimeError::WrongArity { .. }
         ));
     }
 
@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn tmp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("risp-io-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn tmp(name: &str) -> String {
+        tmp_path(name).to_string_lossy().into_owned()
+    }
+
+    // --- slurp / spit ---
+
+    #[test]
+    fn spit_then_slurp_round_trips() {
+        let path = tmp("round-trip.txt");
+        let result = run(&format!(
+            "(do (risp.io/spit \"{path}\" \"hello\") (risp.io/slurp \"{path}\"))"
+        ));
+        assert!(matches!(result, Value::String(s) if s.as_ref() == "hello"));
+    }
+
+    #[test]
+    fn spit_overwrites_by_default() {
+        let path = tmp("overwrite.txt");
+        let result = run(&format!(
+            "(do (risp.io/spit \"{path}\" \"a\") (risp.io/spit \"{path}\" \"b\") (risp.io/slurp \"{path}\"))"
+        ));
+        assert!(matches!(result, Value::String(s) if s.as_ref() == "b"));
+    }
+
+    #[test]
+    fn spit_appends_with_option() {
+        let path = tmp("append.txt");
+        let result = run(&format!(
+            "(do (risp.io/spit \"{path}\" \"a\") (risp.io/spit \"{path}\" 1 :append true) (risp.io/slurp \"{path}\"))"
+        ));
+        assert!(matches!(result, Value::String(s) if s.as_ref() == "a1"));
+    }
+
+    #[test]
+    fn slurp_missing_file_is_io_error() {
+        let path = tmp("missing.txt");
+        assert!(matches!(
+            run_err(&format!("(risp.io/slurp \"{path}\")")),
+            RuntimeError::Io { .. }
+        ));
+    }
+
+    #[test]
+    fn slurp_type_error_on_non_string() {
+        assert!(matches!(
+            run_err("(risp.io/slurp 42)"),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+
+    #[test]
+    fn spit_wrong_arity() {
+        assert!(matches!(
+            run_err("(risp.io/spit \"x\")"),
+            RuntimeError::WrongArity { expected: 2, .. }
+        ));
+    }
+
+    // --- line-seq / read-line ---
+
+    #[test]
+    fn line_seq_over_path() {
+        let path = tmp_path("lines.txt");
+        std::fs::write(&path, "one\ntwo\r\nthree").unwrap();
+        let result = run(&format!("(risp.io/line-seq \"{}\")", path.display()));
+        assert!(matches!(result, Value::List(l) if l.len() == 3
+            && l.get(1).unwrap() == Some(&Value::String("two".into()))));
+    }
+
+    #[test]
+    fn read_line_from_reader_returns_nil_at_eof() {
+        let path = tmp_path("read-line.txt");
+        std::fs::write(&path, "first\n").unwrap();
+        let result = run(&format!(
+            "(with-open [r (risp.io/reader \"{}\")] [(risp.io/read-line r) (risp.io/read-line r)])",
+            path.display()
+        ));
+        assert!(matches!(result, Value::Vector(v)
+            if v[0] == Value::String("first".into()) && v[1] == Value::Nil));
+    }
+
+    // --- files and directories ---
+
+    #[test]
+    fn file_exists() {
+        let path = tmp("exists.txt");
+        let result = run(&format!(
+            "[(risp.io/file-exists? \"{path}\") (do (risp.io/spit \"{path}\" \"\") (risp.io/file-exists? \"{path}\"))]"
+        ));
+        assert!(matches!(result, Value::Vector(v)
+            if v[0] == Value::Bool(false) && v[1] == Value::Bool(true)));
+    }
+
+    #[test]
+    fn make_dirs_list_dir_and_delete_file() {
+        let dir = tmp("nested/dir");
+        let result = run(&format!(
+            "(do (risp.io/make-dirs \"{dir}\")
+                 (risp.io/spit \"{dir}/b.txt\" \"\")
+                 (risp.io/spit \"{dir}/a.txt\" \"\")
+                 (risp.io/delete-file \"{dir}/b.txt\")
+                 (risp.io/list-dir \"{dir}\"))"
+        ));
+        assert!(matches!(result, Value::Vector(v)
+            if *v == vec![Value::String("a.txt".into())]));
+    }
+
+    #[test]
+    fn delete_missing_file_is_io_error() {
+        let path = tmp("never-created.txt");
+        assert!(matches!(
+            run_err(&format!("(risp.io/delete-file \"{path}\")")),
+            RuntimeError::Io { .. }
+        ));
+    }
+
+    #[test]
+    fn delete_missing_file_silently() {
+        let path = tmp("never-created-silently.txt");
+        assert!(matches!(
+            run(&format!("(risp.io/delete-file \"{path}\" true)")),
+            Value::Bool(false)
+        ));
+    }
+
+    // --- with-open ---
+
+    #[test]
+    fn with_open_writes_and_closes() {
+        let path = tmp("with-open.txt");
+        let result = run(&format!(
+            "(do (with-open [w (risp.io/writer \"{path}\")] (risp.io/write w \"data\"))
+                 (risp.io/slurp \"{path}\"))"
+        ));
+        assert!(matches!(result, Value::String(s) if s.as_ref() == "data"));
+    }
+
+    #[test]
+    fn with_open_closes_stream_on_error() {
+        let path = tmp("with-open-error.txt");
+        let mut interpreter = Interpreter::new();
+        interpreter
+            .run(&format!("(def w (risp.io/writer \"{path}\"))"))
+            .unwrap();
+        let err = interpreter
+            .run("(with-open [s w] (do (risp.io/write s \"partial\") (undefined-fn)))")
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::UndefinedVariable { .. }));
+        assert!(matches!(
+            interpreter.run("(risp.io/closed? w)").unwrap(),
+            Value::Bool(true)
+        ));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "partial");
+    }
+
+    #[test]
+    fn with_open_rejects_non_stream_binding() {
+        assert!(matches!(
+            run_err("(with-open [x 1] x)"),
+            RuntimeError::TypeError {
+                expected: "stream",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn write_after_close_is_io_error() {
+        let path = tmp("closed.txt");
+        assert!(matches!(
+            run_err(&format!(
+                "(let [w (risp.io/writer \"{path}\")] (do (risp.io/close w) (risp.io/write w \"x\")))"
+            )),
+            RuntimeError::Io { .. }
+        ));
+    }
+}
@@ -0,0 +1,316 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn printed(source: &str) -> String {
+        Readable(&run(source)).to_string()
+    }
+
+    fn truthy(source: &str) -> bool {
+        run(source) == Value::Bool(true)
+    }
+
+    // --- get / get-in ---
+
+    #[test]
+    fn get_from_map() {
+        assert_eq!(run("(get {:a 1} :a)"), Value::Long(1));
+        assert_eq!(run("(get {:a 1} :b)"), Value::Nil);
+        assert_eq!(run("(get {:a 1} :b 0)"), Value::Long(0));
+        assert_eq!(run("(get {:a nil} :a 0)"), Value::Nil);
+    }
+
+    #[test]
+    fn get_from_other_collections() {
+        assert_eq!(run("(get [10 20] 1)"), Value::Long(20));
+        assert_eq!(run("(get [10 20] 5 :nf)"), run(":nf"));
+        assert_eq!(run("(get #{:x} :x)"), run(":x"));
+        assert_eq!(run("(get \"abc\" 1)"), Value::Char('b'));
+        assert_eq!(run("(get nil :a)"), Value::Nil);
+        assert_eq!(run("(get 42 :a)"), Value::Nil);
+        assert_eq!(run("(get (sorted-map 1 :one) 1)"), run(":one"));
+    }
+
+    #[test]
+    fn get_in_nested() {
+        assert_eq!(run("(get-in {:a {:b [1 2]}} [:a :b 1])"), Value::Long(2));
+        assert_eq!(run("(get-in {:a 1} [:x :y] :nf)"), run(":nf"));
+        assert_eq!(printed("(get-in {:a 1} [])"), "{:a 1}");
+    }
+
+    // --- assoc / assoc-in / dissoc ---
+
+    #[test]
+    fn assoc_map() {
+        assert!(truthy("(= (assoc {:a 1} :b 2 :a 3) {:a 3 :b 2})"));
+        assert_eq!(printed("(assoc nil :a 1)"), "{:a 1}");
+    }
+
+    #[test]
+    fn assoc_vector() {
+        assert_eq!(printed("(assoc [1 2] 0 :x)"), "[:x 2]");
+        assert_eq!(printed("(assoc [1 2] 2 3)"), "[1 2 3]");
+        assert!(matches!(
+            run_err("(assoc [1 2] 5 3)"),
+            RuntimeError::IndexOutOfBounds { got: 5, .. }
+        ));
+        assert!(matches!(
+            run_err("(assoc [1 2] :a 3)"),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+
+    #[test]
+    fn assoc_errors() {
+        assert!(matches!(
+            run_err("(assoc {} :a)"),
+            RuntimeError::WrongArity { .. }
+        ));
+        assert!(matches!(
+            run_err("(assoc '(1) 0 1)"),
+            RuntimeError::TypeError {
+                expected: "map or vector",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn assoc_in_creates_missing_levels() {
+        assert_eq!(printed("(assoc-in {} [:a :b] 1)"), "{:a {:b 1}}");
+        assert_eq!(printed("(assoc-in {:a [1 2]} [:a 0] 9)"), "{:a [9 2]}");
+    }
+
+    #[test]
+    fn dissoc_keys() {
+        assert_eq!(printed("(dissoc {:a 1 :b 2 :c 3} :a :c)"), "{:b 2}");
+        assert_eq!(printed("(dissoc {:a 1} :z)"), "{:a 1}");
+        assert_eq!(run("(dissoc nil :a)"), Value::Nil);
+        assert_eq!(printed("(dissoc (sorted-map 1 2 3 4) 1)"), "{3 4}");
+    }
+
+    // --- update / update-in ---
+
+    #[test]
+    fn update_applies_function() {
+        assert_eq!(printed("(update {:a 1} :a inc)"), "{:a 2}");
+        assert_eq!(printed("(update {:a 1} :a + 10 20)"), "{:a 31}");
+        assert_eq!(printed("(update [1 2] 0 - 1)"), "[0 2]");
+        assert_eq!(printed("(update {} :n (fn [x] (if x x 0)))"), "{:n 0}");
+    }
+
+    #[test]
+    fn update_in_nested() {
+        assert_eq!(
+            printed("(update-in {:a {:b 1}} [:a :b] inc)"),
+            "{:a {:b 2}}"
+        );
+        assert_eq!(printed("(update-in {:a [1 2]} [:a 1] * 10)"), "{:a [1 20]}");
+    }
+
+    // --- merge / merge-with / select-keys ---
+
+    #[test]
+    fn merge_later_wins() {
+        assert!(truthy("(= (merge {:a 1} nil {:b 2} {:a 3}) {:a 3 :b 2})"));
+        assert_eq!(run("(merge)"), Value::Nil);
+        assert_eq!(run("(merge nil nil)"), Value::Nil);
+    }
+
+    #[test]
+    fn merge_keeps_sorted_maps_sorted() {
+        assert_eq!(
+            printed("(merge (sorted-map :c 1) {:b 2} {:a 3})"),
+            "{:a 3, :b 2, :c 1}"
+        );
+    }
+
+    #[test]
+    fn merge_with_combines() {
+        assert!(truthy(
+            "(= (merge-with + {:a 1 :b 2} {:a 10} {:b 5 :c 1}) {:a 11 :b 7 :c 1})"
+        ));
+    }
+
+    #[test]
+    fn merge_type_error() {
+        assert!(matches!(
+            run_err("(merge {} [1 2])"),
+            RuntimeError::TypeError {
+                expected: "map",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn select_keys_keeps_present_keys() {
+        assert_eq!(
+            printed("(select-keys {:a 1 :b 2 :c 3} [:a :c :z])"),
+            "{:a 1, :c 3}"
+        );
+    }
+
+    // --- keys / vals / contains? / find ---
+
+    #[test]
+    fn keys_and_vals() {
+        assert_eq!(printed("(keys {:a 1 :b 2})"), "(:a :b)");
+        assert_eq!(printed("(vals {:a 1 :b 2})"), "(1 2)");
+        assert_eq!(printed("(keys (sorted-map 2 :b 1 :a))"), "(1 2)");
+        assert_eq!(run("(keys {})"), Value::Nil);
+        assert_eq!(run("(vals nil)"), Value::Nil);
+    }
+
+    #[test]
+    fn contains_checks_keys() {
+        assert!(truthy("(contains? {:a nil} :a)"));
+        assert!(!truthy("(contains? {:a 1} 1)"));
+        assert!(truthy("(contains? [:x :y] 1)"));
+        assert!(!truthy("(contains? [:x :y] :x)"));
+        assert!(truthy("(contains? #{1 2} 2)"));
+        assert!(!truthy("(contains? nil 1)"));
+    }
+
+    #[test]
+    fn contains_on_a_list_is_an_error() {
+        assert!(matches!(
+            run_err("(contains? '(1 2) 1)"),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+
+    #[test]
+    fn find_returns_entry() {
+        assert_eq!(printed("(find {:a 1} :a)"), "[:a 1]");
+        assert_eq!(printed("(find [:x :y] 1)"), "[1 :y]");
+        assert_eq!(run("(find {:a 1} :b)"), Value::Nil);
+    }
+
+    // --- zipmap / into ---
+
+    #[test]
+    fn zipmap_pairs_up() {
+        assert_eq!(printed("(zipmap [:a :b :c] [1 2])"), "{:a 1, :b 2}");
+        assert_eq!(printed("(zipmap [:a :a] [1 2])"), "{:a 2}");
+    }
+
+    #[test]
+    fn into_collections() {
+        assert_eq!(printed("(into [] '(1 2 3))"), "[1 2 3]");
+        assert_eq!(printed("(into '() [1 2 3])"), "(3 2 1)");
+        assert_eq!(printed("(into {} [[:a 1] [:b 2]])"), "{:a 1, :b 2}");
+        assert_eq!(printed("(into (sorted-map) {:b 1 :a 2})"), "{:a 2, :b 1}");
+        assert!(truthy("(= (into #{} [1 1 2]) #{1 2})"));
+        assert_eq!(printed("(into)"), "[]");
+        assert_eq!(printed("(into [1])"), "[1]");
+    }
+
+    // --- equality ---
+
+    #[test]
+    fn map_equality_ignores_order() {
+        assert!(truthy("(= {:a 1 :b 2} {:b 2 :a 1})"));
+        assert!(!truthy("(= {:a 1} {:a 1 :b 2})"));
+    }
+
+    // --- collections and keywords as functions ---
+
+    #[test]
+    fn map_called_as_function() {
+        assert_eq!(run("({:a 1} :a)"), Value::Long(1));
+        assert_eq!(run("({:a 1} :b)"), Value::Nil);
+        assert_eq!(run("({:a 1} :b 0)"), Value::Long(0));
+        assert_eq!(run("((sorted-map 1 :x) 1)"), Value::Keyword("x".into()));
+    }
+
+    #[test]
+    fn set_called_as_function() {
+        assert_eq!(run("(#{1 2} 1)"), Value::Long(1));
+        assert_eq!(run("(#{1 2} 3)"), Value::Nil);
+        assert_eq!(run("((sorted-set 1 2) 2)"), Value::Long(2));
+    }
+
+    #[test]
+    fn vector_called_as_function() {
+        assert_eq!(run("([10 20] 1)"), Value::Long(20));
+        assert!(matches!(
+            run_err("([10 20] 2)"),
+            RuntimeError::IndexOutOfBounds { .. }
+        ));
+        let err = run_err("([1 2] -1)");
+        assert!(matches!(
+            err,
+            RuntimeError::IndexOutOfBounds { got: -1, .. }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "(index-out-of-bounds\n  (max-index 1)\n  (got -1))"
+        );
+        assert!(matches!(
+            run_err("([10 20] :a)"),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+
+    #[test]
+    fn keyword_called_as_function() {
+        assert_eq!(run("(:a {:a 1})"), Value::Long(1));
+        assert_eq!(run("(:b {:a 1} 0)"), Value::Long(0));
+        assert_eq!(run("(:a #{:a})"), Value::Keyword("a".into()));
+        assert_eq!(run("(:a #{:b} 0)"), Value::Long(0));
+        assert_eq!(run("(:a nil)"), Value::Nil);
+        assert_eq!(run("(:a nil 0)"), Value::Long(0));
+    }
+
+    #[test]
+    fn collection_call_wrong_arity() {
+        assert!(matches!(run_err("(:a)"), RuntimeError::WrongArity { .. }));
+        assert!(matches!(
+            run_err("({:a 1} :a 0 1)"),
+            RuntimeError::WrongArity {
+                expected: 2,
+                got: 3,
+                ..
+            }
+        ));
+        assert!(matches!(
+            run_err("(#{1} 1 2)"),
+            RuntimeError::WrongArity {
+                expected: 1,
+                got: 2,
+                ..
+            }
+        ));
+        assert!(matches!(run_err("([1])"), RuntimeError::WrongArity { .. }));
+    }
+
+    #[test]
+    fn callables_through_higher_order_functions() {
+        assert_eq!(printed("(map :a [{:a 1} {:a 2}])"), "[1 2]");
+        assert_eq!(printed("(map {1 :x 2 :y} [1 2 3])"), "[:x :y nil]");
+        assert_eq!(printed("(filter #{1 3} [1 2 3 4])"), "(1 3)");
+        assert_eq!(printed("(map [:a :b :c] [2 0])"), "[:c :a]");
+        assert_eq!(run("(apply :a [{:a 1}])"), Value::Long(1));
+        assert_eq!(run("(apply {:a 1} [:b 7])"), Value::Long(7));
+    }
+
+    #[test]
+    fn non_callable_value() {
+        assert!(matches!(run_err("(1 2)"), RuntimeError::NotCallable { .. }));
+        assert!(matches!(
+            run_err("(\"a\" 0)"),
+            RuntimeError::NotCallable { .. }
+        ));
+    }
+}
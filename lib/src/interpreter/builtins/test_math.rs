@@ -1,5 +1,9 @@
 #[cfg(test)]
 mod tests {
+    use std::rc::Rc;
+
+    use num_rational::BigRational;
+
     use crate::interpreter::implementation::Interpreter;
     use crate::interpreter::value::{RuntimeError, Value};
 
@@ -157,4 +161,209 @@ mod tests {
             RuntimeError::TypeError { .. }
         ));
     }
+
+    // --- bigints ---
+
+    fn big(s: &str) -> Value {
+        Value::BigInt(Rc::new(s.parse().unwrap()))
+    }
+
+    #[test]
+    fn bigint_literal() {
+        assert_eq!(run("12N"), big("12"));
+        assert_eq!(run("99999999999999999999"), big("99999999999999999999"));
+    }
+
+    #[test]
+    fn strict_operators_overflow() {
+        for src in [
+            "(* 99999999999 99999999999)",
+            "(+ 9223372036854775807 1)",
+            "(- -9223372036854775808 1)",
+            "(- -9223372036854775808)",
+            "(/ -9223372036854775808 -1)",
+        ] {
+            assert!(matches!(
+                run_err(src),
+                RuntimeError::ArithmeticOverflow { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn promoting_operators() {
+        assert_eq!(
+            run("(*' 99999999999 99999999999)"),
+            big("9999999999800000000001")
+        );
+        assert_eq!(
+            run("(+' 9223372036854775807 1)"),
+            big("9223372036854775808")
+        );
+        assert_eq!(run("(-' -9223372036854775808)"), big("9223372036854775808"));
+        assert!(matches!(run("(+' 1 2)"), Value::Long(3)));
+    }
+
+    #[test]
+    fn bigint_is_contagious() {
+        assert_eq!(run("(+ 1N 2)"), big("3"));
+        assert_eq!(run("(* 2 3N)"), big("6"));
+        assert_eq!(run("(/ 8N 2)"), big("4"));
+        assert!(matches!(run("(+ 1N 0.5)"), Value::Double(v) if approx(v, 1.5)));
+    }
+
+    #[test]
+    fn bigint_equality_and_comparison() {
+        assert_eq!(run("(= 1 1N)"), Value::Bool(true));
+        assert_eq!(run("(= 2N 1)"), Value::Bool(false));
+        assert_eq!(run("(< 1 2N 2.5 99999999999999999999)"), Value::Bool(true));
+        assert_eq!(run("(= {1N :a} {1 :a})"), Value::Bool(true));
+    }
+
+    #[test]
+    fn bigint_mod() {
+        assert_eq!(run("(mod 99999999999999999999 10)"), big("9"));
+        assert!(matches!(
+            run_err("(mod 5N 0)"),
+            RuntimeError::DivisionByZero(_)
+        ));
+    }
+
+    #[test]
+    fn bigint_printing() {
+        assert_eq!(run("(str 5N)"), Value::String("5".into()));
+        assert_eq!(run("(pr-str 5N)"), Value::String("5N".into()));
+    }
+
+    #[test]
+    fn doubles_print_readably() {
+        assert_eq!(
+            run("(pr-str 3.0 0.5 ##Inf ##-Inf ##NaN)"),
+            Value::String("3.0 0.5 ##Inf ##-Inf ##NaN".into())
+        );
+    }
+
+    #[test]
+    fn malformed_literal_is_a_parse_error() {
+        assert!(matches!(
+            run_err("(+ 1 12abc)"),
+            RuntimeError::ParseError(_)
+        ));
+    }
+
+    #[test]
+    fn bigint_conversion() {
+        assert_eq!(run("(bigint 5)"), big("5"));
+        assert_eq!(run("(bigint 2.7)"), big("2"));
+        assert!(matches!(run("(int 5N)"), Value::Long(5)));
+    }
+
+    // --- ratios ---
+
+    fn ratio(n: i64, d: i64) -> Value {
+        Value::Ratio(Rc::new(BigRational::new(n.into(), d.into())))
+    }
+
+    #[test]
+    fn ratio_literal() {
+        assert_eq!(run("22/7"), ratio(22, 7));
+        assert_eq!(run("-6/4"), ratio(-3, 2));
+        assert!(matches!(run("4/2"), Value::Long(2)));
+    }
+
+    #[test]
+    fn divide_integers_is_exact() {
+        assert_eq!(run("(/ 10 4)"), ratio(5, 2));
+        assert_eq!(run("(/ 1 -3)"), ratio(-1, 3));
+        assert_eq!(
+            run("(/ 99999999999999999999 3)"),
+            big("33333333333333333333")
+        );
+    }
+
+    #[test]
+    fn ratio_arithmetic() {
+        assert_eq!(run("(+ 1/3 1/6)"), ratio(1, 2));
+        assert_eq!(run("(- 1/2 1)"), ratio(-1, 2));
+        assert_eq!(run("(* 2/3 3/4)"), ratio(1, 2));
+        assert_eq!(run("(/ 1/2 3)"), ratio(1, 6));
+        assert_eq!(run("(- 1/2)"), ratio(-1, 2));
+    }
+
+    #[test]
+    fn whole_ratio_collapses_to_integer() {
+        assert!(matches!(run("(+ 1/2 1/2)"), Value::Long(1)));
+        assert!(matches!(run("(* 3 1/3)"), Value::Long(1)));
+    }
+
+    #[test]
+    fn ratio_with_double_is_double() {
+        assert!(matches!(run("(+ 1/4 0.5)"), Value::Double(v) if approx(v, 0.75)));
+    }
+
+    #[test]
+    fn ratio_equality_and_comparison() {
+        assert_eq!(run("(= 1/2 (/ 2 4))"), Value::Bool(true));
+        assert_eq!(run("(= 1/2 0.5)"), Value::Bool(false));
+        assert_eq!(run("(< 1/3 0.34 1/2 1 2N)"), Value::Bool(true));
+        assert_eq!(run("(>= 1/2 1/2 -1/2)"), Value::Bool(true));
+    }
+
+    #[test]
+    fn numerator_and_denominator() {
+        assert!(matches!(run("(numerator 22/7)"), Value::Long(22)));
+        assert!(matches!(run("(denominator (/ 6 -4))"), Value::Long(2)));
+        assert!(matches!(run("(numerator 5)"), Value::Long(5)));
+        assert!(matches!(run("(denominator 5)"), Value::Long(1)));
+        assert!(matches!(
+            run_err("(numerator 0.5)"),
+            RuntimeError::TypeError {
+                expected: "rational",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn rationalize_doubles() {
+        assert_eq!(run("(rationalize 0.1)"), ratio(1, 10));
+        assert_eq!(run("(rationalize -2.25)"), ratio(-9, 4));
+        assert!(matches!(run("(rationalize 3.0)"), Value::Long(3)));
+        assert_eq!(run("(rationalize 1/3)"), ratio(1, 3));
+    }
+
+    #[test]
+    fn quot_rem_mod_signs() {
+        assert!(matches!(run("(quot -7 2)"), Value::Long(-3)));
+        assert!(matches!(run("(rem -7 2)"), Value::Long(-1)));
+        assert!(matches!(run("(mod -7 2)"), Value::Long(1)));
+        assert!(matches!(run("(mod 7 -2)"), Value::Long(-1)));
+        assert!(matches!(run("(mod 6 -2)"), Value::Long(0)));
+    }
+
+    #[test]
+    fn quot_rem_mod_across_types() {
+        assert_eq!(run("(quot -7N 2)"), big("-3"));
+        assert_eq!(run("(mod -7N 2)"), big("1"));
+        assert!(matches!(run("(quot 7/2 1)"), Value::Long(3)));
+        assert_eq!(run("(rem -7/2 2)"), ratio(-3, 2));
+        assert_eq!(run("(mod -7/2 2)"), ratio(1, 2));
+        assert!(matches!(run("(quot 7.5 2)"), Value::Double(v) if approx(v, 3.0)));
+        assert!(matches!(run("(mod -7.5 2)"), Value::Double(v) if approx(v, 0.5)));
+    }
+
+    #[test]
+    fn quot_rem_mod_by_zero() {
+        for src in [
+            "(quot 1 0)",
+            "(rem 1N 0)",
+            "(mod 1/2 0)",
+            "(/ 1 0.0)",
+            "(quot 5 0.0)",
+            "(rem 5 0.0)",
+            "(mod 5.5 0.0)",
+        ] {
+            assert!(matches!(run_err(src), RuntimeError::DivisionByZero(_)));
+        }
+    }
 }
@@ -0,0 +1,250 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn printed(source: &str) -> String {
+        Readable(&run(source)).to_string()
+    }
+
+    const SHAPES: &str = "
+        (defmulti area :type)
+        (defmethod area :square [s] (* (:side s) (:side s)))
+        (defmethod area :circle [c] (* 3 (:r c) (:r c)))
+    ";
+
+    // --- dispatch ---
+
+    #[test]
+    fn dispatches_on_keyword() {
+        let src =
+            format!("{SHAPES} [(area {{:type :square :side 2}}) (area {{:type :circle :r 1}})]");
+        assert_eq!(printed(&src), "[4 3]");
+    }
+
+    #[test]
+    fn dispatch_fn_sees_all_args() {
+        assert_eq!(
+            run("(defmulti op (fn [a b] (if (< a b) :lt :ge)))
+                 (defmethod op :lt [a b] (- b a))
+                 (defmethod op :ge [a b] (- a b))
+                 (+ (op 1 5) (op 7 2))"),
+            Value::Long(9)
+        );
+    }
+
+    #[test]
+    fn no_method_names_value() {
+        let err = run_err(&format!("{SHAPES} (area {{:type :hexagon}})"));
+        assert!(matches!(
+            &err,
+            RuntimeError::NoMethod { name, value, .. } if name == "area" && value == ":hexagon"
+        ));
+        assert_eq!(err.to_string(), "(no-method area :hexagon)");
+    }
+
+    #[test]
+    fn default_method_catches_the_rest() {
+        let src =
+            format!("{SHAPES} (defmethod area :default [_] :unknown) (area {{:type :hexagon}})");
+        assert_eq!(printed(&src), ":unknown");
+    }
+
+    #[test]
+    fn custom_default_dispatch_value() {
+        assert_eq!(
+            printed(
+                "(defmulti f identity :default :other)
+                     (defmethod f :other [_] :fallback)
+                     (defmethod f :default [_] :not-the-default)
+                     [(f 1) (f :default)]"
+            ),
+            "[:fallback :not-the-default]"
+        );
+    }
+
+    #[test]
+    fn redefining_a_method_replaces_it() {
+        let src =
+            format!("{SHAPES} (defmethod area :square [_] 0) (area {{:type :square :side 3}})");
+        assert_eq!(run(&src), Value::Long(0));
+    }
+
+    #[test]
+    fn remove_method_and_methods() {
+        let src = format!("{SHAPES} (remove-method area :circle) (keys (methods area))");
+        assert_eq!(printed(&src), "(:square)");
+        let src = format!("{SHAPES} (remove-method area :circle) (area {{:type :circle :r 1}})");
+        assert!(matches!(run_err(&src), RuntimeError::NoMethod { .. }));
+    }
+
+    #[test]
+    fn get_method_follows_dispatch() {
+        let src =
+            format!("{SHAPES} [((get-method area :square) {{:side 5}}) (get-method area :none)]");
+        assert_eq!(printed(&src), "[25 nil]");
+    }
+
+    #[test]
+    fn methods_of_non_multi_is_type_error() {
+        assert!(matches!(
+            run_err("(methods inc)"),
+            RuntimeError::TypeError {
+                expected: "multimethod",
+                ..
+            }
+        ));
+    }
+
+    // --- hierarchy ---
+
+    #[test]
+    fn derive_and_isa() {
+        assert_eq!(
+            printed("(derive :pet/dog :pet/animal) (derive :pet/animal :pet/thing)
+                     [(isa? :pet/dog :pet/thing) (isa? :pet/thing :pet/dog) (isa? 1 1) (isa? [:pet/dog 1] [:pet/animal 1])]"),
+            "[true false true true]"
+        );
+    }
+
+    #[test]
+    fn parents_ancestors_descendants() {
+        assert_eq!(
+            printed(
+                "(derive :dog :mammal) (derive :cat :mammal) (derive :mammal :animal)
+                     [(parents :dog) (ancestors :dog) (descendants :animal) (parents :animal)]"
+            ),
+            "[#{:mammal} #{:mammal :animal} #{:dog :cat :mammal} nil]"
+        );
+    }
+
+    #[test]
+    fn underive_removes_edge() {
+        assert_eq!(
+            run("(derive :a :b) (underive :a :b) (isa? :a :b)"),
+            Value::Bool(false)
+        );
+    }
+
+    #[test]
+    fn cyclic_derive_is_illegal_state() {
+        assert!(matches!(
+            run_err("(derive :a :b) (derive :b :a)"),
+            RuntimeError::IllegalState {
+                message: "cyclic derivation",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn derive_needs_tags() {
+        assert!(matches!(
+            run_err("(derive 1 :a)"),
+            RuntimeError::TypeError {
+                expected: "keyword or symbol",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn dispatch_uses_hierarchy() {
+        assert_eq!(
+            printed(
+                "(derive :click :ui) (derive :ui :event)
+                     (defmulti handle :type)
+                     (defmethod handle :event [_] :event)
+                     (defmethod handle :ui [_] :ui)
+                     [(handle {:type :click}) (handle {:type :event})]"
+            ),
+            "[:ui :event]"
+        );
+    }
+
+    #[test]
+    fn vector_dispatch_values_match_pairwise() {
+        assert_eq!(
+            run("(derive :circle :shape)
+                 (defmulti collide (fn [a b] [a b]))
+                 (defmethod collide [:shape :shape] [a b] :generic)
+                 (collide :circle :circle)"),
+            Value::Keyword("generic".into())
+        );
+    }
+
+    #[test]
+    fn cache_is_refreshed_after_derive() {
+        assert_eq!(
+            printed(
+                "(defmulti f identity)
+                     (defmethod f :parent [_] :parent)
+                     (defmethod f :default [_] :default)
+                     (let [before (f :child)]
+                       (do (derive :child :parent) [before (f :child)]))"
+            ),
+            "[:default :parent]"
+        );
+    }
+
+    #[test]
+    fn cache_is_refreshed_after_new_method() {
+        assert_eq!(
+            printed(
+                "(defmulti f identity)
+                     (defmethod f :default [_] :default)
+                     (let [before (f :x)]
+                       (do (defmethod f :x [_] :x) [before (f :x)]))"
+            ),
+            "[:default :x]"
+        );
+    }
+
+    // --- preferences ---
+
+    const DIAMOND: &str = "
+        (derive :a :p1) (derive :a :p2)
+        (defmulti m identity)
+        (defmethod m :p1 [_] :p1)
+        (defmethod m :p2 [_] :p2)
+    ";
+
+    #[test]
+    fn ambiguous_match_is_an_error() {
+        let err = run_err(&format!("{DIAMOND} (m :a)"));
+        assert!(matches!(err, RuntimeError::AmbiguousMethod { .. }));
+        assert_eq!(err.to_string(), "(ambiguous-method m :a)");
+    }
+
+    #[test]
+    fn prefer_method_breaks_ties() {
+        assert_eq!(
+            printed(&format!("{DIAMOND} (prefer-method m :p2 :p1) (m :a)")),
+            ":p2"
+        );
+    }
+
+    #[test]
+    fn conflicting_preference_is_illegal_state() {
+        assert!(matches!(
+            run_err(&format!(
+                "{DIAMOND} (prefer-method m :p2 :p1) (prefer-method m :p1 :p2)"
+            )),
+            RuntimeError::IllegalState { .. }
+        ));
+    }
+
+    #[test]
+    fn multimethod_prints_its_name() {
+        assert_eq!(printed(&format!("{SHAPES} area")), "#<multi-fn area>");
+    }
+}
@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use num_rational::BigRational;
+
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-10
+    }
+
+    fn double(source: &str) -> f64 {
+        match run(source) {
+            Value::Double(v) => v,
+            v => panic!("expected a double, got {v:?}"),
+        }
+    }
+
+    // --- constants ---
+
+    #[test]
+    fn constants() {
+        assert!(approx(double("risp.math/PI"), std::f64::consts::PI));
+        assert!(approx(double("risp.math/E"), std::f64::consts::E));
+    }
+
+    // --- abs / signum / inc / dec ---
+
+    #[test]
+    fn abs_keeps_type() {
+        assert!(matches!(run("(risp.math/abs -3)"), Value::Long(3)));
+        assert!(approx(double("(risp.math/abs -2.5)"), 2.5));
+        assert_eq!(
+            run("(risp.math/abs -1/2)"),
+            Value::Ratio(Rc::new(BigRational::new(1.into(), 2.into())))
+        );
+        assert_eq!(run("(abs -5N)"), run("5N"));
+    }
+
+    #[test]
+    fn abs_overflow() {
+        assert!(matches!(
+            run_err("(abs -9223372036854775808)"),
+            RuntimeError::ArithmeticOverflow { .. }
+        ));
+    }
+
+    #[test]
+    fn signum() {
+        assert!(matches!(run("(risp.math/signum -7)"), Value::Long(-1)));
+        assert!(matches!(run("(risp.math/signum 0)"), Value::Long(0)));
+        assert!(matches!(run("(risp.math/signum 1/3)"), Value::Long(1)));
+        assert!(approx(double("(risp.math/signum -0.5)"), -1.0));
+        assert!(approx(double("(risp.math/signum 0.0)"), 0.0));
+    }
+
+    #[test]
+    fn inc_dec() {
+        assert!(matches!(run("(inc 1)"), Value::Long(2)));
+        assert!(matches!(run("(dec 1)"), Value::Long(0)));
+        assert!(approx(double("(inc 1.5)"), 2.5));
+        assert!(matches!(
+            run_err("(inc 9223372036854775807)"),
+            RuntimeError::ArithmeticOverflow { .. }
+        ));
+    }
+
+    // --- min / max ---
+
+    #[test]
+    fn min_max_return_the_argument() {
+        assert!(matches!(run("(min 3 1 2)"), Value::Long(1)));
+        assert!(matches!(run("(max 3 1 2)"), Value::Long(3)));
+        assert!(approx(double("(max 1 2.5 2)"), 2.5));
+        assert!(matches!(run("(min 1 1.0)"), Value::Long(1)));
+    }
+
+    #[test]
+    fn min_max_nan_wins() {
+        assert!(double("(max 1 (risp.math/sqrt -1))").is_nan());
+        assert!(double("(min ##NaN 1)").is_nan());
+        assert!(double("(max ##NaN 1 2)").is_nan());
+        assert!(double("(min 1 ##NaN)").is_nan());
+    }
+
+    #[test]
+    fn min_no_args() {
+        assert!(matches!(
+            run_err("(min)"),
+            RuntimeError::WrongArity { expected: 1, .. }
+        ));
+    }
+
+    // --- pow / roots / logs / trig ---
+
+    #[test]
+    fn pow_exact_and_double() {
+        assert!(matches!(run("(risp.math/pow 2 10)"), Value::Long(1024)));
+        assert_eq!(
+            run("(risp.math/pow 2N 100)"),
+            run("1267650600228229401496703205376N")
+        );
+        assert_eq!(run("(risp.math/pow 1/2 2)"), run("1/4"));
+        assert!(approx(double("(risp.math/pow 2 -1)"), 0.5));
+        assert!(approx(double("(risp.math/pow 4 0.5)"), 2.0));
+    }
+
+    #[test]
+    fn pow_overflow() {
+        assert!(matches!(
+            run_err("(risp.math/pow 10 19)"),
+            RuntimeError::ArithmeticOverflow { .. }
+        ));
+    }
+
+    #[test]
+    fn transcendental_functions() {
+        assert!(approx(double("(risp.math/sqrt 16)"), 4.0));
+        assert!(approx(double("(risp.math/exp 0)"), 1.0));
+        assert!(approx(double("(risp.math/log risp.math/E)"), 1.0));
+        assert!(approx(double("(risp.math/log10 1000)"), 3.0));
+        assert!(approx(double("(risp.math/hypot 3 4)"), 5.0));
+    }
+
+    #[test]
+    fn trig_functions() {
+        assert!(approx(double("(risp.math/sin (/ risp.math/PI 2))"), 1.0));
+        assert!(approx(double("(risp.math/cos 0)"), 1.0));
+        assert!(approx(double("(risp.math/tan 0)"), 0.0));
+        assert!(approx(
+            double("(risp.math/asin 1)"),
+            std::f64::consts::FRAC_PI_2
+        ));
+        assert!(approx(double("(risp.math/acos 1)"), 0.0));
+        assert!(approx(
+            double("(risp.math/atan 1)"),
+            std::f64::consts::FRAC_PI_4
+        ));
+        assert!(approx(
+            double("(risp.math/atan2 1 1)"),
+            std::f64::consts::FRAC_PI_4
+        ));
+    }
+
+    // --- rounding ---
+
+    #[test]
+    fn rounding_keeps_type() {
+        assert!(matches!(run("(risp.math/floor 7)"), Value::Long(7)));
+        assert!(approx(double("(risp.math/floor -1.5)"), -2.0));
+        assert!(approx(double("(risp.math/ceil 1.2)"), 2.0));
+        assert!(approx(double("(risp.math/round 2.5)"), 3.0));
+        assert!(approx(double("(risp.math/round -2.5)"), -3.0));
+    }
+
+    #[test]
+    fn rounding_ratios_gives_integers() {
+        assert!(matches!(run("(risp.math/floor -7/2)"), Value::Long(-4)));
+        assert!(matches!(run("(risp.math/ceil 7/2)"), Value::Long(4)));
+        assert!(matches!(run("(risp.math/round 7/2)"), Value::Long(4)));
+    }
+
+    // --- predicates ---
+
+    #[test]
+    fn nan_and_infinite() {
+        assert_eq!(
+            run("(risp.math/NaN? (risp.math/sqrt -1))"),
+            Value::Bool(true)
+        );
+        assert_eq!(run("(risp.math/NaN? 1)"), Value::Bool(false));
+        assert_eq!(run("(risp.math/infinite? 1.5)"), Value::Bool(false));
+        assert_eq!(
+            run("(risp.math/infinite? (risp.math/exp 1000))"),
+            Value::Bool(true)
+        );
+    }
+
+    // --- errors ---
+
+    #[test]
+    fn type_error_points_at_argument() {
+        match run_err("(risp.math/pow 2 \"x\")") {
+            RuntimeError::TypeError {
+                expected: "number",
+                got: "string",
+                span,
+            } => assert_eq!((span.lo, span.hi), (17, 20)),
+            e => panic!("unexpected error {e:?}"),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn printed(source: &str) -> String {
+        Readable(&run(source)).to_string()
+    }
+
+    // --- peek / pop ---
+
+    #[test]
+    fn list_peek_and_pop_use_front() {
+        assert_eq!(run("(peek '(1 2 3))"), Value::Long(1));
+        assert_eq!(printed("(pop '(1 2 3))"), "(2 3)");
+    }
+
+    #[test]
+    fn vector_peek_and_pop_use_back() {
+        assert_eq!(run("(peek [1 2 3])"), Value::Long(3));
+        assert_eq!(printed("(pop [1 2 3])"), "[1 2]");
+    }
+
+    #[test]
+    fn peek_empty_is_nil() {
+        assert_eq!(run("(peek [])"), Value::Nil);
+        assert_eq!(run("(peek '())"), Value::Nil);
+        assert_eq!(run("(peek nil)"), Value::Nil);
+        assert_eq!(run("(pop nil)"), Value::Nil);
+    }
+
+    #[test]
+    fn pop_empty_list_or_vector_is_illegal_state() {
+        assert!(matches!(
+            run_err("(pop [])"),
+            RuntimeError::IllegalState {
+                message: "can't pop empty vector",
+                ..
+            }
+        ));
+        assert!(matches!(
+            run_err("(pop '())"),
+            RuntimeError::IllegalState {
+                message: "can't pop empty list",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn peek_non_stack_is_type_error() {
+        assert!(matches!(
+            run_err("(peek #{1})"),
+            RuntimeError::TypeError {
+                expected: "list, vector or queue",
+                got: "set",
+                ..
+            }
+        ));
+    }
+
+    // --- queues ---
+
+    #[test]
+    fn queue_is_first_in_first_out() {
+        assert_eq!(run("(peek (conj (queue 1) 2))"), Value::Long(1));
+        assert_eq!(printed("(pop (conj (queue 1) 2))"), "#queue [2]");
+        assert_eq!(printed("(pop (queue))"), "#queue []");
+        assert_eq!(run("(peek (queue))"), Value::Nil);
+    }
+
+    #[test]
+    fn queue_literal_evaluates_items() {
+        assert_eq!(printed("#queue [(+ 1 2) :a]"), "#queue [3 :a]");
+        assert_eq!(run("(let [x 5] (peek #queue [x]))"), Value::Long(5));
+    }
+
+    #[test]
+    fn queue_count_seq_and_empty() {
+        assert_eq!(run("(count (conj #queue [1 2] 3))"), Value::Long(3));
+        assert_eq!(printed("(seq (conj #queue [1 2] 3))"), "(1 2 3)");
+        assert_eq!(run("(seq (queue))"), Value::Nil);
+        assert_eq!(run("(empty? (pop (queue 1)))"), Value::Bool(true));
+        assert_eq!(printed("(rest (queue 1 2 3))"), "(2 3)");
+    }
+
+    #[test]
+    fn queue_equality_is_sequential() {
+        assert_eq!(run("(= (queue 1 2) #queue [1 2])"), Value::Bool(true));
+        assert_eq!(run("(= (pop (queue 0 1 2)) [1 2])"), Value::Bool(true));
+        assert_eq!(run("(= (queue 1 2) '(1 2))"), Value::Bool(true));
+        assert_eq!(run("(= (queue 1 2) (queue 2 1))"), Value::Bool(false));
+        assert_eq!(run("(sequential? (queue))"), Value::Bool(true));
+    }
+
+    #[test]
+    fn queue_works_with_sequence_functions() {
+        assert_eq!(run("(reduce + (queue 1 2 3))"), Value::Long(6));
+        assert_eq!(printed("(into (queue) [1 2])"), "#queue [1 2]");
+        assert_eq!(printed("(into [] (queue 1 2))"), "[1 2]");
+    }
+
+    #[test]
+    fn queue_drains_in_order_after_interleaving() {
+        assert_eq!(
+            printed(
+                "(loop [q (queue 1 2) out []]
+                   (if (empty? q)
+                     out
+                     (recur (if (< (peek q) 4) (conj (pop q) (+ (peek q) 2)) (pop q))
+                            (conj out (peek q)))))"
+            ),
+            "[1 2 3 4 5]"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn seeded(seed: u64, source: &str) -> Value {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_seed(seed);
+        interpreter.run(source).unwrap()
+    }
+
+    const SIMULATION: &str =
+        "[(rand) (rand-int 100) (rand-nth [:a :b :c]) (shuffle [0 1 2 3 4 5 6 7 8 9]) (random-uuid)]";
+
+    // --- reproducibility ---
+
+    #[test]
+    fn same_seed_same_results() {
+        assert_eq!(seeded(42, SIMULATION), seeded(42, SIMULATION));
+    }
+
+    #[test]
+    fn different_seeds_differ() {
+        assert_ne!(seeded(1, SIMULATION), seeded(2, SIMULATION));
+    }
+
+    #[test]
+    fn set_seed_from_risp() {
+        assert_eq!(
+            run(&format!("(do (set-seed! 7) {SIMULATION})")),
+            seeded(7, SIMULATION)
+        );
+    }
+
+    #[test]
+    fn reseeding_restarts_the_sequence() {
+        assert_eq!(
+            run("(do (set-seed! 3) (def a (rand)) (rand) (set-seed! 3) (= a (rand)))"),
+            Value::Bool(true)
+        );
+    }
+
+    // --- ranges ---
+
+    #[test]
+    fn rand_in_unit_interval() {
+        for _ in 0..50 {
+            let Value::Double(n) = run("(rand)") else {
+                panic!("rand should answer a double");
+            };
+            assert!((0.0..1.0).contains(&n));
+        }
+    }
+
+    #[test]
+    fn rand_scaled() {
+        let Value::Double(n) = run("(rand 5)") else {
+            panic!("rand should answer a double");
+        };
+        assert!((0.0..5.0).contains(&n));
+    }
+
+    #[test]
+    fn rand_int_in_range() {
+        for _ in 0..50 {
+            let Value::Long(n) = run("(rand-int 6)") else {
+                panic!("rand-int should answer a long");
+            };
+            assert!((0..6).contains(&n));
+        }
+    }
+
+    #[test]
+    fn rand_nth_picks_an_element() {
+        assert!(matches!(run("(rand-nth '(9 9 9))"), Value::Long(9)));
+    }
+
+    #[test]
+    fn shuffle_is_a_permutation() {
+        assert_eq!(
+            run("(= (apply + (shuffle [1 2 4 8 16])) 31)"),
+            Value::Bool(true)
+        );
+        assert_eq!(run("(count (shuffle '(1 2 3)))"), Value::Long(3));
+    }
+
+    #[test]
+    fn random_sample_bounds() {
+        assert_eq!(run("(random-sample 1 [1 2 3])"), run("'(1 2 3)"));
+        assert_eq!(run("(random-sample 0 [1 2 3])"), run("'()"));
+    }
+
+    #[test]
+    fn random_uuid_format() {
+        let Value::String(s) = run("(random-uuid)") else {
+            panic!("random-uuid should answer a string");
+        };
+        let parts: Vec<&str> = s.split('-').collect();
+        assert_eq!(
+            parts.iter().map(|p| p.len()).collect::<Vec<_>>(),
+            [8, 4, 4, 4, 12]
+        );
+        assert!(parts[2].starts_with('4'));
+        assert!(matches!(
+            parts[3].chars().next(),
+            Some('8' | '9' | 'a' | 'b')
+        ));
+    }
+
+    // --- errors ---
+
+    #[test]
+    fn rand_int_needs_positive_bound() {
+        assert!(matches!(
+            run_err("(rand-int 0)"),
+            RuntimeError::TypeError { .. }
+        ));
+        assert!(matches!(
+            run_err("(rand-int \"a\")"),
+            RuntimeError::TypeError {
+                expected: "long",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn rand_nth_of_empty() {
+        assert!(matches!(
+            run_err("(rand-nth [])"),
+            RuntimeError::IndexOutOfBounds { .. }
+        ));
+    }
+
+    #[test]
+    fn set_seed_type_error() {
+        assert!(matches!(
+            run_err("(set-seed! 1.5)"),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn printed(source: &str) -> String {
+        Readable(&run(source)).to_string()
+    }
+
+    const SHAPES: &str = "
+        (defprotocol Shape \"shapes\" (area [this] \"area\") (scale [this k]))
+        (defrecord Rect [w h]
+          Shape
+          (area [this] (* w h))
+          (scale [this k] (->Rect (* w k) (* h k))))
+    ";
+
+    // --- defrecord ---
+
+    #[test]
+    fn positional_and_map_constructors() {
+        let src = format!("{SHAPES} [(->Rect 2 3) (map->Rect {{:h 3 :w 2 :z 1}})]");
+        assert_eq!(printed(&src), "[#Rect{:w 2, :h 3} #Rect{:w 2, :h 3, :z 1}]");
+    }
+
+    #[test]
+    fn fields_read_like_map_keys() {
+        let src = format!("{SHAPES} (def r (->Rect 2 3)) [(:w r) (get r :h)  (contains? r :h)]");
+        assert_eq!(printed(&src), "[2 3 true]");
+    }
+
+    #[test]
+    fn missing_fields_default_to_nil() {
+        assert_eq!(
+            printed(&format!("{SHAPES} (:h (map->Rect {{:w 1}}))")),
+            "nil"
+        );
+    }
+
+    #[test]
+    fn assoc_keeps_record_and_dissoc_of_field_gives_map() {
+        let src = format!(
+            "{SHAPES} (def r (->Rect 2 3))
+             [(assoc r :w 5) (assoc r :z 1) (dissoc r :w) (record? (dissoc r :z))]"
+        );
+        assert_eq!(
+            printed(&src),
+            "[#Rect{:w 5, :h 3} #Rect{:w 2, :h 3, :z 1} {:h 3} true]"
+        );
+    }
+
+    #[test]
+    fn records_compare_by_type_and_value() {
+        let src = format!(
+            "{SHAPES} (defrecord Other [w h])
+             [(= (->Rect 1 2) (->Rect 1 2)) (= (->Rect 1 2) (->Other 1 2))
+              (= (->Rect 1 2) {{:w 1 :h 2}})]"
+        );
+        assert_eq!(printed(&src), "[true false false]");
+    }
+
+    #[test]
+    fn records_are_seqable() {
+        let src = format!("{SHAPES} (def r (->Rect 2 3)) [(count r) (keys r) (vals r) (first r)]");
+        assert_eq!(printed(&src), "[2 (:w :h) (2 3) [:w 2]]");
+    }
+
+    #[test]
+    fn record_predicates() {
+        let src = format!("{SHAPES} [(record? (->Rect 1 2)) (record? {{:w 1}})]");
+        assert_eq!(printed(&src), "[true false]");
+    }
+
+    // --- deftype ---
+
+    #[test]
+    fn deftype_fields_and_identity() {
+        let src = "(deftype Counter [n])
+                   (def c (->Counter 5))
+                   [(.-n c) (= c c) (= c (->Counter 5)) (record? c)]";
+        assert_eq!(printed(src), "[5 true false false]");
+    }
+
+    #[test]
+    fn deftype_prints_opaquely() {
+        assert_eq!(printed("(deftype Counter [n]) (->Counter 1)"), "#<Counter>");
+    }
+
+    #[test]
+    fn unknown_field_is_an_error() {
+        let err = run_err("(deftype Counter [n]) (.-m (->Counter 1))");
+        assert!(matches!(
+            &err,
+            RuntimeError::NoSuchField { field, type_name, .. } if field == ":m" && type_name == "Counter"
+        ));
+    }
+
+    // --- protocols ---
+
+    #[test]
+    fn record_methods_see_fields() {
+        let src = format!("{SHAPES} [(area (->Rect 2 3)) (area (scale (->Rect 1 2) 3))]");
+        assert_eq!(printed(&src), "[6 18]");
+    }
+
+    #[test]
+    fn params_shadow_fields() {
+        let src = "(defprotocol Get (get-w [this w]))
+                   (defrecord Box [w] Get (get-w [this w] w))
+                   (get-w (->Box 1) 2)";
+        assert_eq!(run(src), Value::Long(2));
+    }
+
+    #[test]
+    fn multi_arity_methods() {
+        let src = "(defprotocol Greet (greet [this] [this who]))
+                   (defrecord P [] Greet (greet [this] :hi) (greet [this who] who))
+                   [(greet (->P)) (greet (->P) :bob)]";
+        assert_eq!(printed(src), "[:hi :bob]");
+    }
+
+    #[test]
+    fn extend_protocol_to_builtin_types() {
+        let src = "(defprotocol Describe (describe [x]))
+                   (extend-protocol Describe
+                     String (describe [s] (str \"str \" s))
+                     Long (describe [n] (* n 10))
+                     nil (describe [_] :none))
+                   (extend-type Vector Describe (describe [v] (count v)))
+                   [(describe \"a\") (describe 4) (describe nil) (describe [1 2 3])]";
+        assert_eq!(printed(src), "[\"str a\" 40 :none 3]");
+    }
+
+    #[test]
+    fn missing_impl_names_protocol_and_type() {
+        let err = run_err("(defprotocol Describe (describe [x])) (describe 1.5)");
+        assert!(matches!(
+            &err,
+            RuntimeError::NoMethod { name, value, .. } if name == "Describe/describe" && value == "double"
+        ));
+    }
+
+    #[test]
+    fn extending_unknown_method_is_an_error() {
+        let err = run_err(
+            "(defprotocol Describe (describe [x])) (extend-type Long Describe (explain [n] n))",
+        );
+        assert!(matches!(err, RuntimeError::NoSuchField { .. }));
+    }
+
+    #[test]
+    fn satisfies_and_extends() {
+        let src = format!(
+            "{SHAPES} [(satisfies? Shape (->Rect 1 2)) (satisfies? Shape 1)
+                       (extends? Shape Rect) (extends? Shape String)]"
+        );
+        assert_eq!(printed(&src), "[true false true false]");
+    }
+
+    // --- types ---
+
+    #[test]
+    fn instance_and_type() {
+        let src = format!(
+            "{SHAPES} [(instance? Rect (->Rect 1 2)) (instance? Rect {{:w 1}})
+                       (instance? String \"s\") (= (type 1) Long) (type (->Rect 1 2))]"
+        );
+        assert_eq!(printed(&src), "[true false true true Rect]");
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn printed(source: &str) -> String {
+        Readable(&run(source)).to_string()
+    }
+
+    // --- reduced / reduced? / unreduced ---
+
+    #[test]
+    fn reduced_wraps_value() {
+        assert_eq!(run("(reduced? (reduced 1))"), Value::Bool(true));
+        assert_eq!(run("(reduced? 1)"), Value::Bool(false));
+        assert_eq!(printed("(reduced \"a\")"), "#<reduced \"a\">");
+    }
+
+    #[test]
+    fn unreduced_unwraps_once() {
+        assert_eq!(run("(unreduced (reduced 2))"), Value::Long(2));
+        assert_eq!(run("(unreduced 3)"), Value::Long(3));
+        assert_eq!(
+            run("(reduced? (unreduced (reduced (reduced 1))))"),
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn reduced_wrong_arity() {
+        assert!(matches!(
+            run_err("(reduced)"),
+            RuntimeError::WrongArity { expected: 1, .. }
+        ));
+    }
+
+    // --- reduce stops early ---
+
+    #[test]
+    fn reduce_stops_at_reduced() {
+        assert_eq!(
+            run("(reduce (fn [a x] (if (> x 2) (reduced a) (+ a x))) 0 [1 2 3 4])"),
+            Value::Long(3)
+        );
+    }
+
+    #[test]
+    fn reduce_does_not_call_f_after_reduced() {
+        // The second element would fail to add, so it must never be reached
+        assert_eq!(
+            run("(reduce (fn [a x] (reduced (+ a x))) 0 [1 :not-a-number])"),
+            Value::Long(1)
+        );
+    }
+
+    #[test]
+    fn reduce_without_init_stops_early() {
+        assert_eq!(
+            run("(reduce (fn [a x] (if (= x 3) (reduced :found) x)) [1 2 3 :boom])"),
+            Value::Keyword("found".into())
+        );
+    }
+
+    #[test]
+    fn reductions_stop_at_reduced() {
+        assert_eq!(
+            printed("(reductions (fn [a x] (if (> x 2) (reduced a) (+ a x))) [1 2 3 4])"),
+            "(1 3 3)"
+        );
+    }
+
+    // --- reduce-kv ---
+
+    #[test]
+    fn reduce_kv_over_map() {
+        assert_eq!(
+            run("(= (reduce-kv (fn [m k v] (assoc m v k)) {} {:a 1 :b 2}) {1 :a 2 :b})"),
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn reduce_kv_over_vector_uses_indices() {
+        assert_eq!(
+            printed("(reduce-kv (fn [acc i x] (conj acc [i x])) [] [:a :b])"),
+            "[[0 :a] [1 :b]]"
+        );
+    }
+
+    #[test]
+    fn reduce_kv_stops_at_reduced() {
+        assert_eq!(
+            run("(reduce-kv (fn [acc i x] (if (= i 1) (reduced x) acc)) nil [:a :b :c])"),
+            Value::Keyword("b".into())
+        );
+    }
+
+    #[test]
+    fn reduce_kv_over_nil_returns_init() {
+        assert_eq!(run("(reduce-kv (fn [a k v] k) 7 nil)"), Value::Long(7));
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.into())
+    }
+
+    // --- literals / re-pattern ---
+
+    #[test]
+    fn regex_literal_evaluates_to_regex() {
+        assert!(matches!(run(r#"#"a+""#), Value::Regex(re) if re.as_str() == "a+"));
+    }
+
+    #[test]
+    fn regex_literal_backslashes_are_verbatim() {
+        assert_eq!(run(r#"(re-find #"\d+" "ab12c")"#), string("12"));
+        assert_eq!(run(r#"(re-find #"\"" "a\"b")"#), string("\""));
+    }
+
+    #[test]
+    fn re_pattern_from_string() {
+        assert_eq!(run(r#"(re-find (re-pattern "b+") "abbc")"#), string("bb"));
+    }
+
+    #[test]
+    fn re_pattern_invalid() {
+        let err = run_err(r#"(re-pattern "(a")"#);
+        assert!(matches!(err, RuntimeError::InvalidRegex { span, .. } if span.lo == 12));
+        assert!(err.to_string().starts_with("(invalid-regex"));
+    }
+
+    #[test]
+    fn str_and_pr_str_of_regex() {
+        assert_eq!(run(r#"(str #"a.b")"#), string("a.b"));
+        assert_eq!(run(r#"(pr-str #"a.b")"#), string(r#"#"a.b""#));
+    }
+
+    // --- re-find / re-matches / re-seq ---
+
+    #[test]
+    fn re_find_no_match_is_nil() {
+        assert!(matches!(run(r#"(re-find #"z" "abc")"#), Value::Nil));
+    }
+
+    #[test]
+    fn re_find_groups_as_vector() {
+        assert!(matches!(run(r#"(re-find #"(\w)(\d)?" "a-b")"#),
+            Value::Vector(v) if *v == vec![string("a"), string("a"), Value::Nil]));
+    }
+
+    #[test]
+    fn re_matches_whole_string_only() {
+        assert_eq!(run(r#"(re-matches #"\d+" "123")"#), string("123"));
+        assert!(matches!(run(r#"(re-matches #"\d+" "123a")"#), Value::Nil));
+    }
+
+    #[test]
+    fn re_matches_tries_longer_alternatives() {
+        assert_eq!(run(r#"(re-matches #"a|ab" "ab")"#), string("ab"));
+    }
+
+    #[test]
+    fn re_seq_all_matches() {
+        assert!(matches!(run(r#"(re-seq #"\d" "a1b2c3")"#),
+            Value::List(l) if l == vec![string("1"), string("2"), string("3")].into_iter().collect()));
+        assert!(matches!(run(r#"(re-seq #"\d" "abc")"#), Value::Nil));
+    }
+
+    #[test]
+    fn re_seq_with_groups() {
+        assert_eq!(
+            run(r#"(first (re-seq #"(\w)=(\d)" "a=1 b=2"))"#),
+            run(r#"["a=1" "a" "1"]"#)
+        );
+    }
+
+    #[test]
+    fn re_find_type_errors() {
+        assert!(matches!(
+            run_err(r#"(re-find "a" "abc")"#),
+            RuntimeError::TypeError {
+                expected: "regex",
+                ..
+            }
+        ));
+        assert!(matches!(
+            run_err(r#"(re-find #"a" 1)"#),
+            RuntimeError::TypeError {
+                expected: "string",
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,216 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn printed(source: &str) -> String {
+        Readable(&run(source)).to_string()
+    }
+
+    // --- seq / nil as the empty seq ---
+
+    #[test]
+    fn seq_of_empty_is_nil() {
+        assert_eq!(run("(seq [])"), Value::Nil);
+        assert_eq!(run("(seq nil)"), Value::Nil);
+        assert_eq!(printed("(seq [1 2])"), "(1 2)");
+        assert_eq!(printed("(seq \"ab\")"), "(\\a \\b)");
+    }
+
+    #[test]
+    fn nil_behaves_as_empty_seq() {
+        assert_eq!(run("(first nil)"), Value::Nil);
+        assert_eq!(run("(count nil)"), Value::Long(0));
+        assert_eq!(run("(empty? nil)"), Value::Bool(true));
+        assert_eq!(printed("(rest nil)"), "()");
+        assert_eq!(printed("(cons 1 nil)"), "(1)");
+    }
+
+    #[test]
+    fn sequential_pred() {
+        assert_eq!(run("(sequential? [1])"), Value::Bool(true));
+        assert_eq!(run("(sequential? '(1))"), Value::Bool(true));
+        assert_eq!(run("(sequential? {:a 1})"), Value::Bool(false));
+    }
+
+    // --- map over several collections ---
+
+    #[test]
+    fn map_multiple_collections_stops_at_shortest() {
+        assert_eq!(printed("(map + [1 2 3] [10 20])"), "[11 22]");
+        assert_eq!(
+            printed("(map vector [1 2] \"ab\" [:x :y :z])"),
+            "[[1 \\a :x] [2 \\b :y]]"
+        );
+    }
+
+    // --- take / drop ---
+
+    #[test]
+    fn take_and_drop() {
+        assert_eq!(printed("(take 2 [1 2 3])"), "(1 2)");
+        assert_eq!(printed("(take 5 [1])"), "(1)");
+        assert_eq!(printed("(drop 2 [1 2 3])"), "(3)");
+        assert_eq!(printed("(drop 5 [1])"), "()");
+        assert_eq!(printed("(drop 0 {:a 1})"), "([:a 1])");
+    }
+
+    #[test]
+    fn take_and_drop_while() {
+        assert_eq!(printed("(take-while odd? [1 3 4 5])"), "(1 3)");
+        assert_eq!(printed("(drop-while odd? [1 3 4 5])"), "(4 5)");
+        assert_eq!(printed("(take-while odd? [])"), "()");
+    }
+
+    #[test]
+    fn take_and_drop_last() {
+        assert_eq!(printed("(take-last 2 [1 2 3])"), "(2 3)");
+        assert_eq!(printed("(drop-last [1 2 3])"), "(1 2)");
+        assert_eq!(printed("(drop-last 2 [1 2 3])"), "(1)");
+    }
+
+    #[test]
+    fn split_at_and_with() {
+        assert_eq!(printed("(split-at 1 [1 2 3])"), "[(1) (2 3)]");
+        assert_eq!(printed("(split-with odd? [1 3 2 5])"), "[(1 3) (2 5)]");
+    }
+
+    // --- partitions ---
+
+    #[test]
+    fn partition_drops_incomplete_chunks() {
+        assert_eq!(printed("(partition 2 [1 2 3 4 5])"), "((1 2) (3 4))");
+        assert_eq!(printed("(partition 2 1 [1 2 3])"), "((1 2) (2 3))");
+        assert_eq!(
+            printed("(partition 3 3 [:a] [1 2 3 4])"),
+            "((1 2 3) (4 :a))"
+        );
+    }
+
+    #[test]
+    fn partition_all_keeps_tail() {
+        assert_eq!(printed("(partition-all 2 [1 2 3])"), "((1 2) (3))");
+        assert_eq!(printed("(partition-all 2 1 [1 2 3])"), "((1 2) (2 3) (3))");
+    }
+
+    #[test]
+    fn partition_by_runs() {
+        assert_eq!(
+            printed("(partition-by odd? [1 3 2 4 5])"),
+            "((1 3) (2 4) (5))"
+        );
+        assert_eq!(printed("(partition-by odd? [])"), "()");
+    }
+
+    // --- grouping and counting ---
+
+    #[test]
+    fn group_by_keeps_order_within_groups() {
+        assert!(run("(= (group-by odd? [1 2 3]) {true [1 3] false [2]})") == Value::Bool(true));
+    }
+
+    #[test]
+    fn frequencies_counts() {
+        assert!(run("(= (frequencies [:a :b :a]) {:a 2 :b 1})") == Value::Bool(true));
+    }
+
+    #[test]
+    fn distinct_and_dedupe() {
+        assert_eq!(printed("(distinct [1 2 1 3 2])"), "(1 2 3)");
+        assert_eq!(printed("(dedupe [1 1 2 1 1])"), "(1 2 1)");
+    }
+
+    // --- combining ---
+
+    #[test]
+    fn interleave_and_interpose() {
+        assert_eq!(printed("(interleave [1 2 3] [:a :b])"), "(1 :a 2 :b)");
+        assert_eq!(printed("(interpose :x [1 2 3])"), "(1 :x 2 :x 3)");
+        assert_eq!(printed("(interpose :x [])"), "()");
+    }
+
+    #[test]
+    fn flatten_nested() {
+        assert_eq!(printed("(flatten [1 [2 [3 [4]] '(5)] 6])"), "(1 2 3 4 5 6)");
+        assert_eq!(printed("(flatten [{:a 1}])"), "({:a 1})");
+        assert_eq!(printed("(flatten 1)"), "()");
+    }
+
+    #[test]
+    fn concat_and_mapcat() {
+        assert_eq!(printed("(concat [1] '(2) nil \"a\")"), "(1 2 \\a)");
+        assert_eq!(printed("(mapcat (fn [x] [x x]) [1 2])"), "(1 1 2 2)");
+        assert_eq!(printed("(mapcat list [1 2] [:a :b])"), "(1 :a 2 :b)");
+    }
+
+    #[test]
+    fn keep_remove_and_map_indexed() {
+        assert_eq!(
+            printed("(keep (fn [x] (when (odd? x) (* x 10))) [1 2 3])"),
+            "(10 30)"
+        );
+        assert_eq!(printed("(keep (fn [x] x) [false nil 1])"), "(false 1)");
+        assert_eq!(printed("(remove odd? [1 2 3])"), "(2)");
+        assert_eq!(printed("(map-indexed vector [:a :b])"), "([0 :a] [1 :b])");
+    }
+
+    #[test]
+    fn reductions_intermediate_values() {
+        assert_eq!(printed("(reductions + [1 2 3])"), "(1 3 6)");
+        assert_eq!(printed("(reductions + 10 [1 2])"), "(10 11 13)");
+        assert_eq!(printed("(reductions + [])"), "(0)");
+    }
+
+    // --- function combinators ---
+
+    #[test]
+    fn comp_applies_right_to_left() {
+        assert_eq!(run("((comp inc inc *) 2 3)"), Value::Long(8));
+        assert_eq!(run("((comp) 4)"), Value::Long(4));
+        assert_eq!(printed("((comp str inc) 1)"), "\"2\"");
+    }
+
+    #[test]
+    fn partial_juxt_and_friends() {
+        assert_eq!(run("((partial + 1 2) 3)"), Value::Long(6));
+        assert_eq!(printed("((juxt inc dec) 5)"), "[6 4]");
+        assert_eq!(run("((complement odd?) 2)"), Value::Bool(true));
+        assert_eq!(run("((constantly 7) 1 2)"), Value::Long(7));
+        assert_eq!(run("(identity 3)"), Value::Long(3));
+    }
+
+    #[test]
+    fn fnil_replaces_nil_arguments() {
+        assert_eq!(run("((fnil + 0) nil 5)"), Value::Long(5));
+        assert_eq!(run("((fnil + 0 0) 1 nil)"), Value::Long(1));
+        assert!(run("(= (update {:a nil} :a (fnil inc 10)) {:a 11})") == Value::Bool(true));
+    }
+
+    #[test]
+    fn not_is_truthiness_based() {
+        assert_eq!(run("(not 1)"), Value::Bool(false));
+        assert_eq!(run("(not nil)"), Value::Bool(true));
+        assert_eq!(run("(not false)"), Value::Bool(true));
+    }
+
+    #[test]
+    fn wrong_arity() {
+        assert!(matches!(
+            run_err("(take 1 2 3)"),
+            RuntimeError::WrongArity { .. }
+        ));
+        assert!(matches!(
+            run_err("(partition 1 2 3 4 5)"),
+            RuntimeError::WrongArity { .. }
+        ));
+    }
+}
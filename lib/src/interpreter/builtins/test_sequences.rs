@@ -348,4 +348,46 @@ mod tests {
             RuntimeError::WrongArity { expected: 2, .. }
         ));
     }
+
+    // --- strings as sequences ---
+
+    #[test]
+    fn count_string_counts_chars() {
+        assert!(matches!(run("(count \"héllo\")"), Value::Long(5)));
+    }
+
+    #[test]
+    fn first_last_of_string() {
+        assert_eq!(run("(first \"éa\")"), Value::Char('é'));
+        assert_eq!(run("(last \"aé\")"), Value::Char('é'));
+        assert_eq!(run("(second \"aé\")"), Value::Char('é'));
+        assert!(matches!(run("(first \"\")"), Value::Nil));
+    }
+
+    #[test]
+    fn rest_of_string_is_list_of_chars() {
+        assert!(matches!(run("(rest \"abc\")"), Value::List(l) if l.len() == 2));
+    }
+
+    #[test]
+    fn nth_of_string() {
+        assert_eq!(run("(nth \"日本語\" 1)"), Value::Char('本'));
+        assert!(matches!(
+            run_err("(nth \"ab\" 2)"),
+            RuntimeError::IndexOutOfBounds { .. }
+        ));
+    }
+
+    #[test]
+    fn map_over_string() {
+        assert!(matches!(
+            run("(map int \"ab\")"),
+            Value::Vector(v) if *v == vec![Value::Long(97), Value::Long(98)]
+        ));
+    }
+
+    #[test]
+    fn empty_string() {
+        assert!(matches!(run("(empty? \"\")"), Value::Bool(true)));
+    }
 }
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn printed(source: &str) -> String {
+        Readable(&run(source)).to_string()
+    }
+
+    fn truthy(source: &str) -> bool {
+        run(source) == Value::Bool(true)
+    }
+
+    #[test]
+    fn set_equality_ignores_order() {
+        assert!(truthy("(= #{1 2 3} #{3 1 2})"));
+        assert!(!truthy("(= #{1 2} #{1 2 3})"));
+    }
+
+    // --- disj ---
+
+    #[test]
+    fn disj_removes_items() {
+        assert!(truthy("(= (disj #{1 2 3} 2 3) #{1})"));
+        assert!(truthy("(= (disj #{1} 9) #{1})"));
+        assert_eq!(printed("(disj (sorted-set 1 2 3) 2)"), "#{1 3}");
+        assert_eq!(run("(disj nil 1)"), Value::Nil);
+    }
+
+    #[test]
+    fn disj_type_error() {
+        assert!(matches!(
+            run_err("(disj [1 2] 1)"),
+            RuntimeError::TypeError {
+                expected: "set",
+                ..
+            }
+        ));
+    }
+
+    // --- risp.set ---
+
+    #[test]
+    fn union_of_sets() {
+        assert!(truthy("(= (risp.set/union #{1 2} #{2 3}) #{1 2 3})"));
+        assert!(truthy("(= (risp.set/union) #{})"));
+        assert_eq!(
+            printed("(risp.set/union (sorted-set 3) #{1 2})"),
+            "#{1 2 3}"
+        );
+    }
+
+    #[test]
+    fn intersection_of_sets() {
+        assert!(truthy(
+            "(= (risp.set/intersection #{1 2 3} #{2 3 4} #{3 2}) #{2 3})"
+        ));
+        assert!(truthy("(= (risp.set/intersection #{1} #{2}) #{})"));
+    }
+
+    #[test]
+    fn difference_of_sets() {
+        assert!(truthy("(= (risp.set/difference #{1 2 3} #{2} #{3}) #{1})"));
+        assert_eq!(
+            printed("(risp.set/difference (sorted-set 1 2 3) #{2})"),
+            "#{1 3}"
+        );
+    }
+
+    #[test]
+    fn subset_and_superset() {
+        assert!(truthy("(risp.set/subset? #{1} #{1 2})"));
+        assert!(truthy("(risp.set/subset? #{} #{})"));
+        assert!(!truthy("(risp.set/subset? #{1 3} #{1 2})"));
+        assert!(truthy("(risp.set/superset? #{1 2} #{2})"));
+        assert!(!truthy("(risp.set/superset? #{1} #{1 2})"));
+    }
+
+    #[test]
+    fn set_functions_need_sets() {
+        assert!(matches!(
+            run_err("(risp.set/union #{1} [2])"),
+            RuntimeError::TypeError {
+                expected: "set",
+                ..
+            }
+        ));
+        assert!(matches!(
+            run_err("(risp.set/subset? [1] #{1})"),
+            RuntimeError::TypeError {
+                expected: "set",
+                ..
+            }
+        ));
+    }
+}
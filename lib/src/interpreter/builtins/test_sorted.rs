@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn printed(source: &str) -> String {
+        Readable(&run(source)).to_string()
+    }
+
+    // --- sorted-map ---
+
+    #[test]
+    fn sorted_map_orders_keys() {
+        assert_eq!(printed("(sorted-map :c 3 :a 1 :b 2)"), "{:a 1, :b 2, :c 3}");
+        assert_eq!(
+            printed("(str (sorted-map 2 \"b\" 1 \"a\"))"),
+            "\"{1 a, 2 b}\""
+        );
+    }
+
+    #[test]
+    fn sorted_map_by_comparator() {
+        assert_eq!(
+            printed("(sorted-map-by > 1 :a 3 :c 2 :b)"),
+            "{3 :c, 2 :b, 1 :a}"
+        );
+    }
+
+    #[test]
+    fn sorted_map_later_value_wins() {
+        assert_eq!(printed("(sorted-map :a 1 :a 2)"), "{:a 2}");
+    }
+
+    #[test]
+    fn sorted_map_equals_hash_map() {
+        assert_eq!(
+            run("(= (sorted-map :b 2 :a 1) {:a 1 :b 2})"),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            run("(= {:a 1 :b 2} (sorted-map :b 2 :a 1))"),
+            Value::Bool(true)
+        );
+        assert_eq!(run("(= (sorted-map :a 1) {:a 2})"), Value::Bool(false));
+    }
+
+    #[test]
+    fn sorted_map_conj() {
+        assert_eq!(printed("(conj (sorted-map :b 2) [:a 1])"), "{:a 1, :b 2}");
+        assert_eq!(
+            printed("(conj (sorted-map :b 2) {:c 3 :a 1})"),
+            "{:a 1, :b 2, :c 3}"
+        );
+    }
+
+    #[test]
+    fn sorted_map_is_persistent() {
+        assert_eq!(
+            printed("(let [m (sorted-map 1 1)] (do (conj m [0 0]) m))"),
+            "{1 1}"
+        );
+    }
+
+    #[test]
+    fn sorted_map_seq_functions() {
+        assert_eq!(printed("(first (sorted-map 2 :b 1 :a))"), "[1 :a]");
+        assert_eq!(printed("(last (sorted-map 2 :b 1 :a))"), "[2 :b]");
+        assert_eq!(run("(count (sorted-map 2 :b 1 :a))"), Value::Long(2));
+        assert_eq!(run("(empty? (sorted-map))"), Value::Bool(true));
+    }
+
+    #[test]
+    fn sorted_map_odd_arguments() {
+        assert!(matches!(
+            run_err("(sorted-map 1 2 3)"),
+            RuntimeError::WrongArity { .. }
+        ));
+    }
+
+    // --- sorted-set ---
+
+    #[test]
+    fn sorted_set_orders_and_dedups() {
+        assert_eq!(printed("(sorted-set 3 1 2 1)"), "#{1 2 3}");
+        assert_eq!(printed("(sorted-set-by > 3 1 2)"), "#{3 2 1}");
+    }
+
+    #[test]
+    fn sorted_set_mixed_numbers() {
+        assert_eq!(printed("(sorted-set 1.5 1/2 2N 0)"), "#{0 1/2 1.5 2N}");
+    }
+
+    #[test]
+    fn sorted_set_equals_set() {
+        assert_eq!(run("(= (sorted-set 2 1) #{1 2})"), Value::Bool(true));
+        assert_eq!(run("(= (sorted-set 2 1) #{1 3})"), Value::Bool(false));
+    }
+
+    #[test]
+    fn sorted_set_seq_functions() {
+        assert_eq!(run("(first (sorted-set 5 3 4))"), Value::Long(3));
+        assert_eq!(run("(last (sorted-set 5 3 4))"), Value::Long(5));
+        assert_eq!(run("(second (sorted-set 5 3 4))"), Value::Long(4));
+        assert_eq!(printed("(rest (sorted-set 5 3 4))"), "(4 5)");
+        assert_eq!(printed("(conj (sorted-set 5 3) 4)"), "#{3 4 5}");
+        assert_eq!(printed("(cons 0 (sorted-set 2 1))"), "(0 1 2)");
+    }
+
+    #[test]
+    fn sorted_set_uncomparable_items() {
+        assert!(matches!(
+            run_err("(sorted-set 1 :a)"),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+
+    #[test]
+    fn sorted_predicate() {
+        assert_eq!(run("(sorted? (sorted-set))"), Value::Bool(true));
+        assert_eq!(run("(sorted? (sorted-map))"), Value::Bool(true));
+        assert_eq!(run("(sorted? #{})"), Value::Bool(false));
+    }
+
+    // --- subseq / rsubseq ---
+
+    #[test]
+    fn subseq_single_bound() {
+        let s = "(sorted-set 1 3 5 7 9)";
+        assert_eq!(printed(&format!("(subseq {s} > 3)")), "(5 7 9)");
+        assert_eq!(printed(&format!("(subseq {s} >= 3)")), "(3 5 7 9)");
+        assert_eq!(printed(&format!("(subseq {s} < 5)")), "(1 3)");
+        assert_eq!(printed(&format!("(subseq {s} <= 5)")), "(1 3 5)");
+    }
+
+    #[test]
+    fn subseq_two_bounds() {
+        assert_eq!(
+            printed("(subseq (sorted-set 1 3 5 7 9) >= 3 < 9)"),
+            "(3 5 7)"
+        );
+        assert_eq!(printed("(subseq (sorted-set 1 3 5) > 5 < 9)"), "()");
+    }
+
+    #[test]
+    fn rsubseq_bounds() {
+        let s = "(sorted-set 1 3 5 7 9)";
+        assert_eq!(printed(&format!("(rsubseq {s} < 7)")), "(5 3 1)");
+        assert_eq!(printed(&format!("(rsubseq {s} > 3)")), "(9 7 5)");
+        assert_eq!(printed(&format!("(rsubseq {s} >= 3 <= 7)")), "(7 5 3)");
+    }
+
+    #[test]
+    fn subseq_on_sorted_map() {
+        assert_eq!(
+            printed("(subseq (sorted-map :a 1 :b 2 :c 3) >= :b)"),
+            "([:b 2] [:c 3])"
+        );
+    }
+
+    #[test]
+    fn subseq_follows_the_comparator() {
+        assert_eq!(printed("(subseq (sorted-set-by > 1 2 3 4) > 2)"), "(1)");
+    }
+
+    #[test]
+    fn subseq_errors() {
+        assert!(matches!(
+            run_err("(subseq #{1 2} > 1)"),
+            RuntimeError::TypeError { .. }
+        ));
+        assert!(matches!(
+            run_err("(subseq (sorted-set 1) = 1)"),
+            RuntimeError::TypeError { .. }
+        ));
+        assert!(matches!(
+            run_err("(subseq (sorted-set 1) > 1 <)"),
+            RuntimeError::WrongArity { .. }
+        ));
+    }
+
+    #[test]
+    fn leaderboard() {
+        assert_eq!(
+            printed(
+                "(let [board (sorted-map-by > 120 \"ann\" 95 \"bob\" 210 \"cy\")]
+                   (first board))"
+            ),
+            "[210 \"cy\"]"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn longs(items: &[i64]) -> Value {
+        Value::List(items.iter().map(|n| Value::Long(*n)).collect())
+    }
+
+    // --- compare ---
+
+    #[test]
+    fn compare_numbers_across_types() {
+        assert_eq!(run("(compare 1 2)"), Value::Long(-1));
+        assert_eq!(run("(compare 2 2.0)"), Value::Long(0));
+        assert_eq!(run("(compare 1/2 0.25)"), Value::Long(1));
+        assert_eq!(run("(compare 10N 9)"), Value::Long(1));
+    }
+
+    #[test]
+    fn compare_nil_first() {
+        assert_eq!(run("(compare nil 0)"), Value::Long(-1));
+        assert_eq!(run("(compare \"\" nil)"), Value::Long(1));
+        assert_eq!(run("(compare nil nil)"), Value::Long(0));
+    }
+
+    #[test]
+    fn compare_text_like_values() {
+        assert_eq!(run("(compare \"abc\" \"abd\")"), Value::Long(-1));
+        assert_eq!(run("(compare :b :a)"), Value::Long(1));
+        assert_eq!(run("(compare 'x 'x)"), Value::Long(0));
+        assert_eq!(run("(compare \\a \\b)"), Value::Long(-1));
+        assert_eq!(run("(compare false true)"), Value::Long(-1));
+    }
+
+    #[test]
+    fn compare_vectors_lexicographically() {
+        assert_eq!(run("(compare [1 2] [1 3])"), Value::Long(-1));
+        assert_eq!(run("(compare [1 2] [1])"), Value::Long(1));
+        assert_eq!(run("(compare [1 [2]] '(1 [2]))"), Value::Long(0));
+    }
+
+    #[test]
+    fn compare_insts() {
+        assert_eq!(
+            run(r#"(compare #inst "2026-01-01" #inst "2025-01-01")"#),
+            Value::Long(1)
+        );
+    }
+
+    #[test]
+    fn compare_unrelated_types() {
+        assert!(matches!(
+            run_err("(compare 1 \"a\")"),
+            RuntimeError::TypeError {
+                expected: "long",
+                got: "string",
+                ..
+            }
+        ));
+        assert!(matches!(
+            run_err("(compare {} {})"),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+
+    // --- sort ---
+
+    #[test]
+    fn sort_natural_order() {
+        assert_eq!(run("(sort [3 1 2])"), longs(&[1, 2, 3]));
+        assert_eq!(run("(sort '(\"b\" \"a\"))"), run("'(\"a\" \"b\")"));
+        assert_eq!(run("(sort [])"), longs(&[]));
+        assert_eq!(run("(sort nil)"), longs(&[]));
+    }
+
+    #[test]
+    fn sort_returns_a_list() {
+        assert!(matches!(run("(sort [2 1])"), Value::List(_)));
+    }
+
+    #[test]
+    fn sort_with_bool_comparator() {
+        assert_eq!(run("(sort > [3 1 2])"), longs(&[3, 2, 1]));
+    }
+
+    #[test]
+    fn sort_with_long_comparator() {
+        assert_eq!(run("(sort (fn [a b] (- b a)) [3 1 2])"), longs(&[3, 2, 1]));
+        assert_eq!(run("(sort compare [3 1 2])"), longs(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        assert_eq!(
+            run("(sort (fn [a b] (< (first a) (first b))) [[1 :a] [0 :b] [1 :c] [0 :d]])"),
+            run("'([0 :b] [0 :d] [1 :a] [1 :c])")
+        );
+    }
+
+    #[test]
+    fn sort_mixed_types_error() {
+        assert!(matches!(
+            run_err("(sort [1 :a])"),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+
+    #[test]
+    fn sort_bad_comparator_result() {
+        assert!(matches!(
+            run_err("(sort (fn [a b] \"?\") [1 2])"),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+
+    #[test]
+    fn sort_propagates_comparator_errors() {
+        assert!(matches!(
+            run_err("(sort (fn [a b] (undefined a b)) [1 2])"),
+            RuntimeError::UndefinedVariable { .. }
+        ));
+    }
+
+    #[test]
+    fn sort_wrong_arity() {
+        assert!(matches!(run_err("(sort)"), RuntimeError::WrongArity { .. }));
+    }
+
+    // --- sort-by ---
+
+    #[test]
+    fn sort_by_key() {
+        assert_eq!(
+            run("(sort-by count [\"ccc\" \"a\" \"bb\"])"),
+            run("'(\"a\" \"bb\" \"ccc\")")
+        );
+    }
+
+    #[test]
+    fn sort_by_key_and_comparator_is_stable() {
+        assert_eq!(
+            run("(sort-by first > [[1 :a] [2 :b] [1 :c] [2 :d]])"),
+            run("'([2 :b] [2 :d] [1 :a] [1 :c])")
+        );
+    }
+
+    #[test]
+    fn sort_by_computed_key() {
+        assert_eq!(
+            run("(sort-by (fn [v] (nth v 1)) [[:a 3] [:b 1] [:c 2]])"),
+            run("'([:b 1] [:c 2] [:a 3])")
+        );
+    }
+
+    // --- reverse ---
+
+    #[test]
+    fn reverse_collections() {
+        assert_eq!(run("(reverse [1 2 3])"), longs(&[3, 2, 1]));
+        assert_eq!(run("(reverse '(1 2))"), longs(&[2, 1]));
+        assert_eq!(run("(reverse nil)"), longs(&[]));
+    }
+
+    #[test]
+    fn reverse_type_error() {
+        assert!(matches!(
+            run_err("(reverse 1)"),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+
+    // --- min-key / max-key ---
+
+    #[test]
+    fn max_key_and_min_key() {
+        assert_eq!(
+            run("(max-key count \"ab\" \"c\" \"def\")"),
+            Value::String("def".into())
+        );
+        assert_eq!(
+            run("(min-key count \"ab\" \"c\" \"def\")"),
+            Value::String("c".into())
+        );
+        assert_eq!(run("(max-key inc 5)"), Value::Long(5));
+    }
+
+    #[test]
+    fn max_key_ties_keep_the_last() {
+        assert_eq!(
+            run("(max-key count \"ab\" \"cd\")"),
+            Value::String("cd".into())
+        );
+    }
+}
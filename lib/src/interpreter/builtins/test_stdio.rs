@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io::Write;
+    use std::rc::Rc;
+
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+
+    #[derive(Clone, Default)]
+    struct Capture(Rc<RefCell<Vec<u8>>>);
+
+    impl Capture {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn captured() -> (Interpreter, Capture, Capture) {
+        let out = Capture::default();
+        let err = Capture::default();
+        let mut interpreter = Interpreter::new();
+        interpreter.set_out(Box::new(out.clone()));
+        interpreter.set_err(Box::new(err.clone()));
+        (interpreter, out, err)
+    }
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    // --- ports ---
+
+    #[test]
+    fn println_writes_to_out_port() {
+        let (mut interpreter, out, err) = captured();
+        interpreter.run("(println \"a\" 1)").unwrap();
+        assert_eq!(out.contents(), "a1\n");
+        assert_eq!(err.contents(), "");
+    }
+
+    #[test]
+    fn write_builtin_writes_to_out_port() {
+        let (mut interpreter, out, _) = captured();
+        interpreter.run("(risp.internal/write \"raw\")").unwrap();
+        assert_eq!(out.contents(), "raw");
+    }
+
+    #[test]
+    fn write_unsupported_type() {
+        assert!(matches!(
+            run_err("(risp.internal/write 1)"),
+            RuntimeError::UnsupportedType { .. }
+        ));
+    }
+
+    #[test]
+    fn binding_out_to_err_port() {
+        let (mut interpreter, out, err) = captured();
+        interpreter
+            .run("(binding [*out* *err*] (print \"oops\"))")
+            .unwrap();
+        assert_eq!(out.contents(), "");
+        assert_eq!(err.contents(), "oops");
+    }
+
+    #[test]
+    fn binding_restores_previous_value() {
+        let (mut interpreter, out, _) = captured();
+        interpreter
+            .run("(do (binding [*out* *err*] (print 1)) (print 2))")
+            .unwrap();
+        assert_eq!(out.contents(), "2");
+    }
+
+    #[test]
+    fn binding_restores_previous_value_on_error() {
+        let (mut interpreter, out, _) = captured();
+        assert!(interpreter
+            .run("(binding [*out* *err*] (undefined-fn))")
+            .is_err());
+        interpreter.run("(print 3)").unwrap();
+        assert_eq!(out.contents(), "3");
+    }
+
+    #[test]
+    fn binding_undefined_var() {
+        assert!(matches!(
+            run_err("(binding [*out* 1 *nope* 1] 2)"),
+            RuntimeError::UndefinedVariable { name, span } if name == "*nope*" && span.lo == 18
+        ));
+    }
+
+    #[test]
+    fn write_to_err_port_directly() {
+        let (mut interpreter, out, err) = captured();
+        interpreter
+            .run("(do (risp.io/write *err* \"oops\") (print 1))")
+            .unwrap();
+        assert_eq!(out.contents(), "1");
+        assert_eq!(err.contents(), "oops");
+    }
+
+    // --- with-out-str ---
+
+    #[test]
+    fn with_out_str_captures_output() {
+        assert!(matches!(
+            run("(with-out-str (do (print \"a\") (println \"b\")))"),
+            Value::String(s) if s.as_ref() == "ab\n"
+        ));
+    }
+
+    #[test]
+    fn with_out_str_does_not_leak_to_out() {
+        let (mut interpreter, out, _) = captured();
+        interpreter.run("(with-out-str (print \"x\"))").unwrap();
+        interpreter.run("(print \"y\")").unwrap();
+        assert_eq!(out.contents(), "y");
+    }
+
+    #[test]
+    fn with_out_str_nested() {
+        assert!(matches!(
+            run("(with-out-str (do (print 1) (print (with-out-str (print 2))) (print 3)))"),
+            Value::String(s) if s.as_ref() == "123"
+        ));
+    }
+}
@@ -0,0 +1,248 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.into())
+    }
+
+    fn strings(items: &[&str]) -> Vec<Value> {
+        items.iter().map(|s| string(s)).collect()
+    }
+
+    // --- subs ---
+
+    #[test]
+    fn subs_from_start() {
+        assert_eq!(run("(risp.string/subs \"hello\" 1)"), string("ello"));
+    }
+
+    #[test]
+    fn subs_with_end() {
+        assert_eq!(run("(risp.string/subs \"hello\" 1 3)"), string("el"));
+    }
+
+    #[test]
+    fn subs_is_unicode_aware() {
+        assert_eq!(run("(risp.string/subs \"héllo wörld\" 1 4)"), string("éll"));
+        assert_eq!(run("(risp.string/subs \"日本語\" 2)"), string("語"));
+    }
+
+    #[test]
+    fn subs_out_of_bounds() {
+        assert!(matches!(
+            run_err("(risp.string/subs \"abc\" 1 4)"),
+            RuntimeError::IndexOutOfBounds { got: 4, .. }
+        ));
+    }
+
+    #[test]
+    fn subs_start_after_end() {
+        assert!(matches!(
+            run_err("(risp.string/subs \"abc\" 2 1)"),
+            RuntimeError::IndexOutOfBounds { .. }
+        ));
+    }
+
+    #[test]
+    fn subs_arity_reports_nearest_bound() {
+        assert!(matches!(
+            run_err("(risp.string/subs \"abc\" 0 1 2)"),
+            RuntimeError::WrongArity {
+                expected: 3,
+                got: 4,
+                ..
+            }
+        ));
+        assert!(matches!(
+            run_err("(risp.string/subs \"abc\")"),
+            RuntimeError::WrongArity {
+                expected: 2,
+                got: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn subs_type_error() {
+        assert!(matches!(
+            run_err("(risp.string/subs 1 0)"),
+            RuntimeError::TypeError {
+                expected: "string",
+                ..
+            }
+        ));
+    }
+
+    // --- split / split-lines / join ---
+
+    #[test]
+    fn split_on_separator() {
+        assert!(matches!(run("(risp.string/split \"a,b,c\" \",\")"),
+            Value::Vector(v) if *v == strings(&["a", "b", "c"])));
+    }
+
+    #[test]
+    fn split_drops_trailing_empty_parts() {
+        assert!(matches!(run("(risp.string/split \"a,,b,,\" \",\")"),
+            Value::Vector(v) if *v == strings(&["a", "", "b"])));
+    }
+
+    #[test]
+    fn split_with_limit() {
+        assert!(matches!(run("(risp.string/split \"a,b,c\" \",\" 2)"),
+            Value::Vector(v) if *v == strings(&["a", "b,c"])));
+    }
+
+    #[test]
+    fn split_empty_separator_yields_chars() {
+        assert!(matches!(run("(risp.string/split \"aé\" \"\")"),
+            Value::Vector(v) if *v == strings(&["a", "é"])));
+    }
+
+    #[test]
+    fn split_on_regex() {
+        assert!(matches!(run(r#"(risp.string/split "a1b22c" #"\d+")"#),
+            Value::Vector(v) if *v == strings(&["a", "b", "c"])));
+        assert!(matches!(run(r#"(risp.string/split "a b  c" #"\s+" 2)"#),
+            Value::Vector(v) if *v == strings(&["a", "b  c"])));
+    }
+
+    #[test]
+    fn split_lines() {
+        assert!(matches!(run("(risp.string/split-lines \"a\\nb\\r\\nc\")"),
+            Value::Vector(v) if *v == strings(&["a", "b", "c"])));
+    }
+
+    #[test]
+    fn join_without_separator() {
+        assert_eq!(run("(risp.string/join [1 \"a\" :k])"), string("1a:k"));
+    }
+
+    #[test]
+    fn join_with_separator() {
+        assert_eq!(run("(risp.string/join \", \" '(1 2 3))"), string("1, 2, 3"));
+    }
+
+    #[test]
+    fn join_type_error_on_non_seq() {
+        assert!(matches!(
+            run_err("(risp.string/join \",\" 1)"),
+            RuntimeError::TypeError {
+                expected: "seq",
+                ..
+            }
+        ));
+    }
+
+    // --- replace / trim / case ---
+
+    #[test]
+    fn replace_all_occurrences() {
+        assert_eq!(
+            run("(risp.string/replace \"a-b-c\" \"-\" \"+\")"),
+            string("a+b+c")
+        );
+    }
+
+    #[test]
+    fn replace_regex_with_group_reference() {
+        assert_eq!(
+            run(r#"(risp.string/replace "a1 b2" #"(\w)(\d)" "$2$1")"#),
+            string("1a 2b")
+        );
+    }
+
+    #[test]
+    fn replace_regex_with_function() {
+        assert_eq!(
+            run(r#"(risp.string/replace "a1 b2" #"\d" (fn [d] (str "<" d ">")))"#),
+            string("a<1> b<2>")
+        );
+        assert_eq!(
+            run(r#"(risp.string/replace "k=v" #"(\w)=(\w)" (fn [m] (nth m 2)))"#),
+            string("v")
+        );
+    }
+
+    #[test]
+    fn trim_variants() {
+        assert_eq!(run("(risp.string/trim \"  a \\n\")"), string("a"));
+        assert_eq!(run("(risp.string/triml \"  a \")"), string("a "));
+        assert_eq!(run("(risp.string/trimr \"  a \")"), string("  a"));
+    }
+
+    #[test]
+    fn case_conversion() {
+        assert_eq!(run("(risp.string/upper-case \"ação\")"), string("AÇÃO"));
+        assert_eq!(run("(risp.string/lower-case \"ÀB\")"), string("àb"));
+    }
+
+    // --- searching ---
+
+    #[test]
+    fn index_of_found() {
+        assert!(matches!(
+            run("(risp.string/index-of \"héllo\" \"l\")"),
+            Value::Long(2)
+        ));
+    }
+
+    #[test]
+    fn index_of_from() {
+        assert!(matches!(
+            run("(risp.string/index-of \"héllo\" \"l\" 3)"),
+            Value::Long(3)
+        ));
+    }
+
+    #[test]
+    fn index_of_missing_is_nil() {
+        assert!(matches!(
+            run("(risp.string/index-of \"abc\" \"z\")"),
+            Value::Nil
+        ));
+        assert!(matches!(
+            run("(risp.string/index-of \"abc\" \"a\" 10)"),
+            Value::Nil
+        ));
+    }
+
+    #[test]
+    fn predicates() {
+        assert_eq!(
+            run("(risp.string/starts-with? \"risp\" \"ri\")"),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            run("(risp.string/ends-with? \"risp\" \"ri\")"),
+            Value::Bool(false)
+        );
+        assert_eq!(
+            run("(risp.string/includes? \"risp\" \"is\")"),
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn blank() {
+        assert_eq!(run("(risp.string/blank? nil)"), Value::Bool(true));
+        assert_eq!(run("(risp.string/blank? \" \\t\")"), Value::Bool(true));
+        assert_eq!(run("(risp.string/blank? \" a \")"), Value::Bool(false));
+    }
+
+    #[test]
+    fn reverse_by_chars() {
+        assert_eq!(run("(risp.string/reverse \"aé日\")"), string("日éa"));
+    }
+}
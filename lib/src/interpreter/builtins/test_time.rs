@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.into())
+    }
+
+    // --- literals ---
+
+    #[test]
+    fn inst_literal() {
+        assert!(matches!(
+            run(r#"#inst "1970-01-01T00:00:01Z""#),
+            Value::Inst(i) if i.millis == 1000
+        ));
+    }
+
+    #[test]
+    fn inst_prints_readably() {
+        assert_eq!(
+            Readable(&run(r#"#inst "2026-01-01T00:00:00Z""#)).to_string(),
+            r#"#inst "2026-01-01T00:00:00.000Z""#
+        );
+        assert_eq!(
+            run(r#"(str #inst "2026-01-01T10:00+02:00")"#),
+            string("2026-01-01T10:00:00.000+02:00")
+        );
+    }
+
+    #[test]
+    fn inst_equality() {
+        assert_eq!(
+            run(r#"(= #inst "2026-01-01T02:00:00+02:00" #inst "2026-01-01")"#),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            run(r#"(= #inst "2026-01-01" (parse-inst "2026-01-01T00:00:00.000Z"))"#),
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn invalid_inst_literal() {
+        assert!(matches!(
+            run_err(r#"#inst "2026-02-30""#),
+            RuntimeError::AnalyzeError(_)
+        ));
+    }
+
+    // --- core functions ---
+
+    #[test]
+    fn now_is_an_inst() {
+        assert_eq!(run("(inst? (now))"), Value::Bool(true));
+        assert_eq!(run("(inst? 1)"), Value::Bool(false));
+        assert_eq!(
+            run(r#"(< (inst-ms #inst "2020-01-01") (inst-ms (now)))"#),
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn inst_ms_and_back() {
+        assert_eq!(
+            run(r#"(inst-ms #inst "1970-01-02")"#),
+            Value::Long(86_400_000)
+        );
+        assert_eq!(
+            run(r#"(= (risp.time/inst 86400000) #inst "1970-01-02")"#),
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn parse_inst_errors() {
+        assert!(matches!(
+            run_err(r#"(parse-inst "yesterday")"#),
+            RuntimeError::ParseError(_)
+        ));
+        assert!(matches!(
+            run_err("(parse-inst 1)"),
+            RuntimeError::TypeError {
+                expected: "string",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn nano_time_is_monotonic() {
+        assert_eq!(
+            run("(let [a (nano-time) b (nano-time)] (<= a b))"),
+            Value::Bool(true)
+        );
+    }
+
+    // --- formatting / offsets ---
+
+    #[test]
+    fn format_with_pattern() {
+        assert_eq!(
+            run(r#"(risp.time/format #inst "2024-02-29T13:05:00Z" "EEE dd MMM yyyy, hh:mm a")"#),
+            string("Thu 29 Feb 2024, 01:05 PM")
+        );
+    }
+
+    #[test]
+    fn format_unknown_letter() {
+        assert!(matches!(
+            run_err(r#"(risp.time/format (now) "QQ")"#),
+            RuntimeError::ParseError(_)
+        ));
+    }
+
+    #[test]
+    fn with_offset_keeps_the_instant() {
+        assert_eq!(
+            run(r#"(str (risp.time/with-offset #inst "2026-01-01" "-08:00"))"#),
+            string("2025-12-31T16:00:00.000-08:00")
+        );
+        assert_eq!(
+            run(r#"(= (risp.time/with-offset #inst "2026-01-01" "+09:00") #inst "2026-01-01")"#),
+            Value::Bool(true)
+        );
+        assert!(matches!(
+            run_err(r#"(risp.time/with-offset (now) "CET")"#),
+            RuntimeError::TypeError { .. }
+        ));
+    }
+
+    // --- durations ---
+
+    #[test]
+    fn duration_units() {
+        assert_eq!(run("(risp.time/duration 3 :seconds)"), Value::Long(3000));
+        assert_eq!(
+            run("(risp.time/duration 2 :weeks)"),
+            Value::Long(1_209_600_000)
+        );
+        assert!(matches!(
+            run_err("(risp.time/duration 1 :fortnights)"),
+            RuntimeError::TypeError { .. }
+        ));
+        assert!(matches!(
+            run_err("(risp.time/duration 9223372036854775807 :days)"),
+            RuntimeError::ArithmeticOverflow { .. }
+        ));
+    }
+
+    #[test]
+    fn plus_and_minus() {
+        assert_eq!(
+            run(
+                r#"(= (risp.time/plus #inst "2026-01-01" (risp.time/duration 36 :hours))
+                      #inst "2026-01-02T12:00Z")"#
+            ),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            run(
+                r#"(= (risp.time/minus #inst "2026-03-01" (risp.time/duration 1 :days))
+                      #inst "2026-02-28")"#
+            ),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            run(r#"(risp.time/minus #inst "2024-03-01" #inst "2024-02-01")"#),
+            Value::Long(29 * 86_400_000)
+        );
+    }
+
+    #[test]
+    fn plus_type_error() {
+        assert!(matches!(
+            run_err("(risp.time/plus 1 2)"),
+            RuntimeError::TypeError {
+                expected: "inst",
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn printed(source: &str) -> String {
+        Readable(&run(source)).to_string()
+    }
+
+    // --- volatiles ---
+
+    #[test]
+    fn volatile_reset_and_swap() {
+        assert_eq!(
+            run("(let [v (volatile! 1)] (do (vreset! v 5) (vswap! v + 2 3) (deref v)))"),
+            Value::Long(10)
+        );
+        assert_eq!(run("(volatile? (volatile! nil))"), Value::Bool(true));
+        assert_eq!(run("(volatile? 1)"), Value::Bool(false));
+    }
+
+    #[test]
+    fn deref_non_volatile_is_type_error() {
+        assert!(matches!(
+            run_err("(deref 1)"),
+            RuntimeError::TypeError {
+                expected: "volatile",
+                ..
+            }
+        ));
+    }
+
+    // --- transduce / into ---
+
+    #[test]
+    fn transduce_without_init_calls_f() {
+        assert_eq!(run("(transduce (map inc) + [1 2 3])"), Value::Long(9));
+    }
+
+    #[test]
+    fn transduce_with_init() {
+        assert_eq!(printed("(transduce (map inc) conj [0] [1 2])"), "[0 2 3]");
+    }
+
+    #[test]
+    fn into_with_composed_xform() {
+        assert_eq!(
+            printed("(into [] (comp (filter odd?) (map inc) (take 2)) [1 2 3 4 5 7])"),
+            "[2 4]"
+        );
+    }
+
+    #[test]
+    fn into_set_and_map_with_xform() {
+        assert_eq!(printed("(into #{} (map inc) [1 1])"), "#{2}");
+        assert_eq!(
+            printed("(into {} (map (fn [x] [x (* x x)])) [2 3])"),
+            "{2 4, 3 9}"
+        );
+    }
+
+    #[test]
+    fn into_small_arities() {
+        assert_eq!(printed("(into)"), "[]");
+        assert_eq!(printed("(into [1])"), "[1]");
+        assert_eq!(printed("(into [1] '(2 3))"), "[1 2 3]");
+    }
+
+    // --- individual transducers ---
+
+    #[test]
+    fn filtering_transducers() {
+        assert_eq!(printed("(into [] (filter odd?) [1 2 3])"), "[1 3]");
+        assert_eq!(printed("(into [] (remove odd?) [1 2 3])"), "[2]");
+        assert_eq!(
+            printed("(into [] (keep (fn [x] (when (odd? x) (* 10 x)))) [1 2 3])"),
+            "[10 30]"
+        );
+    }
+
+    #[test]
+    fn take_and_drop_transducers() {
+        assert_eq!(printed("(into [] (take 2) [1 2 3])"), "[1 2]");
+        assert_eq!(printed("(into [] (take 0) [1 2 3])"), "[]");
+        assert_eq!(printed("(into [] (drop 2) [1 2 3])"), "[3]");
+        assert_eq!(printed("(into [] (take-while odd?) [1 3 4 5])"), "[1 3]");
+        assert_eq!(printed("(into [] (drop-while odd?) [1 3 4 5])"), "[4 5]");
+    }
+
+    #[test]
+    fn partitioning_transducers_flush_on_completion() {
+        assert_eq!(
+            printed("(into [] (partition-all 2) [1 2 3])"),
+            "[(1 2) (3)]"
+        );
+        assert_eq!(
+            printed("(into [] (partition-by odd?) [1 3 2 4 5])"),
+            "[(1 3) (2 4) (5)]"
+        );
+    }
+
+    #[test]
+    fn partition_all_flush_respects_take() {
+        assert_eq!(
+            printed("(into [] (comp (partition-all 2) (take 1)) [1 2 3])"),
+            "[(1 2)]"
+        );
+    }
+
+    #[test]
+    fn partition_by_flush_respects_early_stop() {
+        assert_eq!(
+            printed(
+                "(transduce (partition-by odd?) (fn ([a] a) ([a x] (reduced (conj a x)))) [] [1 2 3])"
+            ),
+            "[(1)]"
+        );
+        assert_eq!(
+            printed("(into [] (comp (partition-by odd?) (take 2)) [1 3 2 5 7])"),
+            "[(1 3) (2)]"
+        );
+    }
+
+    #[test]
+    fn dedupe_and_distinct_transducers() {
+        assert_eq!(printed("(into [] (dedupe) [1 1 2 1 1])"), "[1 2 1]");
+        assert_eq!(printed("(into [] (distinct) [1 2 1 3 2])"), "[1 2 3]");
+        assert_eq!(printed("(into [] (dedupe) [nil nil 1])"), "[nil 1]");
+    }
+
+    #[test]
+    fn cat_and_mapcat_transducers() {
+        assert_eq!(printed("(into [] cat [[1 2] [] [3]])"), "[1 2 3]");
+        assert_eq!(
+            printed("(into [] (mapcat (fn [x] [x x])) [1 2])"),
+            "[1 1 2 2]"
+        );
+    }
+
+    #[test]
+    fn take_stops_across_cat() {
+        // The third vector is never reached because take stops the outer reduce
+        assert_eq!(
+            printed("(into [] (comp cat (take 3)) [[1 2] [3 4] [:x :y]])"),
+            "[1 2 3]"
+        );
+        assert_eq!(
+            run("(let [seen (volatile! 0)] (do (into [] (comp (map (fn [x] (do (vswap! seen inc) x))) cat (take 1)) [[1 2] [3]]) (deref seen)))"),
+            Value::Long(1)
+        );
+    }
+
+    #[test]
+    fn map_indexed_and_interpose_transducers() {
+        assert_eq!(
+            printed("(into [] (map-indexed vector) [:a :b])"),
+            "[[0 :a] [1 :b]]"
+        );
+        assert_eq!(printed("(into [] (interpose :x) [1 2 3])"), "[1 :x 2 :x 3]");
+    }
+
+    #[test]
+    fn transducer_state_is_per_reduction() {
+        assert_eq!(
+            printed("(let [xf (take 1)] [(into [] xf [1 2]) (into [] xf [3 4])])"),
+            "[[1] [3]]"
+        );
+    }
+
+    // --- sequence / eduction ---
+
+    #[test]
+    fn sequence_applies_xform() {
+        assert_eq!(printed("(sequence (map inc) [1 2])"), "(2 3)");
+        assert_eq!(printed("(sequence (map inc) [])"), "()");
+        assert_eq!(printed("(sequence [1 2])"), "(1 2)");
+    }
+
+    #[test]
+    fn eduction_composes_xforms() {
+        assert_eq!(
+            printed("(eduction (filter odd?) (map inc) [1 2 3])"),
+            "(2 4)"
+        );
+    }
+}
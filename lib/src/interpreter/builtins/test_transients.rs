@@ -0,0 +1,231 @@
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::implementation::Interpreter;
+    use crate::interpreter::value::{RuntimeError, Value};
+    use crate::interpreter::Readable;
+
+    fn run(source: &str) -> Value {
+        Interpreter::new().run(source).unwrap()
+    }
+
+    fn run_err(source: &str) -> RuntimeError {
+        Interpreter::new().run(source).unwrap_err()
+    }
+
+    fn printed(source: &str) -> String {
+        Readable(&run(source)).to_string()
+    }
+
+    // --- vectors ---
+
+    #[test]
+    fn conj_and_persistent_vector() {
+        assert_eq!(
+            printed("(persistent! (conj! (conj! (transient [1]) 2) 3))"),
+            "[1 2 3]"
+        );
+    }
+
+    #[test]
+    fn transient_leaves_source_untouched() {
+        assert_eq!(
+            printed("(let [v [1 2]] (do (conj! (transient v) 3) v))"),
+            "[1 2]"
+        );
+    }
+
+    #[test]
+    fn assoc_and_pop_vector() {
+        assert_eq!(
+            printed("(persistent! (pop! (assoc! (transient [1 2 3]) 0 :a 3 :d)))"),
+            "[:a 2 3]"
+        );
+    }
+
+    #[test]
+    fn assoc_vector_out_of_bounds() {
+        assert!(matches!(
+            run_err("(assoc! (transient [1]) 5 :x)"),
+            RuntimeError::IndexOutOfBounds {
+                max_accessible: 1,
+                got: 5,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn pop_empty_vector_is_illegal_state() {
+        assert!(matches!(
+            run_err("(pop! (transient []))"),
+            RuntimeError::IllegalState { .. }
+        ));
+    }
+
+    #[test]
+    fn builds_large_vector_in_loop() {
+        assert_eq!(
+            run("(count (persistent! (loop [i 0 t (transient [])] (if (< i 10000) (recur (inc i) (conj! t i)) t))))"),
+            Value::Long(10000)
+        );
+    }
+
+    // --- maps and sets ---
+
+    #[test]
+    fn assoc_and_dissoc_map() {
+        assert_eq!(
+            printed("(persistent! (dissoc! (assoc! (transient {:a 1}) :a 2 :b 3 :c 4) :c))"),
+            "{:a 2, :b 3}"
+        );
+    }
+
+    #[test]
+    fn conj_map_entries() {
+        assert_eq!(
+            printed("(persistent! (conj! (conj! (transient {}) [:a 1]) {:b 2}))"),
+            "{:a 1, :b 2}"
+        );
+    }
+
+    #[test]
+    fn conj_and_disj_set() {
+        assert_eq!(
+            printed("(persistent! (disj! (conj! (conj! (transient #{1}) 2) 2) 1))"),
+            "#{2}"
+        );
+    }
+
+    #[test]
+    fn builds_large_map_with_assoc() {
+        assert_eq!(
+            printed(
+                "(let [t (transient {})]
+                   (do (dotimes [i 100000] (assoc! t i (* 2 i)))
+                       (dissoc! t 7)
+                       (let [m (persistent! t)] [(count m) (get m 99999) (get m 7)])))"
+            ),
+            "[99999 199998 nil]"
+        );
+    }
+
+    #[test]
+    fn keys_equal_across_kinds_are_one_entry() {
+        assert_eq!(
+            printed("(persistent! (assoc! (transient {1 :a [1 2] :b}) 1N :c '(1 2) :d))"),
+            "{1 :c, [1 2] :d}"
+        );
+        assert_eq!(
+            printed(
+                "(persistent! (conj! (conj! (transient #{0.0 {:a 1}}) -0.0) (sorted-map :a 1)))"
+            ),
+            "#{0.0 {:a 1}}"
+        );
+    }
+
+    #[test]
+    fn removal_keeps_insertion_order() {
+        assert_eq!(
+            printed("(persistent! (assoc! (dissoc! (transient {:a 1 :b 2 :c 3}) :a) :a 4))"),
+            "{:b 2, :c 3, :a 4}"
+        );
+    }
+
+    #[test]
+    fn count_and_get_on_transient() {
+        assert_eq!(run("(count (transient [1 2 3]))"), Value::Long(3));
+        assert_eq!(run("(get (transient {:a 1}) :a)"), Value::Long(1));
+        assert_eq!(run("(get (transient [1 2]) 1)"), Value::Long(2));
+        assert_eq!(run("(contains? (transient #{:x}) :x)"), Value::Bool(true));
+    }
+
+    // --- ownership ---
+
+    #[test]
+    fn use_after_persistent_is_illegal_state() {
+        let err = run_err("(let [t (transient [])] (do (persistent! t) (conj! t 1)))");
+        assert!(matches!(err, RuntimeError::IllegalState { .. }));
+        assert_eq!(
+            err.to_string(),
+            "(illegal-state \"transient used after persistent!\")"
+        );
+    }
+
+    #[test]
+    fn persistent_twice_is_illegal_state() {
+        assert!(matches!(
+            run_err("(let [t (transient {})] (do (persistent! t) (persistent! t)))"),
+            RuntimeError::IllegalState { .. }
+        ));
+        assert!(matches!(
+            run_err("(let [t (transient [1])] (do (persistent! t) (count t)))"),
+            RuntimeError::IllegalState { .. }
+        ));
+    }
+
+    // --- wrong kinds ---
+
+    #[test]
+    fn transient_of_list_is_type_error() {
+        assert!(matches!(
+            run_err("(transient '(1 2))"),
+            RuntimeError::TypeError {
+                expected: "vector, map or set",
+                got: "list",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn ops_check_collection_kind() {
+        assert!(matches!(
+            run_err("(disj! (transient [1]) 1)"),
+            RuntimeError::TypeError {
+                expected: "transient set",
+                got: "transient vector",
+                ..
+            }
+        ));
+        assert!(matches!(
+            run_err("(conj! [1] 2)"),
+            RuntimeError::TypeError {
+                expected: "transient",
+                got: "vector",
+                ..
+            }
+        ));
+        assert!(matches!(
+            run_err("(assoc! (transient {}) :a)"),
+            RuntimeError::WrongArity { expected: 3, .. }
+        ));
+    }
+
+    // --- library functions built on transients ---
+
+    #[test]
+    fn into_keeps_target_kind() {
+        assert_eq!(printed("(into [0] '(1 2))"), "[0 1 2]");
+        assert_eq!(printed("(into #{1} [1 2])"), "#{1 2}");
+        assert_eq!(printed("(into '(0) [1 2])"), "(2 1 0)");
+        assert_eq!(printed("(into [] (map inc) [1 2])"), "[2 3]");
+        assert_eq!(printed("(into '() (map inc) [1 2])"), "(3 2)");
+    }
+
+    #[test]
+    fn frequencies_and_group_by() {
+        assert_eq!(printed("(frequencies [:a :b :a])"), "{:a 2, :b 1}");
+        assert_eq!(
+            printed("(group-by odd? [1 2 3])"),
+            "{true [1 3], false [2]}"
+        );
+    }
+
+    #[test]
+    fn conj_bang_as_reducing_function() {
+        assert_eq!(
+            printed("(persistent! (transduce (map inc) conj! [1 2]))"),
+            "[2 3]"
+        );
+    }
+}
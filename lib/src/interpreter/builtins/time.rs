@@ -0,0 +1,176 @@
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use super::expect_arity;
+use crate::inst::{parse_offset, Inst};
+use crate::interpreter::{RuntimeError, Value};
+use crate::lexer::Span;
+
+fn inst_arg(arg: &(Value, Span)) -> Result<Inst, RuntimeError> {
+    match arg {
+        (Value::Inst(i), _) => Ok(*i),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "inst",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn long_arg(arg: &(Value, Span)) -> Result<i64, RuntimeError> {
+    match arg {
+        (Value::Long(n), _) => Ok(*n),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "long",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn str_arg(arg: &(Value, Span)) -> Result<&str, RuntimeError> {
+    match arg {
+        (Value::String(s), _) => Ok(s),
+        (v, span) => Err(RuntimeError::TypeError {
+            expected: "string",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn shifted(inst: Inst, millis: i64, span: Span) -> Result<Value, RuntimeError> {
+    inst.checked_add(millis)
+        .map(Value::Inst)
+        .ok_or(RuntimeError::ArithmeticOverflow { span })
+}
+
+fn now(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 0, 0, span)?;
+    Ok(Value::Inst(Inst::now()))
+}
+
+// Monotonic nanoseconds, only meaningful as a difference between two calls
+fn nano_time(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 0, 0, span)?;
+    static START: OnceLock<Instant> = OnceLock::new();
+    let start = START.get_or_init(Instant::now);
+    Ok(Value::Long(start.elapsed().as_nanos() as i64))
+}
+
+// (inst ms) builds an inst from milliseconds since the epoch
+fn inst(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(Value::Inst(Inst::from_millis(long_arg(&args[0])?)))
+}
+
+fn is_inst(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(Value::Bool(matches!(args[0].0, Value::Inst(_))))
+}
+
+fn inst_ms(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    Ok(Value::Long(inst_arg(&args[0])?.millis))
+}
+
+fn parse_inst(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 1, 1, span)?;
+    let s = str_arg(&args[0])?;
+    Inst::parse(s)
+        .map(Value::Inst)
+        .ok_or_else(|| RuntimeError::ParseError(format!("(invalid-inst :literal {s:?})")))
+}
+
+// (format inst pattern)
+fn format(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let inst = inst_arg(&args[0])?;
+    inst.format(str_arg(&args[1])?)
+        .map(|s| Value::String(s.into()))
+        .map_err(|message| RuntimeError::ParseError(format!("(invalid-pattern {message:?})")))
+}
+
+// (with-offset inst "+02:00") shows the same instant at another offset
+fn with_offset(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let inst = inst_arg(&args[0])?;
+    let offset = parse_offset(str_arg(&args[1])?).ok_or(RuntimeError::TypeError {
+        expected: "offset like \"+02:00\" or \"Z\"",
+        got: "string",
+        span: args[1].1,
+    })?;
+    Ok(Value::Inst(inst.with_offset(offset)))
+}
+
+// (duration amount unit) => milliseconds
+fn duration(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let amount = long_arg(&args[0])?;
+    let scale = match &args[1].0 {
+        Value::Keyword(k) => match k.as_ref() {
+            "millis" => Some(1),
+            "seconds" => Some(1000),
+            "minutes" => Some(60_000),
+            "hours" => Some(3_600_000),
+            "days" => Some(86_400_000),
+            "weeks" => Some(604_800_000),
+            _ => None,
+        },
+        _ => None,
+    }
+    .ok_or(RuntimeError::TypeError {
+        expected: ":millis, :seconds, :minutes, :hours, :days or :weeks",
+        got: args[1].0.type_name(),
+        span: args[1].1,
+    })?;
+    amount
+        .checked_mul(scale)
+        .map(Value::Long)
+        .ok_or(RuntimeError::ArithmeticOverflow { span })
+}
+
+// (plus inst ms)
+fn plus(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    shifted(inst_arg(&args[0])?, long_arg(&args[1])?, span)
+}
+
+// (minus inst ms) => inst
+// (minus inst inst) => milliseconds between them
+fn minus(args: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(args, 2, 2, span)?;
+    let inst = inst_arg(&args[0])?;
+    match &args[1] {
+        (Value::Inst(other), _) => inst
+            .millis
+            .checked_sub(other.millis)
+            .map(Value::Long)
+            .ok_or(RuntimeError::ArithmeticOverflow { span }),
+        arg => {
+            let millis = long_arg(arg)?
+                .checked_neg()
+                .ok_or(RuntimeError::ArithmeticOverflow { span })?;
+            shifted(inst, millis, span)
+        }
+    }
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("now", Value::new_builtin("now", now)),
+        ("nano-time", Value::new_builtin("nano-time", nano_time)),
+        ("inst", Value::new_builtin("inst", inst)),
+        ("inst?", Value::new_builtin("inst?", is_inst)),
+        ("inst-ms", Value::new_builtin("inst-ms", inst_ms)),
+        ("parse-inst", Value::new_builtin("parse-inst", parse_inst)),
+        ("format", Value::new_builtin("format", format)),
+        (
+            "with-offset",
+            Value::new_builtin("with-offset", with_offset),
+        ),
+        ("duration", Value::new_builtin("duration", duration)),
+        ("plus", Value::new_builtin("plus", plus)),
+        ("minus", Value::new_builtin("minus", minus)),
+    ]
+}
@@ -0,0 +1,234 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::expect_arity;
+use super::sequences::seq_to_list;
+use crate::interpreter::transient::{Indexed, Transient};
+use crate::interpreter::{RuntimeError, Value};
+use crate::lexer::Span;
+
+fn used_after_persistent(span: Span) -> RuntimeError {
+    RuntimeError::IllegalState {
+        message: "transient used after persistent!",
+        span,
+    }
+}
+
+fn wrong_kind(transient: &Transient, expected: &'static str, span: Span) -> RuntimeError {
+    RuntimeError::TypeError {
+        expected,
+        got: transient.type_name(),
+        span,
+    }
+}
+
+/// Runs `f` on the live contents of a transient and returns the transient.
+fn edit<F>(target: &(Value, Span), span: Span, f: F) -> Result<Value, RuntimeError>
+where
+    F: FnOnce(&mut Transient) -> Result<(), RuntimeError>,
+{
+    let Value::Transient(cell) = &target.0 else {
+        return Err(RuntimeError::TypeError {
+            expected: "transient",
+            got: target.0.type_name(),
+            span: target.1,
+        });
+    };
+    match cell.borrow_mut().as_mut() {
+        Some(transient) => f(transient)?,
+        None => return Err(used_after_persistent(span)),
+    }
+    Ok(target.0.clone())
+}
+
+/// Runs `f` on the live contents of a transient without changing them.
+pub(crate) fn read<T>(
+    cell: &RefCell<Option<Transient>>,
+    span: Span,
+    f: impl FnOnce(&Transient) -> T,
+) -> Result<T, RuntimeError> {
+    match &*cell.borrow() {
+        Some(transient) => Ok(f(transient)),
+        None => Err(used_after_persistent(span)),
+    }
+}
+
+pub(super) fn conj_into(
+    transient: &mut Transient,
+    (val, val_span): &(Value, Span),
+) -> Result<(), RuntimeError> {
+    match transient {
+        Transient::Vector(v) => v.push(val.clone()),
+        Transient::Set(s) => s.insert(seq_to_list(val.clone())),
+        Transient::Map(m) => match val {
+            Value::Vector(pair) if pair.len() == 2 => {
+                assoc_into(m, pair[0].clone(), pair[1].clone())
+            }
+            Value::Map(other) => {
+                for (k, v) in other.iter() {
+                    assoc_into(m, k.clone(), v.clone());
+                }
+            }
+            v => {
+                return Err(RuntimeError::TypeError {
+                    expected: "vector pair or map",
+                    got: v.type_name(),
+                    span: *val_span,
+                })
+            }
+        },
+    }
+    Ok(())
+}
+
+fn assoc_into(map: &mut Indexed<(Value, Value)>, key: Value, value: Value) {
+    match map.get_mut(&key) {
+        Some(existing) => existing.1 = value,
+        None => map.insert((key, value)),
+    }
+}
+
+fn transient(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    match Transient::of(&elems[0].0) {
+        Some(transient) => Ok(Value::Transient(Rc::new(RefCell::new(Some(transient))))),
+        None => Err(RuntimeError::TypeError {
+            expected: "vector, map or set",
+            got: elems[0].0.type_name(),
+            span: elems[0].1,
+        }),
+    }
+}
+
+// Whether `transient` accepts the collection, used by `into`
+fn is_editable(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(Value::Bool(matches!(
+        elems[0].0,
+        Value::Vector(_) | Value::Map(_) | Value::Set(_)
+    )))
+}
+
+fn persistent(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    match &elems[0].0 {
+        Value::Transient(cell) => match cell.borrow_mut().take() {
+            Some(transient) => Ok(transient.persistent()),
+            None => Err(used_after_persistent(span)),
+        },
+        v => Err(RuntimeError::TypeError {
+            expected: "transient",
+            got: v.type_name(),
+            span: elems[0].1,
+        }),
+    }
+}
+
+// (conj!) starts an empty transient vector and (conj! t) completes, so
+// conj! can be the reducing function of `transduce`
+fn conj(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 0, 2, span)?;
+    match elems {
+        [] => transient(&[(Value::Vector(Rc::new(vec![])), span)], span),
+        [target] => edit(target, span, |_| Ok(())),
+        [target, val] => edit(target, span, |t| conj_into(t, val)),
+        _ => unreachable!(),
+    }
+}
+
+// (assoc! t k v & kvs)
+fn assoc(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    if elems.len() < 3 || elems.len().is_multiple_of(2) {
+        return Err(RuntimeError::WrongArity {
+            expected: 3,
+            got: elems.len(),
+            span,
+        });
+    }
+    edit(&elems[0], span, |t| {
+        for pair in elems[1..].chunks(2) {
+            let (key, key_span) = &pair[0];
+            let value = pair[1].0.clone();
+            match t {
+                Transient::Map(m) => assoc_into(m, key.clone(), value),
+                Transient::Vector(v) => match key {
+                    Value::Long(n) if *n >= 0 && *n as usize <= v.len() => {
+                        if *n as usize == v.len() {
+                            v.push(value);
+                        } else {
+                            v[*n as usize] = value;
+                        }
+                    }
+                    Value::Long(n) if *n >= 0 => {
+                        return Err(RuntimeError::IndexOutOfBounds {
+                            max_accessible: v.len(),
+                            got: *n,
+                            span: *key_span,
+                        })
+                    }
+                    k => {
+                        return Err(RuntimeError::TypeError {
+                            expected: "non-negative index",
+                            got: k.type_name(),
+                            span: *key_span,
+                        })
+                    }
+                },
+                t => return Err(wrong_kind(t, "transient vector or map", elems[0].1)),
+            }
+        }
+        Ok(())
+    })
+}
+
+// (dissoc! t & ks)
+fn dissoc(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, usize::MAX, span)?;
+    edit(&elems[0], span, |t| match t {
+        Transient::Map(m) => {
+            elems[1..].iter().for_each(|(key, _)| m.remove(key));
+            Ok(())
+        }
+        t => Err(wrong_kind(t, "transient map", elems[0].1)),
+    })
+}
+
+// (disj! t & items)
+fn disj(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, usize::MAX, span)?;
+    edit(&elems[0], span, |t| match t {
+        Transient::Set(s) => {
+            elems[1..].iter().for_each(|(item, _)| s.remove(item));
+            Ok(())
+        }
+        t => Err(wrong_kind(t, "transient set", elems[0].1)),
+    })
+}
+
+// Removes the last element of a transient vector
+fn pop(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    edit(&elems[0], span, |t| match t {
+        Transient::Vector(v) => match v.pop() {
+            Some(_) => Ok(()),
+            None => Err(RuntimeError::IllegalState {
+                message: "can't pop empty vector",
+                span,
+            }),
+        },
+        t => Err(wrong_kind(t, "transient vector", elems[0].1)),
+    })
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("transient", Value::new_builtin("transient", transient)),
+        ("editable?", Value::new_builtin("editable?", is_editable)),
+        ("persistent!", Value::new_builtin("persistent!", persistent)),
+        ("conj!", Value::new_builtin("conj!", conj)),
+        ("assoc!", Value::new_builtin("assoc!", assoc)),
+        ("dissoc!", Value::new_builtin("dissoc!", dissoc)),
+        ("disj!", Value::new_builtin("disj!", disj)),
+        ("pop!", Value::new_builtin("pop!", pop)),
+    ]
+}
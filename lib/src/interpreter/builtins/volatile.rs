@@ -0,0 +1,67 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::expect_arity;
+use crate::interpreter::{Interpreter, RuntimeError, Value};
+use crate::lexer::Span;
+
+// A volatile is a mutable box for local state, such as the counter of a
+// `take` transducer
+fn volatile(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(Value::Volatile(Rc::new(RefCell::new(elems[0].0.clone()))))
+}
+
+fn is_volatile(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(Value::Bool(matches!(elems[0].0, Value::Volatile(_))))
+}
+
+fn expect_volatile((value, span): &(Value, Span)) -> Result<&Rc<RefCell<Value>>, RuntimeError> {
+    match value {
+        Value::Volatile(cell) => Ok(cell),
+        v => Err(RuntimeError::TypeError {
+            expected: "volatile",
+            got: v.type_name(),
+            span: *span,
+        }),
+    }
+}
+
+fn deref(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 1, 1, span)?;
+    Ok(expect_volatile(&elems[0])?.borrow().clone())
+}
+
+fn vreset(elems: &[(Value, Span)], span: Span) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, 2, span)?;
+    let cell = expect_volatile(&elems[0])?;
+    cell.replace(elems[1].0.clone());
+    Ok(elems[1].0.clone())
+}
+
+// (vswap! v f args...) sets v to (f @v args...) and returns the new value
+fn vswap(
+    interpreter: &mut Interpreter,
+    elems: &[(Value, Span)],
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    expect_arity(elems, 2, usize::MAX, span)?;
+    let cell = expect_volatile(&elems[0])?.clone();
+    let current = cell.borrow().clone();
+    let mut args = vec![(current, elems[0].1)];
+    args.extend(elems[2..].iter().cloned());
+    let updated = interpreter.call_value(&elems[1].0, args, span)?;
+    cell.replace(updated.clone());
+    Ok(updated)
+}
+
+pub fn builtins() -> Vec<(&'static str, Value)> {
+    vec![
+        ("volatile!", Value::new_builtin("volatile!", volatile)),
+        ("volatile?", Value::new_builtin("volatile?", is_volatile)),
+        ("deref", Value::new_builtin("deref", deref)),
+        ("vreset!", Value::new_builtin("vreset!", vreset)),
+        ("vswap!", Value::new_native("vswap!", vswap)),
+    ]
+}
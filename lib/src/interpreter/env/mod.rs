@@ -76,6 +76,14 @@ impl Env {
         }
     }
 
+    pub fn rebind_global(&self, name: &str, value: Value) -> Option<(String, Value)> {
+        self.registry.borrow_mut().rebind(name, value)
+    }
+
+    pub fn set_in_ns(&self, ns: &str, name: &str, value: Value) -> Option<Value> {
+        self.registry.borrow_mut().set_in_ns(ns, name, value)
+    }
+
     pub fn load_builtins(&self, ns_name: &str, values: Vec<(&'static str, Value)>) {
         self.registry.borrow_mut().load(ns_name, values);
     }
@@ -77,6 +77,36 @@ impl NamespaceRegistry {
             .insert(name.to_string(), value);
     }
 
+    // Namespace owning `name` as resolved from the current namespace
+    fn owner_of(&self, name: &str) -> Option<String> {
+        let current = self.namespaces.get(self.current.as_ref())?;
+        if current.defs.contains_key(name) {
+            return Some(self.current.to_string());
+        }
+        current
+            .referred
+            .iter()
+            .find(|r| {
+                self.namespaces
+                    .get(r.as_str())
+                    .is_some_and(|ns| ns.defs.contains_key(name))
+            })
+            .cloned()
+    }
+
+    pub fn rebind(&mut self, name: &str, value: Value) -> Option<(String, Value)> {
+        let owner = self.owner_of(name)?;
+        let previous = self.set_in_ns(&owner, name, value)?;
+        Some((owner, previous))
+    }
+
+    pub fn set_in_ns(&mut self, ns_name: &str, name: &str, value: Value) -> Option<Value> {
+        self.namespaces
+            .get_mut(ns_name)?
+            .defs
+            .insert(name.to_string(), value)
+    }
+
     pub fn load(&mut self, ns_name: &str, values: Vec<(&'static str, Value)>) {
         let ns = self
             .namespaces
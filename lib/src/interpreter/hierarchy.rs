@@ -0,0 +1,83 @@
+use super::Value;
+
+/// The global tag hierarchy behind `derive` and `isa?`, kept as each tag's
+/// direct parents. `version` changes with every edit so multimethods know
+/// when their dispatch cache is stale.
+#[derive(Default)]
+pub struct Hierarchy {
+    parents: Vec<(Value, Vec<Value>)>,
+    version: u64,
+}
+
+impl Hierarchy {
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn parents(&self, tag: &Value) -> &[Value] {
+        self.parents
+            .iter()
+            .find(|(t, _)| t == tag)
+            .map_or(&[], |(_, ps)| ps.as_slice())
+    }
+
+    /// Parents, their parents and so on, nearest first
+    pub fn ancestors(&self, tag: &Value) -> Vec<Value> {
+        let mut found: Vec<Value> = vec![];
+        let mut pending = vec![tag.clone()];
+        while let Some(next) = pending.pop() {
+            for parent in self.parents(&next) {
+                if !found.contains(parent) {
+                    found.push(parent.clone());
+                    pending.push(parent.clone());
+                }
+            }
+        }
+        found
+    }
+
+    pub fn descendants(&self, tag: &Value) -> Vec<Value> {
+        self.parents
+            .iter()
+            .map(|(t, _)| t)
+            .filter(|t| self.ancestors(t).contains(tag))
+            .cloned()
+            .collect()
+    }
+
+    /// Equal values, a tag and one of its ancestors, or vectors whose items
+    /// are pairwise `isa?`
+    pub fn isa(&self, child: &Value, parent: &Value) -> bool {
+        if child == parent {
+            return true;
+        }
+        match (child, parent) {
+            (Value::Vector(cs), Value::Vector(ps)) => {
+                cs.len() == ps.len() && cs.iter().zip(ps.iter()).all(|(c, p)| self.isa(c, p))
+            }
+            _ => self.ancestors(child).contains(parent),
+        }
+    }
+
+    /// Adds `parent` above `tag`, `false` when that would make a cycle
+    pub fn derive(&mut self, tag: Value, parent: Value) -> bool {
+        if self.isa(&parent, &tag) {
+            return false;
+        }
+        match self.parents.iter_mut().find(|(t, _)| *t == tag) {
+            Some((_, ps)) if ps.contains(&parent) => {}
+            Some((_, ps)) => ps.push(parent),
+            None => self.parents.push((tag, vec![parent])),
+        }
+        self.version += 1;
+        true
+    }
+
+    pub fn underive(&mut self, tag: &Value, parent: &Value) {
+        if let Some((_, ps)) = self.parents.iter_mut().find(|(t, _)| t == tag) {
+            ps.retain(|p| p != parent);
+        }
+        self.parents.retain(|(_, ps)| !ps.is_empty());
+        self.version += 1;
+    }
+}
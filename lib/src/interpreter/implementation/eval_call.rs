@@ -1,12 +1,15 @@
 use super::{Callable, Env, Interpreter, RuntimeError, Value};
-use crate::interpreter::value::ClosureArity;
+use crate::interpreter::builtins::{expect_arity, lookup};
+use crate::interpreter::multimethod::DispatchError;
+use crate::interpreter::value::{method_name, ClosureArity};
+use crate::interpreter::Readable;
 use crate::lexer::Span;
 use crate::sema::{AstNode, Node};
 use std::cell::RefCell;
 use std::rc::Rc;
 
 impl Interpreter {
-    pub(super) fn call_value(
+    pub(crate) fn call_value(
         &mut self,
         func: &Value,
         args: Vec<(Value, Span)>,
@@ -15,6 +18,7 @@ impl Interpreter {
         match func {
             Value::Callable(callable) => match callable.as_ref() {
                 Callable::Builtin { func, .. } => func(&args, span),
+                Callable::Native { func, .. } => func(self, &args, span),
                 Callable::Closure {
                     arities,
                     env,
@@ -42,7 +46,74 @@ impl Interpreter {
                     self.env = saved;
                     result
                 }
+                Callable::Multi(multi) => {
+                    let value = self.call_value(&multi.dispatch, args.clone(), span)?;
+                    let method = multi.find_method(&self.hierarchy, &value).map_err(|e| {
+                        let (name, value) = (multi.name.clone(), Readable(&value).to_string());
+                        match e {
+                            DispatchError::NoMethod => RuntimeError::NoMethod { name, value, span },
+                            DispatchError::Ambiguous => {
+                                RuntimeError::AmbiguousMethod { name, value, span }
+                            }
+                        }
+                    })?;
+                    self.call_value(&method, args, span)
+                }
+                Callable::ProtocolMethod { protocol, method } => {
+                    let Some((target, _)) = args.first() else {
+                        return Err(RuntimeError::WrongArity {
+                            expected: 1,
+                            got: 0,
+                            span,
+                        });
+                    };
+                    let tag = target.type_tag();
+                    match protocol.method(&tag, method) {
+                        Some(f) => self.call_value(&f, args, span),
+                        None => Err(RuntimeError::NoMethod {
+                            name: format!("{}/{}", protocol.name, method_name(method)),
+                            value: tag.name().to_string(),
+                            span,
+                        }),
+                    }
+                }
             },
+            // Keywords look themselves up, collections look up their argument
+            Value::Keyword(_) => {
+                expect_arity(&args, 1, 2, span)?;
+                let default = args.get(1).map_or(Value::Nil, |a| a.0.clone());
+                let found = lookup(self, &args[0].0, func, span)?;
+                Ok(found.map_or(default, |(_, v)| v))
+            }
+            Value::Map(_) | Value::SortedMap(_) => {
+                expect_arity(&args, 1, 2, span)?;
+                let default = args.get(1).map_or(Value::Nil, |a| a.0.clone());
+                let found = lookup(self, func, &args[0].0, args[0].1)?;
+                Ok(found.map_or(default, |(_, v)| v))
+            }
+            Value::Set(_) | Value::SortedSet(_) => {
+                expect_arity(&args, 1, 1, span)?;
+                let found = lookup(self, func, &args[0].0, args[0].1)?;
+                Ok(found.map_or(Value::Nil, |(_, v)| v))
+            }
+            Value::Vector(v) => {
+                expect_arity(&args, 1, 1, span)?;
+                match &args[0] {
+                    (Value::Long(n), n_span) if *n < 0 || *n as usize >= v.len() => {
+                        Err(RuntimeError::IndexOutOfBounds {
+                            max_accessible: v.len().saturating_sub(1),
+                            got: *n,
+                            span: *n_span,
+                        })
+                    }
+                    (Value::Long(n), _) => Ok(v[*n as usize].clone()),
+                    (other, other_span) => Err(RuntimeError::TypeError {
+                        expected: "long",
+                        got: other.type_name(),
+                        span: *other_span,
+                    }),
+                }
+            }
             _ => Err(RuntimeError::NotCallable { span }),
         }
     }
@@ -78,36 +149,9 @@ impl Interpreter {
                 }
 
                 let callee_value = self.eval(callee)?;
-                match callee_value {
-                    Value::Callable(callable) => {
-                        let evaluated_args: Result<Vec<(Value, Span)>, _> =
-                            args.iter().map(|a| Ok((self.eval(a)?, a.span))).collect();
-                        self.call_value(&Value::Callable(callable), evaluated_args?, node.span)
-                    }
-                    Value::Keyword(v) => {
-                        if args.len() != 1 {
-                            return Err(RuntimeError::WrongArity {
-                                expected: 1,
-                                got: args.len(),
-                                span: node.span,
-                            });
-                        }
-                        let arg = self.eval(&args[0])?;
-                        match arg {
-                            Value::Map(pairs) => Ok(pairs
-                                .iter()
-                                .find(|(key, _)| matches!(key, Value::Keyword(s) if *s == v))
-                                .map(|(_, v)| v.clone())
-                                .unwrap_or(Value::Nil)),
-                            _ => Err(RuntimeError::TypeError {
-                                expected: "map",
-                                got: arg.type_name(),
-                                span: args[0].span,
-                            }),
-                        }
-                    }
-                    _ => Err(RuntimeError::NotCallable { span: node.span }),
-                }
+                let evaluated_args: Result<Vec<(Value, Span)>, _> =
+                    args.iter().map(|a| Ok((self.eval(a)?, a.span))).collect();
+                self.call_value(&callee_value, evaluated_args?, node.span)
             }
             _ => unreachable!(),
         }
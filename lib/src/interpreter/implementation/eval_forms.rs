@@ -1,36 +1,132 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use num_traits::ToPrimitive;
+
 use super::{Callable, Env, Interpreter, RuntimeError, Value};
-use crate::sema::{AstNode, LocalId, Node};
+use crate::interpreter::stream::Stream;
+use crate::interpreter::Readable;
+use crate::lexer::Span;
+use crate::sema::{AstNode, CaseKey, LocalId, Node};
 
 impl Interpreter {
-    pub(super) fn eval_bindings_with_toplevel_frame(
+    // Inside a function frame the binding slots are already allocated; at
+    // top-level no function frame exists, so a temporary one is pushed and
+    // the previous env is returned for the caller to restore.
+    fn enter_bindings_frame(
         &mut self,
         bindings: &[(LocalId, AstNode)],
-    ) -> Result<Option<Rc<RefCell<Env>>>, RuntimeError> {
+    ) -> Option<Rc<RefCell<Env>>> {
         let max_id = bindings
             .iter()
             .map(|(id, _)| *id as usize)
             .max()
             .unwrap_or(0);
         if self.env.borrow().frame_len() > max_id {
-            // Inside a function frame: slots already allocated, write directly
-            for (id, val_node) in bindings.iter() {
-                let val = self.eval(val_node)?;
-                self.env.borrow_mut().set_local(*id, val);
-            }
-            Ok(None)
+            None
         } else {
-            // Top-level: no function frame exists, allocate a temporary one
             let child_env = Rc::new(RefCell::new(Env::with_frame(self.env.clone(), max_id + 1)));
-            let saved = std::mem::replace(&mut self.env, child_env);
-            for (id, val_node) in bindings.iter() {
-                let val = self.eval(val_node)?;
-                self.env.borrow_mut().set_local(*id, val);
+            Some(std::mem::replace(&mut self.env, child_env))
+        }
+    }
+
+    pub(super) fn eval_bindings_with_toplevel_frame(
+        &mut self,
+        bindings: &[(LocalId, AstNode)],
+    ) -> Result<Option<Rc<RefCell<Env>>>, RuntimeError> {
+        let saved = self.enter_bindings_frame(bindings);
+        for (id, val_node) in bindings.iter() {
+            let val = self.eval(val_node)?;
+            self.env.borrow_mut().set_local(*id, val);
+        }
+        Ok(saved)
+    }
+
+    pub(super) fn eval_with_open(
+        &mut self,
+        bindings: &[(LocalId, AstNode)],
+        body: &AstNode,
+    ) -> Result<Value, RuntimeError> {
+        let saved = self.enter_bindings_frame(bindings);
+        let mut opened: Vec<(Rc<Stream>, Span)> = vec![];
+
+        let bound = bindings
+            .iter()
+            .try_for_each(|(id, val_node)| match self.eval(val_node)? {
+                Value::Stream(stream) => {
+                    opened.push((stream.clone(), val_node.span));
+                    self.env.borrow_mut().set_local(*id, Value::Stream(stream));
+                    Ok(())
+                }
+                v => Err(RuntimeError::TypeError {
+                    expected: "stream",
+                    got: v.type_name(),
+                    span: val_node.span,
+                }),
+            });
+        let mut result = bound.and_then(|_| self.eval(body));
+
+        // Close in reverse order even when the body failed; the first error wins
+        for (stream, span) in opened.iter().rev() {
+            if let Err(e) = stream.close() {
+                if result.is_ok() {
+                    result = Err(RuntimeError::from_io(e, *span));
+                }
+            }
+        }
+
+        if let Some(env) = saved {
+            self.env = env;
+        }
+        result
+    }
+
+    pub(super) fn eval_binding(
+        &mut self,
+        bindings: &[(String, Span, AstNode)],
+        body: &AstNode,
+    ) -> Result<Value, RuntimeError> {
+        let values = bindings
+            .iter()
+            .map(|(_, _, val_node)| self.eval(val_node))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut restore: Vec<(String, &str, Value)> = vec![];
+        let mut result = Ok(Value::Nil);
+        for ((name, name_span, _), value) in bindings.iter().zip(values) {
+            let rebound = self.env.borrow().rebind_global(name, value);
+            match rebound {
+                Some((ns, previous)) => restore.push((ns, name, previous)),
+                None => {
+                    result = Err(RuntimeError::UndefinedVariable {
+                        name: name.clone(),
+                        span: *name_span,
+                    });
+                    break;
+                }
             }
-            Ok(Some(saved))
         }
+        if result.is_ok() {
+            result = self.eval(body);
+        }
+
+        for (ns, name, previous) in restore.into_iter().rev() {
+            self.env.borrow().set_in_ns(&ns, name, previous);
+        }
+        result
+    }
+
+    pub(super) fn eval_with_out_str(&mut self, body: &AstNode) -> Result<Value, RuntimeError> {
+        let buffer = Rc::new(Stream::buffer("*out*"));
+        let previous =
+            self.env
+                .borrow()
+                .set_in_ns("risp.core", "*out*", Value::Stream(buffer.clone()));
+        let result = self.eval(body);
+        if let Some(previous) = previous {
+            self.env.borrow().set_in_ns("risp.core", "*out*", previous);
+        }
+        result.map(|_| Value::String(buffer.buffered().into()))
     }
 
     pub(super) fn eval_if(&mut self, node: &AstNode) -> Result<Value, RuntimeError> {
@@ -111,6 +207,32 @@ impl Interpreter {
         }
     }
 
+    pub(super) fn select_case_branch<'a>(
+        &mut self,
+        node: &'a AstNode,
+    ) -> Result<&'a AstNode, RuntimeError> {
+        match &node.node {
+            Node::Case {
+                expr,
+                table,
+                branches,
+                default,
+            } => {
+                let value = self.eval(expr)?;
+                let index = case_key(&value).and_then(|key| table.get(&key));
+                match (index, default) {
+                    (Some(i), _) => Ok(&branches[*i]),
+                    (None, Some(default)) => Ok(default),
+                    (None, None) => Err(RuntimeError::NoMatchingClause {
+                        value: Readable(&value).to_string(),
+                        span: expr.span,
+                    }),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
     pub(super) fn eval_do(&mut self, elems: &[AstNode]) -> Result<Value, RuntimeError> {
         elems
             .iter()
@@ -119,3 +241,18 @@ impl Interpreter {
             .unwrap_or(Ok(Value::Nil))
     }
 }
+
+fn case_key(value: &Value) -> Option<CaseKey> {
+    match value {
+        Value::Nil => Some(CaseKey::Nil),
+        Value::Bool(b) => Some(CaseKey::Bool(*b)),
+        Value::Long(n) => Some(CaseKey::Long(*n)),
+        // Equal to the long it holds, so it takes the same branch
+        Value::BigInt(n) => n.to_i64().map(CaseKey::Long),
+        Value::Char(c) => Some(CaseKey::Char(*c)),
+        Value::String(s) => Some(CaseKey::String(s.to_string())),
+        Value::Keyword(s) => Some(CaseKey::Keyword(s.to_string())),
+        Value::Symbol(s) => Some(CaseKey::Symbol(s.to_string())),
+        _ => None,
+    }
+}
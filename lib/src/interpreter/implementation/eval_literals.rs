@@ -1,7 +1,7 @@
 use std::rc::Rc;
 
 use super::{Interpreter, RuntimeError, Value};
-use crate::collections::RispList;
+use crate::collections::{RispList, RispQueue};
 use crate::lexer::Span;
 use crate::sema::{AstNode, LocalId};
 
@@ -91,4 +91,12 @@ impl Interpreter {
         }
         Ok(Value::Set(Rc::new(values)))
     }
+
+    pub(super) fn eval_queue_literal(&mut self, elems: &[AstNode]) -> Result<Value, RuntimeError> {
+        elems
+            .iter()
+            .map(|e| self.eval(e))
+            .collect::<Result<RispQueue<_>, _>>()
+            .map(Value::Queue)
+    }
 }
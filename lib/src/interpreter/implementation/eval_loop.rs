@@ -1,6 +1,10 @@
-use super::{Interpreter, RuntimeError, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::{Env, Interpreter, RuntimeError, Value};
+use crate::interpreter::builtins::seq_items;
 use crate::interpreter::value::EvalFlow;
-use crate::sema::{AstNode, LocalId, Node};
+use crate::sema::{AstNode, ForClause, LocalId, Node};
 
 impl Interpreter {
     pub fn eval_flow(&mut self, node: &AstNode) -> Result<EvalFlow, RuntimeError> {
@@ -33,6 +37,10 @@ impl Interpreter {
                 }
                 self.eval_flow(last)
             }
+            Node::Case { .. } => {
+                let branch = self.select_case_branch(node)?;
+                self.eval_flow(branch)
+            }
             Node::Let { bindings, body } => {
                 let saved = self.eval_bindings_with_toplevel_frame(bindings)?;
                 let result = self.eval_flow(body);
@@ -66,4 +74,73 @@ impl Interpreter {
         }
         Ok(result)
     }
+
+    pub(super) fn eval_for(&mut self, node: &AstNode) -> Result<Value, RuntimeError> {
+        match &node.node {
+            Node::For {
+                clauses,
+                body,
+                collect,
+                frame_size,
+            } => {
+                let mut out = collect.then(Vec::new);
+                self.eval_for_clauses(clauses, body, *frame_size, &mut out)?;
+                Ok(out.map_or(Value::Nil, |items| Value::List(items.into_iter().collect())))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    // Walks the clauses of a `for`/`doseq`. Recursion only goes as deep as
+    // the clause count, each binding iterates in a plain loop. Returns false
+    // when a `:while` failed, which ends the nearest enclosing binding.
+    fn eval_for_clauses(
+        &mut self,
+        clauses: &[ForClause],
+        body: &AstNode,
+        frame_size: usize,
+        out: &mut Option<Vec<Value>>,
+    ) -> Result<bool, RuntimeError> {
+        let Some((clause, rest)) = clauses.split_first() else {
+            let v = self.eval(body)?;
+            if let Some(items) = out {
+                items.push(v);
+            }
+            return Ok(true);
+        };
+
+        match clause {
+            ForClause::Bind(id, coll) => {
+                let items = seq_items(&self.eval(coll)?, coll.span)?;
+                let outer = self.env.clone();
+                for item in items {
+                    // A fresh frame per item keeps closures from seeing later items
+                    let mut frame = Env::with_frame(outer.clone(), frame_size);
+                    frame.set_local(*id, item);
+                    self.env = Rc::new(RefCell::new(frame));
+                    let result = self.eval_for_clauses(rest, body, frame_size, out);
+                    self.env = outer.clone();
+                    if !result? {
+                        break;
+                    }
+                }
+                Ok(true)
+            }
+            ForClause::Let(bindings) => {
+                for (id, val_node) in bindings {
+                    let val = self.eval(val_node)?;
+                    self.env.borrow_mut().set_local(*id, val);
+                }
+                self.eval_for_clauses(rest, body, frame_size, out)
+            }
+            ForClause::When(test) => match self.eval(test)?.is_truthy() {
+                true => self.eval_for_clauses(rest, body, frame_size, out),
+                false => Ok(true),
+            },
+            ForClause::While(test) => match self.eval(test)?.is_truthy() {
+                true => self.eval_for_clauses(rest, body, frame_size, out),
+                false => Ok(false),
+            },
+        }
+    }
 }
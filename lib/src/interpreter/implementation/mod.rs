@@ -5,18 +5,26 @@ mod eval_literals;
 mod eval_logic;
 mod eval_loop;
 
-use super::builtins::builtins;
+use super::builtins::{
+    builtins, io_builtins, math_builtins, set_builtins, string_builtins, time_builtins,
+};
+use crate::interpreter::hierarchy::Hierarchy;
+use crate::interpreter::random::Rng;
+use crate::interpreter::stream::Stream;
 pub use crate::interpreter::{Callable, Env, RuntimeError, Value};
 use crate::lexer::Lexer;
 use crate::parser::Parser;
 use crate::sema::{analyze, AstNode, Node};
 use std::cell::RefCell;
+use std::io::Write;
 use std::rc::Rc;
 
 const SRC_STDLIB_CORE: &str = include_str!("../stdlib/src/core.risp");
 
 pub struct Interpreter {
     pub(super) env: Rc<RefCell<Env>>,
+    pub(super) rng: Rng,
+    pub(super) hierarchy: Hierarchy,
 }
 
 impl Default for Interpreter {
@@ -31,16 +39,47 @@ impl Interpreter {
         {
             let e = env.borrow();
             e.load_builtins("risp.internal", builtins());
+            e.load_builtins("risp.io", io_builtins());
+            e.load_builtins("risp.string", string_builtins());
+            e.load_builtins("risp.math", math_builtins());
+            e.load_builtins("risp.set", set_builtins());
+            e.load_builtins("risp.time", time_builtins());
             e.create_ns("risp.core", vec!["risp.internal"]);
             e.create_ns("user", vec!["risp.core"]);
         }
-        let mut interp = Self { env };
+        let mut interp = Self {
+            env,
+            rng: Rng::from_clock(),
+            hierarchy: Hierarchy::default(),
+        };
+        interp.set_out(Box::new(std::io::stdout()));
+        interp.set_err(Box::new(std::io::stderr()));
         interp
             .run_in_ns(SRC_STDLIB_CORE, "risp.core")
             .expect("core.risp failed to load");
         interp
     }
 
+    /// Restarts the generator behind `rand` & co. so runs are reproducible.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Rng::seeded(seed);
+    }
+
+    pub fn set_out(&mut self, out: Box<dyn Write>) {
+        self.set_port("*out*", out);
+    }
+
+    pub fn set_err(&mut self, err: Box<dyn Write>) {
+        self.set_port("*err*", err);
+    }
+
+    fn set_port(&mut self, name: &'static str, sink: Box<dyn Write>) {
+        let port = Value::Stream(Rc::new(Stream::port(name, sink)));
+        self.env
+            .borrow()
+            .load_builtins("risp.core", vec![(name, port)]);
+    }
+
     fn run_in_ns(&mut self, source: &str, ns: &str) -> Result<Value, RuntimeError> {
         let current_ns = self.env.borrow().get_current_namespace();
         self.env.borrow().set_current_namespace(ns);
@@ -51,7 +90,7 @@ impl Interpreter {
 
     pub fn completions(&self) -> Vec<String> {
         let builtins = self.env.borrow().public_names();
-        let special_forms = ["if", "let", "fn", "def", "defn", "do", "apply"];
+        let special_forms = ["if", "let", "fn", "def", "defn", "do", "apply", "with-open"];
         special_forms
             .iter()
             .map(|s| s.to_string())
@@ -60,7 +99,8 @@ impl Interpreter {
     }
 
     pub fn run(&mut self, source: &str) -> Result<Value, RuntimeError> {
-        let tokens = Lexer::tokenize(source);
+        let tokens =
+            Lexer::tokenize(source).map_err(|e| RuntimeError::ParseError(format!("{e}")))?;
         let cst = Parser::parse(tokens).map_err(|e| RuntimeError::ParseError(format!("{e}")))?;
         let nodes = analyze(cst).map_err(|e| RuntimeError::AnalyzeError(format!("{e}")))?;
         nodes
@@ -73,10 +113,15 @@ impl Interpreter {
     pub(super) fn eval(&mut self, node: &AstNode) -> Result<Value, RuntimeError> {
         match &node.node {
             Node::Long(n) => Ok(Value::Long(*n)),
+            Node::BigInt(n) => Ok(Value::BigInt(n.clone())),
+            Node::Ratio(r) => Ok(Value::Ratio(r.clone())),
             Node::Double(n) => Ok(Value::Double(*n)),
+            Node::Char(c) => Ok(Value::Char(*c)),
             Node::Bool(b) => Ok(Value::Bool(*b)),
             Node::Nil => Ok(Value::Nil),
             Node::String(s) => Ok(Value::String(Rc::from(s.as_str()))),
+            Node::Regex(re) => Ok(Value::Regex(re.clone())),
+            Node::Inst(inst) => Ok(Value::Inst(*inst)),
             Node::Keyword(s) => Ok(Value::Keyword(Rc::from(s.as_str()))),
             Node::Var(id) => self.eval_var(*id, node.span),
             Node::GlobalVar(name) => self.eval_global_var(name, node.span),
@@ -88,13 +133,22 @@ impl Interpreter {
             Node::Def { .. } => self.eval_def(node),
             Node::Call { .. } => self.eval_call(node),
             Node::Do(elems) => self.eval_do(elems),
+            Node::Case { .. } => {
+                let branch = self.select_case_branch(node)?;
+                self.eval(branch)
+            }
             Node::List(elems) => self.eval_list_literal(elems),
             Node::Vector(elems) => self.eval_vector_literal(elems),
             Node::Map(pairs) => self.eval_map_literal(pairs),
             Node::Set(elems) => self.eval_set_literal(elems),
+            Node::Queue(elems) => self.eval_queue_literal(elems),
             Node::Symbol(s) => Ok(Value::Symbol(Rc::from(s.as_str()))),
             Node::Loop { bindings, body } => self.eval_loop(bindings, body),
+            Node::For { .. } => self.eval_for(node),
             Node::Recur(_) => Err(RuntimeError::RecurOutsideLoop { span: node.span }),
+            Node::WithOpen { bindings, body } => self.eval_with_open(bindings, body),
+            Node::Binding { bindings, body } => self.eval_binding(bindings, body),
+            Node::WithOutStr(body) => self.eval_with_out_str(body),
         }
     }
 }
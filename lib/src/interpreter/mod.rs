@@ -1,10 +1,19 @@
 mod builtins;
 mod env;
+mod hierarchy;
 mod implementation;
+mod multimethod;
+mod printer;
+mod random;
+mod record;
+mod sorted;
+mod stream;
 #[cfg(test)]
 mod test_interpreter;
+mod transient;
 mod value;
 
 pub use env::Env;
 pub use implementation::Interpreter;
+pub use printer::Readable;
 pub use value::{Callable, RuntimeError, Value};
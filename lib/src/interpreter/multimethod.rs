@@ -0,0 +1,137 @@
+use std::cell::RefCell;
+
+use super::hierarchy::Hierarchy;
+use super::Value;
+
+/// A function that picks its implementation by calling `dispatch` on the
+/// arguments and matching the result against the method table with `isa?`.
+pub struct MultiFn {
+    pub name: String,
+    pub dispatch: Value,
+    pub default: Value,
+    methods: RefCell<Vec<(Value, Value)>>,
+    // Dispatch values each one is preferred over, from `prefer-method`
+    prefers: RefCell<Vec<(Value, Vec<Value>)>>,
+    // Resolved methods by dispatch value, valid for one hierarchy version.
+    // Only `isa?` matches are kept, so the cache stays within the method
+    // table and hierarchy rather than growing with every value dispatched
+    // to the default.
+    cache: RefCell<(u64, Vec<(Value, Value)>)>,
+}
+
+/// Why no single method applies to a dispatch value
+pub enum DispatchError {
+    NoMethod,
+    Ambiguous,
+}
+
+impl MultiFn {
+    pub fn new(name: String, dispatch: Value, default: Value) -> Self {
+        Self {
+            name,
+            dispatch,
+            default,
+            methods: RefCell::new(vec![]),
+            prefers: RefCell::new(vec![]),
+            cache: RefCell::new((0, vec![])),
+        }
+    }
+
+    pub fn methods(&self) -> Vec<(Value, Value)> {
+        self.methods.borrow().clone()
+    }
+
+    pub fn add_method(&self, dispatch_value: Value, method: Value) {
+        let mut methods = self.methods.borrow_mut();
+        match methods.iter_mut().find(|(k, _)| *k == dispatch_value) {
+            Some(existing) => existing.1 = method,
+            None => methods.push((dispatch_value, method)),
+        }
+        self.cache.borrow_mut().1.clear();
+    }
+
+    pub fn remove_method(&self, dispatch_value: &Value) {
+        self.methods
+            .borrow_mut()
+            .retain(|(k, _)| k != dispatch_value);
+        self.cache.borrow_mut().1.clear();
+    }
+
+    /// Prefers `x` over `y`, `false` when `y` is already preferred over `x`
+    pub fn prefer(&self, hierarchy: &Hierarchy, x: Value, y: Value) -> bool {
+        if self.prefers(hierarchy, &y, &x) {
+            return false;
+        }
+        let mut prefers = self.prefers.borrow_mut();
+        match prefers.iter_mut().find(|(k, _)| *k == x) {
+            Some((_, over)) => over.push(y),
+            None => prefers.push((x, vec![y])),
+        }
+        self.cache.borrow_mut().1.clear();
+        true
+    }
+
+    // A preference also holds for the parents of either side
+    fn prefers(&self, hierarchy: &Hierarchy, x: &Value, y: &Value) -> bool {
+        let direct = self
+            .prefers
+            .borrow()
+            .iter()
+            .any(|(k, over)| k == x && over.contains(y));
+        direct
+            || hierarchy
+                .parents(y)
+                .iter()
+                .any(|p| self.prefers(hierarchy, x, p))
+            || hierarchy
+                .parents(x)
+                .iter()
+                .any(|p| self.prefers(hierarchy, p, y))
+    }
+
+    fn dominates(&self, hierarchy: &Hierarchy, x: &Value, y: &Value) -> bool {
+        self.prefers(hierarchy, x, y) || hierarchy.isa(x, y)
+    }
+
+    /// The method for `value`: the most specific `isa?` match, else the
+    /// method registered for the default dispatch value.
+    pub fn find_method(
+        &self,
+        hierarchy: &Hierarchy,
+        value: &Value,
+    ) -> Result<Value, DispatchError> {
+        {
+            let mut cache = self.cache.borrow_mut();
+            if cache.0 != hierarchy.version() {
+                *cache = (hierarchy.version(), vec![]);
+            }
+            if let Some((_, method)) = cache.1.iter().find(|(k, _)| k == value) {
+                return Ok(method.clone());
+            }
+        }
+        let methods = self.methods.borrow();
+        let mut best: Option<&(Value, Value)> = None;
+        for entry in methods.iter().filter(|(k, _)| hierarchy.isa(value, k)) {
+            best = match best {
+                None => Some(entry),
+                Some(current) if self.dominates(hierarchy, &entry.0, &current.0) => Some(entry),
+                Some(current) if self.dominates(hierarchy, &current.0, &entry.0) => Some(current),
+                Some(_) => return Err(DispatchError::Ambiguous),
+            };
+        }
+        match best {
+            Some((_, method)) => {
+                self.cache
+                    .borrow_mut()
+                    .1
+                    .push((value.clone(), method.clone()));
+                Ok(method.clone())
+            }
+            None => methods
+                .iter()
+                .find(|(k, _)| *k == self.default)
+                .map(|(_, method)| method.clone())
+                .ok_or(DispatchError::NoMethod),
+        }
+    }
+}
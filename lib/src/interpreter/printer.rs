@@ -0,0 +1,95 @@
+use std::fmt::{Display, Formatter, Result};
+
+use super::value::{map_entries, set_items};
+use super::Value;
+
+/// Prints a value so the reader gives it back: strings are quoted and
+/// escaped and chars keep their `\` literal form, also inside collections.
+pub struct Readable<'a>(pub &'a Value);
+
+fn char_name(c: char) -> Option<&'static str> {
+    match c {
+        '\n' => Some("newline"),
+        ' ' => Some("space"),
+        '\t' => Some("tab"),
+        '\r' => Some("return"),
+        '\u{8}' => Some("backspace"),
+        '\u{c}' => Some("formfeed"),
+        _ => None,
+    }
+}
+
+fn write_seq<'a>(f: &mut Formatter<'_>, items: impl Iterator<Item = &'a Value>) -> Result {
+    for (i, e) in items.enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", Readable(e))?;
+    }
+    Ok(())
+}
+
+impl Display for Readable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            Value::String(s) => write!(f, "{s:?}"),
+            Value::BigInt(n) => write!(f, "{n}N"),
+            Value::Double(n) if n.is_nan() => write!(f, "##NaN"),
+            Value::Double(n) if n.is_infinite() => {
+                write!(f, "{}", if *n > 0.0 { "##Inf" } else { "##-Inf" })
+            }
+            // Debug keeps the `.0` of whole doubles so they read back as doubles
+            Value::Double(n) => write!(f, "{n:?}"),
+            Value::Regex(re) => write!(f, "#\"{}\"", re.as_str()),
+            Value::Inst(i) => write!(f, "#inst \"{i}\""),
+            Value::Char(c) => match char_name(*c) {
+                Some(name) => write!(f, "\\{name}"),
+                None if c.is_control() => write!(f, "\\u{:04x}", *c as u32),
+                None => write!(f, "\\{c}"),
+            },
+            Value::List(l) => {
+                write!(f, "(")?;
+                write_seq(f, l.iter())?;
+                write!(f, ")")
+            }
+            Value::Queue(q) => {
+                write!(f, "#queue [")?;
+                write_seq(f, q.iter())?;
+                write!(f, "]")
+            }
+            Value::Vector(v) => {
+                write!(f, "[")?;
+                write_seq(f, v.iter())?;
+                write!(f, "]")
+            }
+            Value::Set(_) | Value::SortedSet(_) => {
+                write!(f, "#{{")?;
+                write_seq(f, set_items(self.0).into_iter())?;
+                write!(f, "}}")
+            }
+            Value::Map(_) | Value::SortedMap(_) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in map_entries(self.0).into_iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{} {}", Readable(k), Readable(v))?;
+                }
+                write!(f, "}}")
+            }
+            Value::Record(r) if r.rtype.is_record => {
+                write!(f, "#{}{{", r.rtype.name)?;
+                for (i, (k, v)) in r.entries().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{} {}", Readable(k), Readable(v))?;
+                }
+                write!(f, "}}")
+            }
+            Value::Reduced(v) => write!(f, "#<reduced {}>", Readable(v)),
+            Value::Volatile(v) => write!(f, "#<volatile {}>", Readable(&v.borrow())),
+            v => write!(f, "{v}"),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::Value;
+
+/// A type made by `defrecord` or `deftype`. Each definition is a new type,
+/// so types compare by identity rather than by name.
+pub struct RecordType {
+    pub name: String,
+    // Field keywords in constructor order
+    pub fields: Vec<Value>,
+    // Records behave as maps, `deftype` instances only hold their fields
+    pub is_record: bool,
+}
+
+#[derive(Clone)]
+pub struct Record {
+    pub rtype: Rc<RecordType>,
+    pub values: Vec<Value>,
+    // Entries assoc'ed onto a record beyond its fields
+    pub extra: Vec<(Value, Value)>,
+}
+
+impl Record {
+    pub fn field(&self, key: &Value) -> Option<&Value> {
+        self.rtype
+            .fields
+            .iter()
+            .position(|f| f == key)
+            .map(|i| &self.values[i])
+    }
+
+    /// Fields first, then extra entries
+    pub fn entries(&self) -> impl Iterator<Item = (&Value, &Value)> {
+        self.rtype
+            .fields
+            .iter()
+            .zip(self.values.iter())
+            .chain(self.extra.iter().map(|(k, v)| (k, v)))
+    }
+}
+
+/// What protocols dispatch on: a built-in kind of value, named after
+/// `Value::type_name`, or a record type.
+#[derive(Clone)]
+pub enum TypeTag {
+    Builtin(&'static str),
+    Record(Rc<RecordType>),
+}
+
+impl PartialEq for TypeTag {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TypeTag::Builtin(a), TypeTag::Builtin(b)) => a == b,
+            (TypeTag::Record(a), TypeTag::Record(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl TypeTag {
+    pub fn name(&self) -> &str {
+        match self {
+            TypeTag::Builtin(name) => name,
+            TypeTag::Record(t) => &t.name,
+        }
+    }
+}
+
+// Method name keywords paired with their implementations
+type Methods = Vec<(Value, Value)>;
+
+/// A named set of methods, each implemented per type by `extend`.
+pub struct Protocol {
+    pub name: String,
+    pub methods: Vec<Value>,
+    impls: RefCell<Vec<(TypeTag, Methods)>>,
+}
+
+impl Protocol {
+    pub fn new(name: String, methods: Vec<Value>) -> Self {
+        Self {
+            name,
+            methods,
+            impls: RefCell::new(vec![]),
+        }
+    }
+
+    /// Adds or replaces the implementations of some methods for `tag`
+    pub fn extend(&self, tag: TypeTag, methods: Methods) {
+        let mut impls = self.impls.borrow_mut();
+        let table = match impls.iter().position(|(t, _)| *t == tag) {
+            Some(i) => &mut impls[i].1,
+            None => {
+                impls.push((tag, vec![]));
+                &mut impls.last_mut().unwrap().1
+            }
+        };
+        for (name, f) in methods {
+            match table.iter_mut().find(|(n, _)| *n == name) {
+                Some(existing) => existing.1 = f,
+                None => table.push((name, f)),
+            }
+        }
+    }
+
+    pub fn extends(&self, tag: &TypeTag) -> bool {
+        self.impls.borrow().iter().any(|(t, _)| t == tag)
+    }
+
+    pub fn method(&self, tag: &TypeTag, name: &Value) -> Option<Value> {
+        self.impls
+            .borrow()
+            .iter()
+            .find(|(t, _)| t == tag)
+            .and_then(|(_, table)| table.iter().find(|(n, _)| n == name))
+            .map(|(_, f)| f.clone())
+    }
+}
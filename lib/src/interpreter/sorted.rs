@@ -0,0 +1,27 @@
+use crate::collections::SortedTree;
+
+use super::Value;
+
+/// Backing store of `sorted-map` (`V = Value`) and `sorted-set` (`V = ()`):
+/// the tree plus the comparator it is ordered by, `None` meaning `compare`.
+pub struct Sorted<V> {
+    pub tree: SortedTree<Value, V>,
+    pub comparator: Option<Value>,
+}
+
+impl<V: Clone> Sorted<V> {
+    pub fn empty(comparator: Option<Value>) -> Self {
+        Self {
+            tree: SortedTree::new(),
+            comparator,
+        }
+    }
+
+    /// Same ordering, different contents.
+    pub fn with_tree(&self, tree: SortedTree<Value, V>) -> Self {
+        Self {
+            tree,
+            comparator: self.comparator.clone(),
+        }
+    }
+}
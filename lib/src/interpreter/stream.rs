@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::rc::Rc;
+
+enum StreamKind {
+    Reader(BufReader<File>),
+    Writer(BufWriter<File>),
+    // Host-provided sink (stdout, stderr, embedder capture); flushed per write
+    Port(Box<dyn Write>),
+    Buffer(String),
+}
+
+pub struct Stream {
+    pub name: Rc<str>,
+    kind: RefCell<Option<StreamKind>>,
+}
+
+fn closed() -> io::Error {
+    io::Error::other("stream closed")
+}
+
+impl Stream {
+    pub fn reader(name: &str, file: File) -> Self {
+        Self {
+            name: name.into(),
+            kind: RefCell::new(Some(StreamKind::Reader(BufReader::new(file)))),
+        }
+    }
+
+    pub fn writer(name: &str, file: File) -> Self {
+        Self {
+            name: name.into(),
+            kind: RefCell::new(Some(StreamKind::Writer(BufWriter::new(file)))),
+        }
+    }
+
+    pub fn port(name: &str, sink: Box<dyn Write>) -> Self {
+        Self {
+            name: name.into(),
+            kind: RefCell::new(Some(StreamKind::Port(sink))),
+        }
+    }
+
+    pub fn buffer(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            kind: RefCell::new(Some(StreamKind::Buffer(String::new()))),
+        }
+    }
+
+    /// Contents written so far to a buffer stream, empty for any other kind.
+    pub fn buffered(&self) -> String {
+        match self.kind.borrow().as_ref() {
+            Some(StreamKind::Buffer(buf)) => buf.clone(),
+            _ => String::new(),
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.kind.borrow().is_none()
+    }
+
+    pub fn close(&self) -> io::Result<()> {
+        match self.kind.borrow_mut().take() {
+            Some(StreamKind::Writer(mut w)) => w.flush(),
+            Some(StreamKind::Port(mut w)) => w.flush(),
+            Some(StreamKind::Reader(_)) | Some(StreamKind::Buffer(_)) | None => Ok(()),
+        }
+    }
+
+    pub fn read_line(&self) -> io::Result<Option<String>> {
+        match self.kind.borrow_mut().as_mut() {
+            Some(StreamKind::Reader(r)) => read_line_from(r),
+            Some(_) => Err(io::Error::other("stream is not readable")),
+            None => Err(closed()),
+        }
+    }
+
+    pub fn write_str(&self, s: &str) -> io::Result<()> {
+        match self.kind.borrow_mut().as_mut() {
+            Some(StreamKind::Writer(w)) => w.write_all(s.as_bytes()),
+            Some(StreamKind::Port(w)) => w.write_all(s.as_bytes()).and_then(|_| w.flush()),
+            Some(StreamKind::Buffer(buf)) => {
+                buf.push_str(s);
+                Ok(())
+            }
+            Some(StreamKind::Reader(_)) => Err(io::Error::other("stream is not writable")),
+            None => Err(closed()),
+        }
+    }
+}
+
+/// Reads one line without its trailing `\n` / `\r\n`, `None` at end of input.
+pub fn read_line_from(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    Ok(Some(line))
+}
@@ -25,6 +25,8 @@ mod tests {
         assert!(matches!(run("-7"), Value::Long(-7)));
     }
 
+    // 3.14 is the literal under test, not an approximation of PI
+    #[allow(clippy::approx_constant)]
     #[test]
     fn eval_double() {
         assert!(matches!(run("3.14"), Value::Double(v) if (v - 3.14).abs() < f64::EPSILON));
@@ -501,4 +503,194 @@ mod tests {
     fn eval_string_escape_backslash() {
         assert!(matches!(run("\"a\\\\b\""), Value::String(s) if s.as_ref() == "a\\b"));
     }
+
+    #[test]
+    fn eval_threading_macros() {
+        assert!(matches!(run("(-> 1 inc (* 10) (- 3))"), Value::Long(17)));
+        assert!(matches!(run("(->> 10 (- 3))"), Value::Long(-7)));
+        assert!(matches!(run("(as-> 5 x (+ x 1) (* 2 x))"), Value::Long(12)));
+        assert!(matches!(run("(-> {:a {:b 2}} :a :b)"), Value::Long(2)));
+    }
+
+    #[test]
+    fn eval_some_thread_stops_at_nil() {
+        assert!(matches!(run("(some-> {:a 1} :a inc)"), Value::Long(2)));
+        assert!(matches!(run("(some-> {:a 1} :b inc)"), Value::Nil));
+        assert!(matches!(run("(some-> false not)"), Value::Bool(true)));
+    }
+
+    #[test]
+    fn eval_cond_thread() {
+        assert!(matches!(
+            run("(cond-> 1 true inc false (* 100) :always (* 10))"),
+            Value::Long(20)
+        ));
+    }
+
+    #[test]
+    fn eval_when_forms() {
+        assert!(matches!(run("(when 1 2 3)"), Value::Long(3)));
+        assert!(matches!(run("(when false 1)"), Value::Nil));
+        assert!(matches!(run("(when-let [x 4] (* x x))"), Value::Long(16)));
+        assert!(matches!(run("(when-let [x nil] 1)"), Value::Nil));
+        assert!(matches!(run("(if-let [x 3] x 0)"), Value::Long(3)));
+        assert!(matches!(run("(if-let [x false] x 0)"), Value::Long(0)));
+    }
+
+    #[test]
+    fn eval_if_let_else_does_not_see_binding() {
+        assert!(matches!(
+            run_err("(if-let [y nil] 1 y)"),
+            RuntimeError::UndefinedVariable { .. }
+        ));
+    }
+
+    #[test]
+    fn eval_cond() {
+        assert!(matches!(run("(cond (= 1 2) 1 (= 1 1) 2 :else 3)"), Value::Long(2)));
+        assert!(matches!(run("(cond false 1 :else 3)"), Value::Long(3)));
+        assert!(matches!(run("(cond false 1)"), Value::Nil));
+    }
+
+    #[test]
+    fn eval_condp() {
+        assert!(matches!(run("(condp = 3 1 :a 3 :b :c)"), Value::Keyword(k) if &*k == "b"));
+        assert!(matches!(run("(condp = 9 1 :a :c)"), Value::Keyword(k) if &*k == "c"));
+        assert!(matches!(run("(condp get :k {:k 4} :>> inc 0)"), Value::Long(5)));
+        assert!(matches!(
+            run_err("(condp = 9 1 :a)"),
+            RuntimeError::NoMatchingClause { .. }
+        ));
+    }
+
+    #[test]
+    fn eval_case() {
+        assert!(matches!(run("(case 2 1 :a (2 3) :b :c)"), Value::Keyword(k) if &*k == "b"));
+        assert!(matches!(run("(case 7 1 :a :c)"), Value::Keyword(k) if &*k == "c"));
+        assert!(matches!(run("(case \"s\" \"s\" 1 2)"), Value::Long(1)));
+        assert!(matches!(run("(case 'x x 1 2)"), Value::Long(1)));
+        assert!(matches!(run("(case nil nil 1 2)"), Value::Long(1)));
+        assert!(matches!(run("(case [1] 1 :a :c)"), Value::Keyword(k) if &*k == "c"));
+        assert!(matches!(run("(case (+ 1N 1) 2 :long :default)"), Value::Keyword(k) if &*k == "long"));
+    }
+
+    #[test]
+    fn eval_case_without_match() {
+        let source = "(case (+ 4 5) 1 :a)";
+        match run_err(source) {
+            RuntimeError::NoMatchingClause { value, span } => {
+                assert_eq!(value, "9");
+                assert_eq!(span.lo as usize, source.find("(+").unwrap());
+            }
+            e => panic!("expected no matching clause, got {e}"),
+        }
+    }
+
+    #[test]
+    fn eval_case_in_loop_tail_position() {
+        assert!(matches!(
+            run("(loop [i 0] (case i 5 i (recur (inc i))))"),
+            Value::Long(5)
+        ));
+    }
+
+    #[test]
+    fn eval_threading_error_points_at_user_form() {
+        let source = "(-> 1 (undefined-fn 2))";
+        match run_err(source) {
+            RuntimeError::UndefinedVariable { span, .. } => {
+                assert_eq!(span.lo as usize, source.find("undefined-fn").unwrap())
+            }
+            e => panic!("expected undefined variable, got {e}"),
+        }
+    }
+
+    #[test]
+    fn eval_for_nested_bindings() {
+        match run("(for [x [1 2] y [:a :b]] [x y])") {
+            Value::List(l) => assert_eq!(l.len(), 4),
+            v => panic!("expected list, got {v:?}"),
+        }
+    }
+
+    #[test]
+    fn eval_for_modifiers() {
+        assert_eq!(
+            run("(for [x [1 2 3 4 5] :let [y (* x x)] :when (odd? x)] y)"),
+            run("'(1 9 25)")
+        );
+        assert_eq!(
+            run("(for [x [1 2 3 4 5] :while (< x 3)] x)"),
+            run("'(1 2)")
+        );
+        // :while only ends the binding right before it
+        assert_eq!(
+            run("(for [x [1 2 3] y [1 2 3] :while (< y x)] [x y])"),
+            run("'([2 1] [3 1] [3 2])")
+        );
+    }
+
+    #[test]
+    fn eval_for_closures_capture_each_item() {
+        assert_eq!(
+            run("(map (fn [f] (f)) (for [x [1 2]] (fn [] x)))"),
+            run("[1 2]")
+        );
+    }
+
+    #[test]
+    fn eval_for_inside_fn_sees_params() {
+        assert_eq!(
+            run("((fn [n] (for [x [1 2] :let [y (+ x n)]] y)) 10)"),
+            run("'(11 12)")
+        );
+    }
+
+    #[test]
+    fn eval_doseq_returns_nil() {
+        assert!(matches!(run("(doseq [x [1 2] y \"ab\"] (str x y))"), Value::Nil));
+        assert!(matches!(
+            run("(with-out-str (doseq [x [1 2] :when (even? x)] (pr x) (pr :x)))"),
+            Value::String(s) if &*s == "2:x"
+        ));
+    }
+
+    #[test]
+    fn eval_for_large_count_in_constant_stack() {
+        assert!(matches!(
+            run("(def v [0 1 2 3 4 5 6 7 8 9])
+                 (count (for [a v b v c v d v e v] e))"),
+            Value::Long(100000)
+        ));
+    }
+
+    #[test]
+    fn eval_dotimes() {
+        assert!(matches!(
+            run("(with-out-str (dotimes [i 3] (pr i)))"),
+            Value::String(s) if &*s == "012"
+        ));
+        assert!(matches!(
+            run("(def n 0) (dotimes [i 100000] (def n (+ n i))) n"),
+            Value::Long(4999950000)
+        ));
+    }
+
+    #[test]
+    fn eval_while() {
+        assert!(matches!(
+            run("(def i 0) (while (< i 100000) (def i (inc i))) i"),
+            Value::Long(100000)
+        ));
+        assert!(matches!(run("(while false 1)"), Value::Nil));
+    }
+
+    #[test]
+    fn eval_for_bad_bindings() {
+        assert!(matches!(run_err("(for [:when true] 1)"), RuntimeError::AnalyzeError(_)));
+        assert!(matches!(run_err("(for [x] x)"), RuntimeError::AnalyzeError(_)));
+        assert!(matches!(run_err("(for [x [1]] x x)"), RuntimeError::AnalyzeError(_)));
+        assert!(matches!(run_err("(for [x 1] x)"), RuntimeError::TypeError { .. }));
+    }
 }
+
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::value::hash_value;
+use super::Value;
+
+/// Contents of a `transient` collection, edited in place by `conj!` and
+/// friends until `persistent!` turns it back into a value.
+pub enum Transient {
+    Vector(Vec<Value>),
+    Map(Indexed<(Value, Value)>),
+    Set(Indexed<Value>),
+}
+
+impl Transient {
+    /// A copy of the contents of `coll`, `None` when it cannot be edited.
+    pub fn of(coll: &Value) -> Option<Self> {
+        match coll {
+            Value::Vector(v) => Some(Self::Vector((**v).clone())),
+            Value::Map(m) => Some(Self::Map(m.iter().cloned().collect())),
+            Value::Set(s) => Some(Self::Set(s.iter().cloned().collect())),
+            _ => None,
+        }
+    }
+
+    pub fn persistent(self) -> Value {
+        match self {
+            Self::Vector(v) => Value::Vector(Rc::new(v)),
+            Self::Map(m) => Value::Map(Rc::new(m.into_vec())),
+            Self::Set(s) => Value::Set(Rc::new(s.into_vec())),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Vector(v) => v.len(),
+            Self::Map(m) => m.len(),
+            Self::Set(s) => s.len(),
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Vector(_) => "transient vector",
+            Self::Map(_) => "transient map",
+            Self::Set(_) => "transient set",
+        }
+    }
+}
+
+/// Map entries or set items, keyed by the map key or the item itself.
+pub trait Keyed {
+    fn key(&self) -> &Value;
+}
+
+impl Keyed for Value {
+    fn key(&self) -> &Value {
+        self
+    }
+}
+
+impl Keyed for (Value, Value) {
+    fn key(&self) -> &Value {
+        &self.0
+    }
+}
+
+/// Items in insertion order with an index from key hash to slot, so adding
+/// and finding a key doesn't scan. Removed items leave an empty slot until
+/// `into_vec`, which keeps removal cheap and the order intact.
+pub struct Indexed<T> {
+    slots: Vec<Option<T>>,
+    index: HashMap<u64, Vec<usize>>,
+    len: usize,
+}
+
+impl<T: Keyed> Indexed<T> {
+    fn slot(&self, key: &Value) -> Option<usize> {
+        self.index
+            .get(&hash_value(key))?
+            .iter()
+            .copied()
+            .find(|&i| self.slots[i].as_ref().is_some_and(|item| item.key() == key))
+    }
+
+    pub fn get(&self, key: &Value) -> Option<&T> {
+        self.slot(key).and_then(|i| self.slots[i].as_ref())
+    }
+
+    pub fn get_mut(&mut self, key: &Value) -> Option<&mut T> {
+        self.slot(key).and_then(|i| self.slots[i].as_mut())
+    }
+
+    /// Adds `item` unless its key is already present.
+    pub fn insert(&mut self, item: T) {
+        if self.slot(item.key()).is_some() {
+            return;
+        }
+        self.index
+            .entry(hash_value(item.key()))
+            .or_default()
+            .push(self.slots.len());
+        self.slots.push(Some(item));
+        self.len += 1;
+    }
+
+    pub fn remove(&mut self, key: &Value) {
+        let Some(i) = self.slot(key) else {
+            return;
+        };
+        let hash = hash_value(key);
+        if let Some(bucket) = self.index.get_mut(&hash) {
+            bucket.retain(|&s| s != i);
+            if bucket.is_empty() {
+                self.index.remove(&hash);
+            }
+        }
+        self.slots[i] = None;
+        self.len -= 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.slots.into_iter().flatten().collect()
+    }
+}
+
+impl<T: Keyed> FromIterator<T> for Indexed<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut indexed = Self {
+            slots: vec![],
+            index: HashMap::new(),
+            len: 0,
+        };
+        for item in items {
+            indexed.insert(item);
+        }
+        indexed
+    }
+}
@@ -1,11 +1,25 @@
-use crate::collections::RispList;
+use crate::collections::{RispList, RispQueue};
+use crate::inst::Inst;
 use crate::lexer::Span;
+use crate::pattern::Pattern;
 use crate::sema::{AstNode, FnArity, LocalId};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::{cell::RefCell, rc::Rc};
 
 use super::env::Env;
+use super::multimethod::MultiFn;
+use super::record::{Protocol, Record, TypeTag};
+use super::sorted::Sorted;
+use super::stream::Stream;
+use super::transient::Transient;
+use super::Interpreter;
 
 type BuiltinFn = fn(&[(Value, Span)], Span) -> Result<Value, RuntimeError>;
+// Builtins that need the running interpreter (ports, calling back into closures)
+type NativeFn = fn(&mut Interpreter, &[(Value, Span)], Span) -> Result<Value, RuntimeError>;
 
 #[derive(Clone)]
 pub struct ClosureArity {
@@ -51,15 +65,49 @@ pub enum RuntimeError {
     },
     IndexOutOfBounds {
         max_accessible: usize,
-        got: usize,
+        got: i64,
         span: Span,
     },
     DivisionByZero(Span),
+    ArithmeticOverflow {
+        span: Span,
+    },
     ParseError(String),
     AnalyzeError(String),
     RecurOutsideLoop {
         span: Span,
     },
+    Io {
+        message: String,
+        span: Span,
+    },
+    NoMatchingClause {
+        value: String,
+        span: Span,
+    },
+    IllegalState {
+        message: &'static str,
+        span: Span,
+    },
+    NoMethod {
+        name: String,
+        value: String,
+        span: Span,
+    },
+    AmbiguousMethod {
+        name: String,
+        value: String,
+        span: Span,
+    },
+    NoSuchField {
+        field: String,
+        type_name: String,
+        span: Span,
+    },
+    InvalidRegex {
+        message: String,
+        span: Span,
+    },
 }
 
 #[derive(Clone)]
@@ -79,6 +127,16 @@ pub enum Callable {
         name: &'static str,
         func: BuiltinFn,
     },
+    Native {
+        name: &'static str,
+        func: NativeFn,
+    },
+    Multi(Rc<MultiFn>),
+    // A `defprotocol` method, dispatching on the type of its first argument
+    ProtocolMethod {
+        protocol: Rc<Protocol>,
+        method: Value,
+    },
 }
 
 impl std::fmt::Display for Callable {
@@ -93,24 +151,59 @@ impl std::fmt::Display for Callable {
                 write!(f, "#<fn {value}>")
             }
             Self::Builtin { name, func: _ } => write!(f, "{name}"),
+            Self::Native { name, func: _ } => write!(f, "{name}"),
+            Self::Multi(multi) => write!(f, "#<multi-fn {}>", multi.name),
+            Self::ProtocolMethod { protocol, method } => {
+                write!(
+                    f,
+                    "#<protocol-fn {}/{}>",
+                    protocol.name,
+                    method_name(method)
+                )
+            }
         }
     }
 }
 
+// Protocol methods are keyed by keyword, shown without the colon
+pub(super) fn method_name(method: &Value) -> &str {
+    match method {
+        Value::Keyword(k) => k,
+        _ => "?",
+    }
+}
+
 #[derive(Clone)]
 pub enum Value {
     Nil,
     Bool(bool),
     Long(i64),
+    BigInt(Rc<BigInt>),
+    Ratio(Rc<BigRational>),
     Double(f64),
+    Char(char),
     String(Rc<str>),
+    Regex(Rc<Pattern>),
+    Inst(Inst),
     Keyword(Rc<str>),
     List(RispList<Value>),
+    Queue(RispQueue<Value>),
     Vector(Rc<Vec<Value>>),
     Map(Rc<Vec<(Value, Value)>>),
     Set(Rc<Vec<Value>>),
+    SortedMap(Rc<Sorted<Value>>),
+    SortedSet(Rc<Sorted<()>>),
     Symbol(Rc<str>),
     Callable(Rc<Callable>),
+    Stream(Rc<Stream>),
+    // Marks a reduction as finished, see `reduced`
+    Reduced(Rc<Value>),
+    Volatile(Rc<RefCell<Value>>),
+    // `None` once `persistent!` has taken the contents back
+    Transient(Rc<RefCell<Option<Transient>>>),
+    Record(Rc<Record>),
+    Type(TypeTag),
+    Protocol(Rc<Protocol>),
 }
 
 impl PartialEq for Value {
@@ -119,7 +212,13 @@ impl PartialEq for Value {
             (Value::Nil, Value::Nil) => true,
             (Value::Bool(a), Value::Bool(b)) => a == b,
             (Value::Long(a), Value::Long(b)) => a == b,
+            (Value::BigInt(a), Value::BigInt(b)) => a == b,
+            (Value::Ratio(a), Value::Ratio(b)) => a == b,
+            (Value::Long(l), Value::BigInt(b)) | (Value::BigInt(b), Value::Long(l)) => {
+                BigInt::from(*l) == **b
+            }
             (Value::Double(a), Value::Double(b)) => a == b,
+            (Value::Char(a), Value::Char(b)) => a == b,
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Keyword(a), Value::Keyword(b)) => a == b,
             (Value::Symbol(a), Value::Symbol(b)) => a == b,
@@ -128,28 +227,180 @@ impl PartialEq for Value {
             (Value::List(l), Value::Vector(v)) | (Value::Vector(v), Value::List(l)) => {
                 l.len() == v.len() && l.iter().zip(v.iter()).all(|(x, y)| x == y)
             }
-            (Value::Set(a), Value::Set(b)) => a == b,
-            (Value::Map(a), Value::Map(b)) => a == b,
+            (Value::Queue(a), Value::Queue(b)) => a == b,
+            (Value::Queue(q), Value::List(l)) | (Value::List(l), Value::Queue(q)) => {
+                q.len() == l.len() && q.iter().zip(l.iter()).all(|(x, y)| x == y)
+            }
+            (Value::Queue(q), Value::Vector(v)) | (Value::Vector(v), Value::Queue(q)) => {
+                q.len() == v.len() && q.iter().zip(v.iter()).all(|(x, y)| x == y)
+            }
+            // Maps and sets are equal when they hold the same entries, whatever
+            // their order or kind
+            (Value::SortedMap(_) | Value::Map(_), Value::SortedMap(_) | Value::Map(_)) => {
+                let (a, b) = (map_entries(self), map_entries(other));
+                a.len() == b.len() && a.iter().all(|entry| b.contains(entry))
+            }
+            (Value::SortedSet(_) | Value::Set(_), Value::SortedSet(_) | Value::Set(_)) => {
+                let (a, b) = (set_items(self), set_items(other));
+                a.len() == b.len() && a.iter().all(|item| b.contains(item))
+            }
+            (Value::Stream(a), Value::Stream(b)) => Rc::ptr_eq(a, b),
+            (Value::Regex(a), Value::Regex(b)) => Rc::ptr_eq(a, b),
+            (Value::Inst(a), Value::Inst(b)) => a == b,
+            (Value::Reduced(a), Value::Reduced(b)) => a == b,
+            (Value::Volatile(a), Value::Volatile(b)) => Rc::ptr_eq(a, b),
+            (Value::Transient(a), Value::Transient(b)) => Rc::ptr_eq(a, b),
+            // Records of one type are equal by value, `deftype` instances
+            // only to themselves
+            (Value::Record(a), Value::Record(b)) if a.rtype.is_record => {
+                let (x, y) = (map_entries(self), map_entries(other));
+                Rc::ptr_eq(&a.rtype, &b.rtype)
+                    && x.len() == y.len()
+                    && x.iter().all(|entry| y.contains(entry))
+            }
+            (Value::Record(a), Value::Record(b)) => Rc::ptr_eq(a, b),
+            (Value::Type(a), Value::Type(b)) => a == b,
+            (Value::Protocol(a), Value::Protocol(b)) => Rc::ptr_eq(a, b),
             // Callables are never equal
             _ => false,
         }
     }
 }
+// Entries of a map or sorted map, empty for anything else
+pub(super) fn map_entries(v: &Value) -> Vec<(&Value, &Value)> {
+    match v {
+        Value::Map(m) => m.iter().map(|(k, v)| (k, v)).collect(),
+        Value::SortedMap(m) => m.tree.iter().collect(),
+        Value::Record(r) if r.rtype.is_record => r.entries().collect(),
+        _ => vec![],
+    }
+}
+
+pub(super) fn set_items(v: &Value) -> Vec<&Value> {
+    match v {
+        Value::Set(s) => s.iter().collect(),
+        Value::SortedSet(s) => s.tree.iter().map(|(k, _)| k).collect(),
+        _ => vec![],
+    }
+}
+
+/// A hash that agrees with `==`: values that compare equal across kinds
+/// (`1` and `1N`, a list and a vector, a map and a sorted map) hash alike.
+pub fn hash_value(v: &Value) -> u64 {
+    let mut state = DefaultHasher::new();
+    hash_into(v, &mut state);
+    state.finish()
+}
+
+fn hash_into(v: &Value, state: &mut DefaultHasher) {
+    fn addr<T: ?Sized>(rc: &Rc<T>) -> usize {
+        Rc::as_ptr(rc) as *const () as usize
+    }
+    // Maps and sets are equal in any order, so their entries are summed
+    fn unordered<'a>(items: impl Iterator<Item = (&'a Value, Option<&'a Value>)>) -> u64 {
+        items.fold(0u64, |acc, (k, v)| {
+            let mut entry = DefaultHasher::new();
+            hash_into(k, &mut entry);
+            if let Some(v) = v {
+                hash_into(v, &mut entry);
+            }
+            acc.wrapping_add(entry.finish())
+        })
+    }
+    match v {
+        Value::Nil => 0u8.hash(state),
+        Value::Bool(b) => (1u8, b).hash(state),
+        Value::Long(n) => (2u8, n).hash(state),
+        Value::BigInt(n) => match n.to_i64() {
+            Some(n) => (2u8, n).hash(state),
+            None => (3u8, &**n).hash(state),
+        },
+        Value::Ratio(r) => (4u8, &**r).hash(state),
+        // 0.0 and -0.0 are equal
+        Value::Double(d) => (5u8, if *d == 0.0 { 0 } else { d.to_bits() }).hash(state),
+        Value::Char(c) => (6u8, c).hash(state),
+        Value::String(s) => (7u8, &**s).hash(state),
+        Value::Keyword(s) => (8u8, &**s).hash(state),
+        Value::Symbol(s) => (9u8, &**s).hash(state),
+        Value::List(l) => {
+            10u8.hash(state);
+            l.iter().for_each(|x| hash_into(x, state));
+        }
+        Value::Vector(xs) => {
+            10u8.hash(state);
+            xs.iter().for_each(|x| hash_into(x, state));
+        }
+        Value::Queue(q) => {
+            10u8.hash(state);
+            q.iter().for_each(|x| hash_into(x, state));
+        }
+        Value::Record(r) if !r.rtype.is_record => (16u8, addr(r)).hash(state),
+        Value::Map(_) | Value::SortedMap(_) | Value::Record(_) => {
+            let entries = map_entries(v);
+            (
+                11u8,
+                unordered(entries.into_iter().map(|(k, v)| (k, Some(v)))),
+            )
+                .hash(state)
+        }
+        Value::Set(_) | Value::SortedSet(_) => {
+            (12u8, unordered(set_items(v).into_iter().map(|x| (x, None)))).hash(state)
+        }
+        Value::Inst(i) => (13u8, i.millis).hash(state),
+        Value::Reduced(x) => {
+            14u8.hash(state);
+            hash_into(x, state);
+        }
+        Value::Type(t) => (15u8, t.name()).hash(state),
+        Value::Stream(s) => (16u8, addr(s)).hash(state),
+        Value::Regex(re) => (16u8, addr(re)).hash(state),
+        Value::Volatile(c) => (16u8, addr(c)).hash(state),
+        Value::Transient(c) => (16u8, addr(c)).hash(state),
+        Value::Protocol(p) => (16u8, addr(p)).hash(state),
+        // Never equal to anything, so any hash will do
+        Value::Callable(_) => 17u8.hash(state),
+    }
+}
+
 impl std::fmt::Debug for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Value::Nil => write!(f, "Nil"),
             Value::Bool(b) => write!(f, "Bool({b})"),
             Value::Long(n) => write!(f, "Long({n})"),
+            Value::BigInt(n) => write!(f, "BigInt({n})"),
+            Value::Ratio(r) => write!(f, "Ratio({r})"),
             Value::Double(n) => write!(f, "Double({n})"),
+            Value::Char(c) => write!(f, "Char({c:?})"),
             Value::String(s) => write!(f, "String({s:?})"),
+            Value::Regex(re) => write!(f, "Regex({:?})", re.as_str()),
+            Value::Inst(i) => write!(f, "Inst({i})"),
             Value::Keyword(s) => write!(f, "Keyword({s})"),
             Value::List(v) => write!(f, "List({v:?})"),
+            Value::Queue(q) => write!(f, "Queue({q:?})"),
             Value::Vector(v) => write!(f, "Vector({v:?})"),
             Value::Map(m) => write!(f, "Map({m:?})"),
             Value::Set(v) => write!(f, "Set({v:?})"),
+            Value::SortedMap(_) => write!(f, "SortedMap({:?})", map_entries(self)),
+            Value::SortedSet(_) => write!(f, "SortedSet({:?})", set_items(self)),
             Value::Symbol(s) => write!(f, "Symbol({s})"),
             Value::Callable(_) => write!(f, "Callable(...)"),
+            Value::Stream(s) => write!(f, "Stream({})", s.name),
+            Value::Reduced(v) => write!(f, "Reduced({v:?})"),
+            Value::Volatile(v) => write!(f, "Volatile({:?})", v.borrow()),
+            Value::Transient(_) => write!(f, "Transient({self})"),
+            Value::Record(_) => write!(f, "Record({self})"),
+            Value::Type(t) => write!(f, "Type({})", t.name()),
+            Value::Protocol(p) => write!(f, "Protocol({})", p.name),
+        }
+    }
+}
+
+impl RuntimeError {
+    pub fn from_io(err: std::io::Error, span: Span) -> Self {
+        RuntimeError::Io {
+            message: err.to_string(),
+            span,
         }
     }
 }
@@ -171,6 +422,7 @@ impl std::fmt::Display for RuntimeError {
                 write!(f, "(type-error\n  (expected {expected})\n  (got {got}))")
             }
             RuntimeError::DivisionByZero(_) => write!(f, "(division-by-zero)"),
+            RuntimeError::ArithmeticOverflow { .. } => write!(f, "(arithmetic-overflow)"),
             RuntimeError::ParseError(msg) => write!(f, "(parse-error\n  {msg})"),
             RuntimeError::AnalyzeError(msg) => write!(f, "(analyze-error {msg})"),
             RuntimeError::UnsupportedType { t, span: _ } => write!(f, "(unsupported-type \"{t})\""),
@@ -183,6 +435,25 @@ impl std::fmt::Display for RuntimeError {
                 "(index-out-of-bounds\n  (max-index {max_accessible})\n  (got {got}))",
             ),
             RuntimeError::RecurOutsideLoop { .. } => write!(f, "(recur-outside-loop)"),
+            RuntimeError::Io { message, .. } => write!(f, "(io-error \"{message}\")"),
+            RuntimeError::NoMatchingClause { value, .. } => {
+                write!(f, "(no-matching-clause {value})")
+            }
+            RuntimeError::IllegalState { message, .. } => {
+                write!(f, "(illegal-state \"{message}\")")
+            }
+            RuntimeError::NoMethod { name, value, .. } => {
+                write!(f, "(no-method {name} {value})")
+            }
+            RuntimeError::AmbiguousMethod { name, value, .. } => {
+                write!(f, "(ambiguous-method {name} {value})")
+            }
+            RuntimeError::NoSuchField {
+                field, type_name, ..
+            } => write!(f, "(no-such-field {field} {type_name})"),
+            RuntimeError::InvalidRegex { message, .. } => {
+                write!(f, "(invalid-regex\n  {message})")
+            }
         }
     }
 }
@@ -193,10 +464,16 @@ impl std::fmt::Display for Value {
             Value::Nil => write!(f, "nil"),
             Value::Bool(b) => write!(f, "{b}"),
             Value::Long(n) => write!(f, "{n}"),
+            Value::BigInt(n) => write!(f, "{n}"),
+            Value::Ratio(r) => write!(f, "{r}"),
             Value::Double(n) => write!(f, "{n}"),
+            Value::Char(c) => write!(f, "{c}"),
             Value::String(s) => write!(f, "{s}"),
+            Value::Regex(re) => write!(f, "{}", re.as_str()),
+            Value::Inst(i) => write!(f, "{i}"),
             Value::Keyword(s) => write!(f, ":{s}"),
             Value::List(v) => write!(f, "{v}"),
+            Value::Queue(q) => write!(f, "{q}"),
             Value::Vector(v) => {
                 write!(f, "[")?;
                 for (i, e) in v.iter().enumerate() {
@@ -207,9 +484,9 @@ impl std::fmt::Display for Value {
                 }
                 write!(f, "]")
             }
-            Value::Map(m) => {
+            Value::Map(_) | Value::SortedMap(_) => {
                 write!(f, "{{")?;
-                for (i, (k, v)) in m.iter().enumerate() {
+                for (i, (k, v)) in map_entries(self).into_iter().enumerate() {
                     if i > 0 {
                         write!(f, ", ")?;
                     }
@@ -217,9 +494,9 @@ impl std::fmt::Display for Value {
                 }
                 write!(f, "}}")
             }
-            Value::Set(v) => {
+            Value::Set(_) | Value::SortedSet(_) => {
                 write!(f, "#{{")?;
-                for (i, e) in v.iter().enumerate() {
+                for (i, e) in set_items(self).into_iter().enumerate() {
                     if i > 0 {
                         write!(f, " ")?;
                     }
@@ -229,6 +506,26 @@ impl std::fmt::Display for Value {
             }
             Value::Symbol(s) => write!(f, "{s}"),
             Value::Callable(c) => write!(f, "{c}"),
+            Value::Stream(s) => write!(f, "#<stream {}>", s.name),
+            Value::Reduced(v) => write!(f, "#<reduced {v}>"),
+            Value::Volatile(v) => write!(f, "#<volatile {}>", v.borrow()),
+            Value::Transient(t) => match &*t.borrow() {
+                Some(t) => write!(f, "#<{}>", t.type_name()),
+                None => write!(f, "#<transient>"),
+            },
+            Value::Record(r) if r.rtype.is_record => {
+                write!(f, "#{}{{", r.rtype.name)?;
+                for (i, (k, v)) in r.entries().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{k} {v}")?;
+                }
+                write!(f, "}}")
+            }
+            Value::Record(r) => write!(f, "#<{}>", r.rtype.name),
+            Value::Type(t) => write!(f, "{}", t.name()),
+            Value::Protocol(p) => write!(f, "#<protocol {}>", p.name),
         }
     }
 }
@@ -239,15 +536,31 @@ impl Value {
             Value::Nil => "nil",
             Value::Bool(_) => "bool",
             Value::Long(_) => "long",
+            Value::BigInt(_) => "bigint",
+            Value::Ratio(_) => "ratio",
             Value::Double(_) => "double",
+            Value::Char(_) => "char",
             Value::String(_) => "string",
+            Value::Regex(_) => "regex",
+            Value::Inst(_) => "inst",
             Value::Keyword(_) => "keyword",
             Value::List(_) => "list",
+            Value::Queue(_) => "queue",
             Value::Vector(_) => "vector",
             Value::Map(_) => "map",
             Value::Set(_) => "set",
+            Value::SortedMap(_) => "sorted-map",
+            Value::SortedSet(_) => "sorted-set",
             Value::Symbol(_) => "symbol",
             Value::Callable(_) => "callable",
+            Value::Stream(_) => "stream",
+            Value::Reduced(_) => "reduced",
+            Value::Volatile(_) => "volatile",
+            Value::Transient(_) => "transient",
+            Value::Record(r) if r.rtype.is_record => "record",
+            Value::Record(_) => "object",
+            Value::Type(_) => "type",
+            Value::Protocol(_) => "protocol",
         }
     }
 
@@ -255,6 +568,18 @@ impl Value {
         Value::Callable(Rc::new(Callable::Builtin { name, func }))
     }
 
+    pub fn new_native(name: &'static str, func: NativeFn) -> Value {
+        Value::Callable(Rc::new(Callable::Native { name, func }))
+    }
+
+    /// What protocol dispatch and `instance?` look at
+    pub fn type_tag(&self) -> TypeTag {
+        match self {
+            Value::Record(r) => TypeTag::Record(r.rtype.clone()),
+            v => TypeTag::Builtin(v.type_name()),
+        }
+    }
+
     pub fn is_truthy(&self) -> bool {
         !matches!(self, Value::Nil | Value::Bool(false))
     }
@@ -1,3 +1,4 @@
+mod number;
 #[cfg(test)]
 mod test_lexer;
 #[cfg(test)]
@@ -5,6 +6,27 @@ mod test_token;
 mod token;
 pub use token::{Content, Span, Token};
 
+use number::{looks_numeric, read_number};
+
+#[derive(Debug)]
+pub enum LexError {
+    InvalidNumber { literal: String, span: Span },
+    InvalidChar { literal: String, span: Span },
+}
+
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LexError::InvalidNumber { literal, span } => {
+                write!(f, "(invalid-number :literal \"{literal}\" :at {})", span.lo)
+            }
+            LexError::InvalidChar { literal, span } => {
+                write!(f, "(invalid-char :literal \"{literal}\" :at {})", span.lo)
+            }
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct Lexer {
     tokens: Vec<Token>,
@@ -12,13 +34,18 @@ pub struct Lexer {
     buffer_lo: u32,
     in_comment: bool,
     in_string: bool,
+    // A string opened right after `#`: escapes are kept verbatim for the regex
+    in_regex: bool,
     escape_next: bool,
+    // Set right after a `\` starting a char literal: the next char is taken
+    // verbatim, even whitespace or a delimiter (`\ `, `\(`)
+    in_char: bool,
 }
 
 type DelimiterVariant = fn(Content<()>) -> Token;
 
 impl Lexer {
-    pub fn tokenize(program: &str) -> Vec<Token> {
+    pub fn tokenize(program: &str) -> Result<Vec<Token>, LexError> {
         let mut lexer = Lexer::default();
 
         for (ch_offset, ch) in program.char_indices() {
@@ -29,6 +56,12 @@ impl Lexer {
                 continue;
             }
             if lexer.in_string {
+                if lexer.escape_next && lexer.in_regex {
+                    lexer.escape_next = false;
+                    lexer.push_to_buffer('\\', ch_offset - 1);
+                    lexer.push_to_buffer(ch, ch_offset);
+                    continue;
+                }
                 if lexer.escape_next {
                     lexer.escape_next = false;
                     let escaped = match ch {
@@ -52,42 +85,64 @@ impl Lexer {
                 }
             }
 
+            if lexer.in_char {
+                lexer.in_char = false;
+                lexer.push_to_buffer(ch, ch_offset);
+                continue;
+            }
+
             match ch {
+                '\\' if lexer.buffer.is_empty() || lexer.buffer.starts_with('\\') => {
+                    lexer.flush_buffer(ch_offset)?;
+                    lexer.push_to_buffer(ch, ch_offset);
+                    lexer.in_char = true;
+                }
                 '(' => {
-                    lexer.push_delimiter(Token::LParen, ch_offset);
+                    lexer.push_delimiter(Token::LParen, ch_offset)?;
                 }
                 ')' => {
-                    lexer.push_delimiter(Token::RParen, ch_offset);
+                    lexer.push_delimiter(Token::RParen, ch_offset)?;
                 }
                 '{' => {
-                    lexer.push_delimiter(Token::LBrace, ch_offset);
+                    lexer.push_delimiter(Token::LBrace, ch_offset)?;
                 }
                 '}' => {
-                    lexer.push_delimiter(Token::RBrace, ch_offset);
+                    lexer.push_delimiter(Token::RBrace, ch_offset)?;
                 }
                 '[' => {
-                    lexer.push_delimiter(Token::LBracket, ch_offset);
+                    lexer.push_delimiter(Token::LBracket, ch_offset)?;
                 }
                 ']' => {
-                    lexer.push_delimiter(Token::RBracket, ch_offset);
+                    lexer.push_delimiter(Token::RBracket, ch_offset)?;
+                }
+                '#' if lexer.buffer.is_empty() && lexer.follows_hash(ch_offset) => {
+                    lexer.tokens.pop();
+                    lexer.push_to_buffer('#', ch_offset - 1);
+                    lexer.push_to_buffer('#', ch_offset);
                 }
                 '#' => {
-                    lexer.push_delimiter(Token::Hash, ch_offset);
+                    lexer.push_delimiter(Token::Hash, ch_offset)?;
                 }
-                '\'' => {
-                    lexer.push_delimiter(Token::Quote, ch_offset);
+                '\'' if lexer.buffer.is_empty() => {
+                    lexer.push_delimiter(Token::Quote, ch_offset)?;
                 }
                 ' ' | '\t' | '\n' | '\r' => {
-                    lexer.flush_buffer(ch_offset);
+                    lexer.flush_buffer(ch_offset)?;
                 }
                 ';' => {
-                    lexer.flush_buffer(ch_offset);
+                    lexer.flush_buffer(ch_offset)?;
                     lexer.in_comment = true;
                 }
                 '"' => {
                     if lexer.in_string {
-                        lexer.flush_buffer(ch_offset + 1);
+                        lexer.flush_buffer(ch_offset + 1)?;
                         lexer.in_string = false;
+                        lexer.in_regex = false;
+                    } else if lexer.buffer.is_empty() && lexer.follows_hash(ch_offset) {
+                        lexer.tokens.pop();
+                        lexer.push_to_buffer('#', ch_offset - 1);
+                        lexer.in_string = true;
+                        lexer.in_regex = true;
                     } else {
                         lexer.push_to_buffer(ch, ch_offset);
                         lexer.in_string = true;
@@ -98,52 +153,85 @@ impl Lexer {
                 }
             }
         }
-        lexer.flush_buffer(program.len());
-        lexer.tokens
+        lexer.flush_buffer(program.len())?;
+        Ok(lexer.tokens)
     }
 
-    fn push_delimiter(&mut self, variant: DelimiterVariant, ch_offset: usize) {
-        self.flush_buffer(ch_offset);
+    fn push_delimiter(
+        &mut self,
+        variant: DelimiterVariant,
+        ch_offset: usize,
+    ) -> Result<(), LexError> {
+        self.flush_buffer(ch_offset)?;
         self.push_token(variant(Content::new((), Span::at(ch_offset))));
+        Ok(())
     }
 
-    fn flush_buffer(&mut self, hi: usize) {
+    fn flush_buffer(&mut self, hi: usize) -> Result<(), LexError> {
         if self.buffer.is_empty() {
-            return;
+            return Ok(());
         }
         let span = Span {
             lo: self.buffer_lo,
             hi: hi as u32,
         };
-        let token = self.classify_buffer(span);
+        let token = self.classify_buffer(span)?;
 
         self.push_token(token);
         self.buffer.clear();
+        Ok(())
+    }
+
+    fn follows_hash(&self, ch_offset: usize) -> bool {
+        matches!(self.tokens.last(), Some(Token::Hash(c)) if c.span.hi as usize == ch_offset)
     }
 
-    fn classify_buffer(&self, span: Span) -> Token {
+    fn classify_buffer(&self, span: Span) -> Result<Token, LexError> {
         None.or_else(|| {
-            self.in_string
-                .then(|| Token::String(Content::new(self.buffer[1..].to_string(), span)))
+            (self.in_string && self.in_regex).then(|| {
+                Ok(Token::Regex(Content::new(
+                    self.buffer[1..].to_string(),
+                    span,
+                )))
+            })
         })
         .or_else(|| {
-            self.buffer
-                .starts_with(':')
-                .then(|| Token::Keyword(Content::new(self.buffer[1..].to_string(), span)))
+            self.in_string.then(|| {
+                Ok(Token::String(Content::new(
+                    self.buffer[1..].to_string(),
+                    span,
+                )))
+            })
         })
         .or_else(|| {
-            self.buffer
-                .parse::<i64>()
-                .ok()
-                .map(|v| Token::Long(Content::new(v, span)))
+            self.buffer.strip_prefix('\\').map(|name| {
+                parse_char_name(name)
+                    .map(|c| Token::Char(Content::new(c, span)))
+                    .ok_or_else(|| LexError::InvalidChar {
+                        literal: self.buffer.clone(),
+                        span,
+                    })
+            })
         })
         .or_else(|| {
-            self.buffer
-                .parse::<f64>()
-                .ok()
-                .map(|v| Token::Double(Content::new(v, span)))
+            self.buffer.starts_with(':').then(|| {
+                Ok(Token::Keyword(Content::new(
+                    self.buffer[1..].to_string(),
+                    span,
+                )))
+            })
         })
-        .unwrap_or_else(|| Token::Symbol(Content::new(self.buffer.clone(), span)))
+        .or_else(|| {
+            (self.buffer.starts_with("##") || looks_numeric(&self.buffer)).then(|| {
+                read_symbolic(&self.buffer, span)
+                    .or_else(|| read_number(&self.buffer, span))
+                    .ok_or_else(|| LexError::InvalidNumber {
+                        literal: self.buffer.clone(),
+                        span,
+                    })
+            })
+        })
+        .unwrap_or_else(|| Ok(Token::Symbol(Content::new(self.buffer.clone(), span))))
     }
     fn push_to_buffer(&mut self, ch: char, offset: usize) {
         if self.buffer.is_empty() {
@@ -156,3 +244,35 @@ impl Lexer {
         self.tokens.push(token);
     }
 }
+
+// `a`, `é`, `newline`, `u00e9`... as written after the backslash
+fn parse_char_name(name: &str) -> Option<char> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c);
+    }
+    match name {
+        "newline" => Some('\n'),
+        "space" => Some(' '),
+        "tab" => Some('\t'),
+        "return" => Some('\r'),
+        "backspace" => Some('\u{8}'),
+        "formfeed" => Some('\u{c}'),
+        _ => name
+            .strip_prefix('u')
+            .filter(|hex| hex.len() == 4)
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32),
+    }
+}
+
+// `##Inf`, `##-Inf`, `##NaN`
+fn read_symbolic(s: &str, span: Span) -> Option<Token> {
+    let v = match s {
+        "##Inf" => f64::INFINITY,
+        "##-Inf" => f64::NEG_INFINITY,
+        "##NaN" => f64::NAN,
+        _ => return None,
+    };
+    Some(Token::Double(Content::new(v, span)))
+}
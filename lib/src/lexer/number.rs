@@ -0,0 +1,145 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Num, ToPrimitive, Zero};
+
+use super::{Content, Span, Token};
+
+// Largest power of ten an exact decimal literal may scale by; past this the
+// literal is rejected rather than building an enormous integer
+const MAX_DECIMAL_SCALE: u32 = 4096;
+
+/// Whether `s` has to be read as a number: it starts with a digit, or with a
+/// sign followed by a digit. Anything else is a symbol (`-`, `->`, `+'`).
+pub(super) fn looks_numeric(s: &str) -> bool {
+    let body = s.strip_prefix(['-', '+']).unwrap_or(s);
+    body.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Reads a numeric literal, `None` when it is malformed (`12abc`, `08`, `1__0`).
+///
+/// Integers: `42`, `0xFF`, `0b1010`, `017` (octal), `36rZZ`, with `N` forcing
+/// a bigint. Ratios: `22/7`. Doubles: `1.5`, `2e10`, rejected when too large
+/// for a double rather than read as infinity. There is no decimal type: an
+/// `M` suffix reads the decimal exactly as a ratio or integer, so `1.5M` is
+/// `3/2` and prints as `3/2`, which reads back as the same value. `_` may
+/// separate digits.
+pub(super) fn read_number(s: &str, span: Span) -> Option<Token> {
+    let (negative, body) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let sign = |n: BigInt| if negative { -n } else { n };
+
+    if let Some((numer, denom)) = body.split_once('/') {
+        let denom = read_integer(denom, 10)?;
+        if denom.is_zero() {
+            return None;
+        }
+        let ratio = BigRational::new(sign(read_integer(numer, 10)?), denom);
+        return Some(exact_token(ratio, span));
+    }
+    if let Some(digits) = body.strip_suffix('N') {
+        let n = sign(read_radix_integer(digits)?);
+        return Some(Token::BigInt(Content::new(n, span)));
+    }
+    if let Some(decimal) = body.strip_suffix('M') {
+        let exact = read_exact_decimal(decimal)?;
+        let exact = if negative { -exact } else { exact };
+        return Some(exact_token(exact, span));
+    }
+    if let Some(n) = read_radix_integer(body) {
+        return Some(exact_token(BigRational::from_integer(sign(n)), span));
+    }
+
+    let double = read_double(body).filter(|d| d.is_finite())?;
+    Some(Token::Double(Content::new(
+        if negative { -double } else { double },
+        span,
+    )))
+}
+
+// A literal that is a whole number (`4/2`, `3M`) reads as a long, or as a
+// bigint when it does not fit
+fn exact_token(r: BigRational, span: Span) -> Token {
+    if !r.is_integer() {
+        return Token::Ratio(Content::new(r, span));
+    }
+    let n = r.to_integer();
+    match n.to_i64() {
+        Some(v) => Token::Long(Content::new(v, span)),
+        None => Token::BigInt(Content::new(n, span)),
+    }
+}
+
+// Every `_` has to sit between two digits
+fn strip_separators(s: &str, is_digit: impl Fn(char) -> bool) -> Option<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let separated = chars.iter().enumerate().all(|(i, c)| {
+        *c != '_'
+            || (i > 0 && i + 1 < chars.len() && is_digit(chars[i - 1]) && is_digit(chars[i + 1]))
+    });
+    separated.then(|| s.replace('_', ""))
+}
+
+fn read_integer(s: &str, radix: u32) -> Option<BigInt> {
+    let digits = strip_separators(s, |c| c.is_digit(radix))?;
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    BigInt::from_str_radix(&digits, radix).ok()
+}
+
+fn read_radix_integer(s: &str) -> Option<BigInt> {
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        return read_integer(hex, 16);
+    }
+    if let Some(bin) = s.strip_prefix("0b").or_else(|| s.strip_prefix("0B")) {
+        return read_integer(bin, 2);
+    }
+    if let Some((radix, digits)) = s.split_once(['r', 'R']) {
+        let radix: u32 = radix.parse().ok().filter(|r| (2..=36).contains(r))?;
+        return read_integer(digits, radix);
+    }
+    if let Some(octal) = s.strip_prefix('0').filter(|rest| !rest.is_empty()) {
+        return read_integer(octal, 8);
+    }
+    read_integer(s, 10)
+}
+
+// Digits with a fraction and/or an exponent; a bare `08` is not a double
+fn read_double(s: &str) -> Option<f64> {
+    if !s.contains(['.', 'e', 'E']) {
+        return None;
+    }
+    let plain = strip_separators(s, |c| c.is_ascii_digit())?;
+    if !plain
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'))
+    {
+        return None;
+    }
+    plain.parse().ok()
+}
+
+fn read_exact_decimal(s: &str) -> Option<BigRational> {
+    if read_double(s).is_none() && read_integer(s, 10).is_none() {
+        return None;
+    }
+    let plain = s.replace('_', "");
+    let (mantissa, exponent) = match plain.split_once(['e', 'E']) {
+        Some((m, e)) => (m, e.parse::<i32>().ok()?),
+        None => (plain.as_str(), 0),
+    };
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let numer: BigInt = format!("{int_part}{frac_part}").parse().ok()?;
+    let scale = exponent.checked_sub(i32::try_from(frac_part.len()).ok()?)?;
+    if scale.unsigned_abs() > MAX_DECIMAL_SCALE {
+        return None;
+    }
+    let factor = BigRational::from_integer(num_traits::pow(
+        BigInt::from(10),
+        scale.unsigned_abs() as usize,
+    ));
+    let n = BigRational::from_integer(numer);
+    Some(if scale < 0 { n / factor } else { n * factor })
+}
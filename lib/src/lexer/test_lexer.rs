@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
-    use crate::lexer::{Content, Lexer, Span, Token};
+    use num_rational::BigRational;
+
+    use crate::lexer::{Content, LexError, Lexer, Span, Token};
 
     fn span(lo: u32, hi: u32) -> Span {
         Span { lo, hi }
@@ -8,7 +10,7 @@ mod tests {
 
     #[test]
     fn tokenizes_arithmetic_expression() {
-        let tokens = Lexer::tokenize("(+ 1 2)");
+        let tokens = Lexer::tokenize("(+ 1 2)").unwrap();
         assert_eq!(
             tokens,
             vec![
@@ -23,7 +25,7 @@ mod tests {
 
     #[test]
     fn tokenizes_consecutive_delimiters() {
-        let tokens = Lexer::tokenize("(let((x 10)))");
+        let tokens = Lexer::tokenize("(let((x 10)))").unwrap();
         assert_eq!(
             tokens,
             vec![
@@ -42,7 +44,7 @@ mod tests {
 
     #[test]
     fn tokenizes_multiline_program() {
-        let tokens = Lexer::tokenize("(foo\n bar)");
+        let tokens = Lexer::tokenize("(foo\n bar)").unwrap();
         assert_eq!(
             tokens,
             vec![
@@ -56,7 +58,7 @@ mod tests {
 
     #[test]
     fn tokenizes_multiline_program_with_comment() {
-        let tokens = Lexer::tokenize("(foo)\n(bar);(bla)");
+        let tokens = Lexer::tokenize("(foo)\n(bar);(bla)").unwrap();
         assert_eq!(
             tokens,
             vec![
@@ -72,7 +74,7 @@ mod tests {
 
     #[test]
     fn tokenizes_multiline_program_starting_with_comment() {
-        let tokens = Lexer::tokenize(";1234567\n(foo)");
+        let tokens = Lexer::tokenize(";1234567\n(foo)").unwrap();
         assert_eq!(
             tokens,
             vec![
@@ -85,7 +87,7 @@ mod tests {
 
     #[test]
     fn tokenizes_string_literal() {
-        let tokens = Lexer::tokenize("\"hello world\"");
+        let tokens = Lexer::tokenize("\"hello world\"").unwrap();
         assert_eq!(
             tokens,
             vec![Token::String(Content::new(
@@ -97,7 +99,7 @@ mod tests {
 
     #[test]
     fn tokenizes_string_in_expression() {
-        let tokens = Lexer::tokenize("(println \"hi\")");
+        let tokens = Lexer::tokenize("(println \"hi\")").unwrap();
         assert_eq!(
             tokens,
             vec![
@@ -111,7 +113,7 @@ mod tests {
 
     #[test]
     fn tokenizes_keyword() {
-        let tokens = Lexer::tokenize(":foo");
+        let tokens = Lexer::tokenize(":foo").unwrap();
         assert_eq!(
             tokens,
             vec![Token::Keyword(Content::new("foo".to_string(), span(0, 4)))]
@@ -120,7 +122,7 @@ mod tests {
 
     #[test]
     fn tokenizes_keyword_in_expression() {
-        let tokens = Lexer::tokenize("(assoc m :key 1)");
+        let tokens = Lexer::tokenize("(assoc m :key 1)").unwrap();
         assert_eq!(
             tokens,
             vec![
@@ -136,7 +138,7 @@ mod tests {
 
     #[test]
     fn tokenizes_vector_literal() {
-        let tokens = Lexer::tokenize("[1 2 3]");
+        let tokens = Lexer::tokenize("[1 2 3]").unwrap();
         assert_eq!(
             tokens,
             vec![
@@ -151,7 +153,7 @@ mod tests {
 
     #[test]
     fn tokenizes_map_literal() {
-        let tokens = Lexer::tokenize("{:a 1}");
+        let tokens = Lexer::tokenize("{:a 1}").unwrap();
         assert_eq!(
             tokens,
             vec![
@@ -163,15 +165,17 @@ mod tests {
         );
     }
 
+    // 3.14 is the literal under test, not an approximation of PI
+    #[allow(clippy::approx_constant)]
     #[test]
     fn tokenizes_double_literal() {
-        let tokens = Lexer::tokenize("3.14");
+        let tokens = Lexer::tokenize("3.14").unwrap();
         assert_eq!(tokens, vec![Token::Double(Content::new(3.14, span(0, 4)))]);
     }
 
     #[test]
     fn tokenizes_double_in_expression() {
-        let tokens = Lexer::tokenize("(+ 1 2.5)");
+        let tokens = Lexer::tokenize("(+ 1 2.5)").unwrap();
         assert_eq!(
             tokens,
             vec![
@@ -186,17 +190,17 @@ mod tests {
 
     #[test]
     fn empty_input_produces_no_tokens() {
-        assert_eq!(Lexer::tokenize(""), vec![]);
+        assert_eq!(Lexer::tokenize("").unwrap(), vec![]);
     }
 
     #[test]
     fn only_whitespace_produces_no_tokens() {
-        assert_eq!(Lexer::tokenize("   \t\n  "), vec![]);
+        assert_eq!(Lexer::tokenize("   \t\n  ").unwrap(), vec![]);
     }
 
     #[test]
     fn string_escape_newline() {
-        let tokens = Lexer::tokenize(r#""\n""#);
+        let tokens = Lexer::tokenize(r#""\n""#).unwrap();
         assert_eq!(
             tokens,
             vec![Token::String(Content::new("\n".to_string(), span(0, 4)))]
@@ -205,7 +209,7 @@ mod tests {
 
     #[test]
     fn string_escape_tab() {
-        let tokens = Lexer::tokenize(r#""\t""#);
+        let tokens = Lexer::tokenize(r#""\t""#).unwrap();
         assert_eq!(
             tokens,
             vec![Token::String(Content::new("\t".to_string(), span(0, 4)))]
@@ -214,7 +218,7 @@ mod tests {
 
     #[test]
     fn string_escape_carriage_return() {
-        let tokens = Lexer::tokenize(r#""\r""#);
+        let tokens = Lexer::tokenize(r#""\r""#).unwrap();
         assert_eq!(
             tokens,
             vec![Token::String(Content::new("\r".to_string(), span(0, 4)))]
@@ -223,7 +227,7 @@ mod tests {
 
     #[test]
     fn string_escape_backslash() {
-        let tokens = Lexer::tokenize(r#""\\""#);
+        let tokens = Lexer::tokenize(r#""\\""#).unwrap();
         assert_eq!(
             tokens,
             vec![Token::String(Content::new("\\".to_string(), span(0, 4)))]
@@ -232,7 +236,7 @@ mod tests {
 
     #[test]
     fn string_escape_double_quote() {
-        let tokens = Lexer::tokenize(r#""\"""#);
+        let tokens = Lexer::tokenize(r#""\"""#).unwrap();
         assert_eq!(
             tokens,
             vec![Token::String(Content::new("\"".to_string(), span(0, 4)))]
@@ -241,7 +245,7 @@ mod tests {
 
     #[test]
     fn string_escape_in_middle() {
-        let tokens = Lexer::tokenize(r#""a\nb""#);
+        let tokens = Lexer::tokenize(r#""a\nb""#).unwrap();
         assert_eq!(
             tokens,
             vec![Token::String(Content::new("a\nb".to_string(), span(0, 6)))]
@@ -250,10 +254,273 @@ mod tests {
 
     #[test]
     fn string_unknown_escape_passes_through() {
-        let tokens = Lexer::tokenize(r#""\x""#);
+        let tokens = Lexer::tokenize(r#""\x""#).unwrap();
         assert_eq!(
             tokens,
             vec![Token::String(Content::new("x".to_string(), span(0, 4)))]
         );
     }
+
+    #[test]
+    fn char_literal() {
+        let tokens = Lexer::tokenize(r"\a").unwrap();
+        assert_eq!(tokens, vec![Token::Char(Content::new('a', span(0, 2)))]);
+    }
+
+    #[test]
+    fn char_literal_named() {
+        let tokens = Lexer::tokenize(r"\newline \space \tab").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Char(Content::new('\n', span(0, 8))),
+                Token::Char(Content::new(' ', span(9, 15))),
+                Token::Char(Content::new('\t', span(16, 20))),
+            ]
+        );
+    }
+
+    #[test]
+    fn char_literal_unicode() {
+        let tokens = Lexer::tokenize(r"\u00e9 \é").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Char(Content::new('é', span(0, 6))),
+                Token::Char(Content::new('é', span(7, 10))),
+            ]
+        );
+    }
+
+    #[test]
+    fn char_literal_delimiters() {
+        let tokens = Lexer::tokenize(r"[\a\(\ ]").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LBracket(Content::new((), span(0, 1))),
+                Token::Char(Content::new('a', span(1, 3))),
+                Token::Char(Content::new('(', span(3, 5))),
+                Token::Char(Content::new(' ', span(5, 7))),
+                Token::RBracket(Content::new((), span(7, 8))),
+            ]
+        );
+    }
+
+    #[test]
+    fn regex_literal_keeps_escapes() {
+        let tokens = Lexer::tokenize(r#"#"\d+\"""#).unwrap();
+        assert_eq!(
+            tokens,
+            vec![Token::Regex(Content::new(
+                r#"\d+\""#.to_string(),
+                span(0, 8)
+            ))]
+        );
+    }
+
+    #[test]
+    fn hash_before_brace_is_still_a_set() {
+        let tokens = Lexer::tokenize(r#"#{"a"}"#).unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Hash(Content::new((), span(0, 1))),
+                Token::LBrace(Content::new((), span(1, 2))),
+                Token::String(Content::new("a".to_string(), span(2, 5))),
+                Token::RBrace(Content::new((), span(5, 6))),
+            ]
+        );
+    }
+
+    #[test]
+    fn bigint_literals() {
+        let tokens = Lexer::tokenize("12N 99999999999999999999").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::BigInt(Content::new(12.into(), span(0, 3))),
+                Token::BigInt(Content::new(
+                    "99999999999999999999".parse().unwrap(),
+                    span(4, 24)
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn quote_inside_symbol_is_part_of_it() {
+        let tokens = Lexer::tokenize("(+' 'a)").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LParen(Content::new((), span(0, 1))),
+                Token::Symbol(Content::new("+'".to_string(), span(1, 3))),
+                Token::Quote(Content::new((), span(4, 5))),
+                Token::Symbol(Content::new("a".to_string(), span(5, 6))),
+                Token::RParen(Content::new((), span(6, 7))),
+            ]
+        );
+    }
+
+    #[test]
+    fn ratio_literals() {
+        let tokens = Lexer::tokenize("22/7 4/2 a/b /").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Ratio(Content::new(
+                    BigRational::new(22.into(), 7.into()),
+                    span(0, 4)
+                )),
+                Token::Long(Content::new(2, span(5, 8))),
+                Token::Symbol(Content::new("a/b".to_string(), span(9, 12))),
+                Token::Symbol(Content::new("/".to_string(), span(13, 14))),
+            ]
+        );
+    }
+
+    fn single(source: &str) -> Token {
+        let mut tokens = Lexer::tokenize(source).unwrap();
+        assert_eq!(tokens.len(), 1, "{source} should be a single token");
+        tokens.pop().unwrap()
+    }
+
+    fn long(source: &str) -> i64 {
+        match single(source) {
+            Token::Long(c) => c.content,
+            t => panic!("{source} should be a long, got {t:?}"),
+        }
+    }
+
+    fn double(source: &str) -> f64 {
+        match single(source) {
+            Token::Double(c) => c.content,
+            t => panic!("{source} should be a double, got {t:?}"),
+        }
+    }
+
+    #[test]
+    fn radix_integer_literals() {
+        assert_eq!(long("0xFF"), 255);
+        assert_eq!(long("-0x10"), -16);
+        assert_eq!(long("0b1010"), 10);
+        assert_eq!(long("017"), 15);
+        assert_eq!(long("2r1010"), 10);
+        assert_eq!(long("36rZz"), 1295);
+        assert_eq!(long("+7"), 7);
+    }
+
+    #[test]
+    fn digit_separators() {
+        assert_eq!(long("1_000_000"), 1_000_000);
+        assert_eq!(long("0xFF_FF"), 0xFFFF);
+        assert_eq!(double("1_000.5"), 1000.5);
+    }
+
+    #[test]
+    fn double_literals_with_exponent() {
+        assert_eq!(double("1e3"), 1000.0);
+        assert_eq!(double("-1.5e-2"), -0.015);
+        assert_eq!(double("2."), 2.0);
+    }
+
+    #[test]
+    fn symbolic_double_literals() {
+        assert_eq!(double("##Inf"), f64::INFINITY);
+        assert_eq!(double("##-Inf"), f64::NEG_INFINITY);
+        assert!(double("##NaN").is_nan());
+    }
+
+    #[test]
+    fn suffixed_literals() {
+        assert_eq!(
+            single("0xFFN"),
+            Token::BigInt(Content::new(255.into(), span(0, 5)))
+        );
+        assert_eq!(
+            single("1.25M"),
+            Token::Ratio(Content::new(
+                BigRational::new(5.into(), 4.into()),
+                span(0, 5)
+            ))
+        );
+        assert_eq!(long("-3M"), -3);
+        assert_eq!(long("1e2M"), 100);
+    }
+
+    #[test]
+    fn sign_without_digit_is_a_symbol() {
+        for source in ["-", "+", "->", "-x", "+'"] {
+            assert!(matches!(single(source), Token::Symbol(_)), "{source}");
+        }
+    }
+
+    #[test]
+    fn malformed_numbers_are_errors() {
+        for source in [
+            "12abc", "08", "1__0", "1_", "0x", "0xG", "37r1", "2r2", "1/0", "1.2.3", "1e", "##Foo",
+        ] {
+            assert!(
+                matches!(Lexer::tokenize(source), Err(LexError::InvalidNumber { .. })),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn decimal_reads_back_from_its_printed_ratio() {
+        let (Token::Ratio(decimal), Token::Ratio(ratio)) = (single("1.5M"), single("3/2")) else {
+            panic!("1.5M and 3/2 should both be ratios");
+        };
+        assert_eq!(decimal.content, ratio.content);
+        assert_eq!(decimal.content.to_string(), "3/2");
+    }
+
+    #[test]
+    fn overflowing_doubles_are_errors() {
+        for source in ["1e400", "-1e400", "1.5e309"] {
+            assert!(
+                matches!(Lexer::tokenize(source), Err(LexError::InvalidNumber { .. })),
+                "{source}"
+            );
+        }
+        assert_eq!(double("1e-400"), 0.0);
+        // Exact decimals have no such limit
+        assert!(matches!(single("1e400M"), Token::BigInt(_)));
+    }
+
+    #[test]
+    fn huge_decimal_exponents_are_errors() {
+        for source in [
+            "1.5e-2147483648M",
+            "1e-2147483647M",
+            "15e-2147483648M",
+            "1e5000M",
+        ] {
+            assert!(
+                matches!(Lexer::tokenize(source), Err(LexError::InvalidNumber { .. })),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_number_error_span() {
+        match Lexer::tokenize("(+ 1 12abc)") {
+            Err(LexError::InvalidNumber { literal, span: s }) => {
+                assert_eq!(literal, "12abc");
+                assert_eq!(s, span(5, 10));
+            }
+            r => panic!("unexpected {r:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_char_name_is_an_error() {
+        assert!(matches!(
+            Lexer::tokenize(r"\foo"),
+            Err(LexError::InvalidChar { .. })
+        ));
+    }
 }
@@ -9,7 +9,7 @@ mod tests {
     #[test]
     fn span_covers_token_at_end_of_input() {
         use crate::lexer::Lexer;
-        let tokens = Lexer::tokenize("hello");
+        let tokens = Lexer::tokenize("hello").unwrap();
         assert_eq!(tokens.len(), 1);
         let s = match &tokens[0] {
             Token::Symbol(c) => &c.span,
@@ -22,7 +22,7 @@ mod tests {
     #[test]
     fn span_of_integer_is_exact() {
         use crate::lexer::Lexer;
-        let tokens = Lexer::tokenize(" 314 ");
+        let tokens = Lexer::tokenize(" 314 ").unwrap();
         assert_eq!(tokens.len(), 1);
         let s = match &tokens[0] {
             Token::Long(c) => &c.span,
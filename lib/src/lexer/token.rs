@@ -1,3 +1,5 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
 use std::fmt::{Debug, Display};
 
 #[derive(Clone, Debug, Copy, PartialEq)]
@@ -68,9 +70,13 @@ impl<T> Content<T> {
 #[derive(PartialEq, Clone)]
 pub enum Token {
     Long(Content<i64>),
+    BigInt(Content<BigInt>),
+    Ratio(Content<BigRational>),
     Double(Content<f64>),
+    Char(Content<char>),
     Symbol(Content<String>),
     String(Content<String>),
+    Regex(Content<String>),
     Keyword(Content<String>),
     LParen(Content<()>),
     RParen(Content<()>),
@@ -92,6 +98,20 @@ impl Display for Token {
                 hi = c.span.hi,
                 value = c.content
             ),
+            Token::BigInt(c) => write!(
+                f,
+                "{lo}..{hi} BigInt({value}N)",
+                lo = c.span.lo,
+                hi = c.span.hi,
+                value = c.content
+            ),
+            Token::Ratio(c) => write!(
+                f,
+                "{lo}..{hi} Ratio({value})",
+                lo = c.span.lo,
+                hi = c.span.hi,
+                value = c.content
+            ),
             Token::Double(c) => write!(
                 f,
                 "{lo}..{hi} Double({value})",
@@ -99,6 +119,13 @@ impl Display for Token {
                 hi = c.span.hi,
                 value = c.content
             ),
+            Token::Char(c) => write!(
+                f,
+                "{lo}..{hi} Char({value:?})",
+                lo = c.span.lo,
+                hi = c.span.hi,
+                value = c.content
+            ),
             Token::Symbol(c) => write!(
                 f,
                 "{lo}..{hi} Symbol({value})",
@@ -122,6 +149,13 @@ impl Display for Token {
                 hi = c.span.hi,
                 value = c.content
             ),
+            Token::Regex(c) => write!(
+                f,
+                "{lo}..{hi} Regex({value})",
+                lo = c.span.lo,
+                hi = c.span.hi,
+                value = c.content
+            ),
 
             Token::LBracket(c) => write!(f, "{lo}..{hi} LBracket", lo = c.span.lo, hi = c.span.hi),
             Token::RBracket(c) => write!(f, "{lo}..{hi} RBracket", lo = c.span.lo, hi = c.span.hi),
@@ -143,6 +177,20 @@ impl Debug for Token {
                 hi = c.span.hi,
                 value = c.content
             ),
+            Token::BigInt(c) => write!(
+                f,
+                "{lo}..{hi} BigInt({value}N)",
+                lo = c.span.lo,
+                hi = c.span.hi,
+                value = c.content
+            ),
+            Token::Ratio(c) => write!(
+                f,
+                "{lo}..{hi} Ratio({value})",
+                lo = c.span.lo,
+                hi = c.span.hi,
+                value = c.content
+            ),
             Token::Double(c) => write!(
                 f,
                 "{lo}..{hi} Double({value})",
@@ -150,6 +198,13 @@ impl Debug for Token {
                 hi = c.span.hi,
                 value = c.content
             ),
+            Token::Char(c) => write!(
+                f,
+                "{lo}..{hi} Char({value:?})",
+                lo = c.span.lo,
+                hi = c.span.hi,
+                value = c.content
+            ),
             Token::Symbol(c) => write!(
                 f,
                 "{lo}..{hi} Symbol({value})",
@@ -173,6 +228,13 @@ impl Debug for Token {
                 hi = c.span.hi,
                 value = c.content
             ),
+            Token::Regex(c) => write!(
+                f,
+                "{lo}..{hi} Regex({value})",
+                lo = c.span.lo,
+                hi = c.span.hi,
+                value = c.content
+            ),
             Token::LBracket(c) => write!(f, "{lo}..{hi} LBracket", lo = c.span.lo, hi = c.span.hi),
             Token::RBracket(c) => write!(f, "{lo}..{hi} RBracket", lo = c.span.lo, hi = c.span.hi),
             Token::LBrace(c) => write!(f, "{lo}..{hi} LBrace", lo = c.span.lo, hi = c.span.hi),
@@ -1,8 +1,11 @@
 mod collections;
+mod inst;
 mod interpreter;
 mod lexer;
 mod parser;
+mod pattern;
 mod sema;
 
 pub use interpreter::Env;
 pub use interpreter::Interpreter;
+pub use interpreter::Readable;
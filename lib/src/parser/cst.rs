@@ -1,12 +1,19 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
 use crate::lexer::Span;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ExprKind {
     Long(i64),
+    BigInt(BigInt),
+    Ratio(BigRational),
     Double(f64),
+    Char(char),
     Bool(bool),
     Nil,
     String(String),
+    Regex(String),
     Keyword(String),
 
     Symbol(String),
@@ -17,6 +24,8 @@ pub enum ExprKind {
     Map(Vec<(Expr, Expr)>),
     Set(Vec<Expr>),
     Quote(Box<Expr>),
+    // Reader literal such as `#inst "2026-01-01"`
+    Tagged { tag: String, form: Box<Expr> },
 }
 
 #[derive(Debug, Clone)]
@@ -49,6 +49,8 @@ pub enum Frame {
     Map(Vec<Expr>, Span),
     Set(Vec<Expr>, Span),
     Quote(Span),
+    // `#tag` waiting for the form it applies to
+    Tag(String, Span),
 }
 
 #[derive(Debug)]
@@ -105,9 +107,17 @@ impl Parser {
                     self.stack.push(Frame::Quote(c.span));
                 }
                 Token::Long(c) => self.push_to_frame(ExprKind::Long(c.content), c.span)?,
+                Token::BigInt(c) => self.push_to_frame(ExprKind::BigInt(c.content), c.span)?,
+                Token::Ratio(c) => self.push_to_frame(ExprKind::Ratio(c.content), c.span)?,
                 Token::Double(c) => self.push_to_frame(ExprKind::Double(c.content), c.span)?,
+                Token::Char(c) => self.push_to_frame(ExprKind::Char(c.content), c.span)?,
+                Token::Symbol(c) if self.pending_hash => {
+                    self.pending_hash = false;
+                    self.stack.push(Frame::Tag(c.content, c.span));
+                }
                 Token::Symbol(c) => self.parse_symbol(c)?,
                 Token::String(c) => self.push_to_frame(ExprKind::String(c.content), c.span)?,
+                Token::Regex(c) => self.push_to_frame(ExprKind::Regex(c.content), c.span)?,
                 Token::Keyword(c) => self.push_to_frame(ExprKind::Keyword(c.content), c.span)?,
             }
         }
@@ -115,7 +125,7 @@ impl Parser {
         if let Some(frame) = self.stack.last() {
             let span = match frame {
                 Frame::List(_, s) | Frame::Vector(_, s) | Frame::Map(_, s) | Frame::Set(_, s) => s,
-                Frame::Quote(s) => s,
+                Frame::Quote(s) | Frame::Tag(_, s) => s,
             };
             return Err(ParseError::UnmatchedOpen(*span));
         }
@@ -167,13 +177,26 @@ impl Parser {
             };
             return self.push_expr(quoted);
         }
+        if matches!(self.stack.last(), Some(Frame::Tag(..))) {
+            let Some(Frame::Tag(tag, tag_span)) = self.stack.pop() else {
+                unreachable!()
+            };
+            let tagged = Expr {
+                span: tag_span.full(expr.span),
+                kind: ExprKind::Tagged {
+                    tag,
+                    form: Box::new(expr),
+                },
+            };
+            return self.push_expr(tagged);
+        }
 
         match self.stack.last_mut() {
             Some(Frame::List(elems, _)) => elems.push(expr),
             Some(Frame::Vector(elems, _)) => elems.push(expr),
             Some(Frame::Map(elems, _)) => elems.push(expr),
             Some(Frame::Set(elems, _)) => elems.push(expr),
-            Some(Frame::Quote(_)) => unreachable!(),
+            Some(Frame::Quote(_) | Frame::Tag(..)) => unreachable!(),
             None => self.result.push(expr),
         }
         Ok(())
@@ -199,7 +222,7 @@ impl Parser {
                 found: ')',
                 span,
             }),
-            Some(Frame::Quote(span)) => Err(ParseError::UnmatchedOpen(span)),
+            Some(Frame::Quote(span) | Frame::Tag(_, span)) => Err(ParseError::UnmatchedOpen(span)),
             None => Err(ParseError::UnmatchedClose(')', current_span)),
         }
     }
@@ -224,7 +247,7 @@ impl Parser {
                 found: ']',
                 span,
             }),
-            Some(Frame::Quote(span)) => Err(ParseError::UnmatchedOpen(span)),
+            Some(Frame::Quote(span) | Frame::Tag(_, span)) => Err(ParseError::UnmatchedOpen(span)),
             None => Err(ParseError::UnmatchedClose(']', current_span)),
         }
     }
@@ -255,7 +278,7 @@ impl Parser {
                 found: '}',
                 span,
             }),
-            Some(Frame::Quote(span)) => Err(ParseError::UnmatchedOpen(span)),
+            Some(Frame::Quote(span) | Frame::Tag(_, span)) => Err(ParseError::UnmatchedOpen(span)),
             None => Err(ParseError::UnmatchedClose('}', current_span)),
         }
     }
@@ -12,11 +12,11 @@ mod tests {
     }
 
     fn parse(input: &str) -> Vec<Expr> {
-        Parser::parse(Lexer::tokenize(input)).unwrap()
+        Parser::parse(Lexer::tokenize(input).unwrap()).unwrap()
     }
 
     fn parse_err(input: &str) -> ParseError {
-        Parser::parse(Lexer::tokenize(input)).unwrap_err()
+        Parser::parse(Lexer::tokenize(input).unwrap()).unwrap_err()
     }
 
     #[test]
@@ -31,6 +31,8 @@ mod tests {
         assert_eq!(result[0].kind, ExprKind::Long(-7));
     }
 
+    // 3.14 is the literal under test, not an approximation of PI
+    #[allow(clippy::approx_constant)]
     #[test]
     fn parses_double() {
         let result = parse("3.14");
@@ -43,6 +45,35 @@ mod tests {
         assert_eq!(result[0].kind, ExprKind::String("hello".to_string()));
     }
 
+    #[test]
+    fn parses_char() {
+        let result = parse("\\newline");
+        assert_eq!(result[0].kind, ExprKind::Char('\n'));
+    }
+
+    #[test]
+    fn parses_regex() {
+        let result = parse("#\"a+\"");
+        assert_eq!(result[0].kind, ExprKind::Regex("a+".to_string()));
+    }
+
+    #[test]
+    fn parses_tagged_literal() {
+        let result = parse("#inst \"2026-01-01\"");
+        assert_eq!(
+            result[0].kind,
+            ExprKind::Tagged {
+                tag: "inst".to_string(),
+                form: Box::new(expr(ExprKind::String("2026-01-01".to_string()))),
+            }
+        );
+    }
+
+    #[test]
+    fn unfinished_tag_is_unmatched() {
+        assert!(matches!(parse_err("#inst"), ParseError::UnmatchedOpen(_)));
+    }
+
     #[test]
     fn parses_keyword() {
         let result = parse(":foo");
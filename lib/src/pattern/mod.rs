@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod test_pattern;
+
+use std::cell::OnceCell;
+use std::ops::Deref;
+
+use regex::Regex;
+
+/// A compiled regex, from a `#"..."` literal or `re-pattern`. Derefs to the
+/// regex itself; the whole-input form `re-matches` needs is compiled on first
+/// use and kept.
+#[derive(Debug)]
+pub struct Pattern {
+    regex: Regex,
+    anchored: OnceCell<Regex>,
+}
+
+impl Pattern {
+    pub fn new(source: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            regex: Regex::new(source)?,
+            anchored: OnceCell::new(),
+        })
+    }
+
+    /// The pattern wrapped in `\A(?:...)\z`. A leftmost-first search can stop
+    /// short of the end even when a longer alternative would match the whole
+    /// input, so checking a plain match's extent is not enough.
+    pub fn anchored(&self) -> Result<&Regex, regex::Error> {
+        if let Some(re) = self.anchored.get() {
+            return Ok(re);
+        }
+        let re = Regex::new(&format!(r"\A(?:{})\z", self.regex.as_str()))?;
+        Ok(self.anchored.get_or_init(|| re))
+    }
+}
+
+impl Deref for Pattern {
+    type Target = Regex;
+
+    fn deref(&self) -> &Regex {
+        &self.regex
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::pattern::Pattern;
+
+    #[test]
+    fn derefs_to_the_regex() {
+        let p = Pattern::new("b+").unwrap();
+        assert_eq!(p.find("abbc").map(|m| m.as_str()), Some("bb"));
+        assert_eq!(p.as_str(), "b+");
+    }
+
+    #[test]
+    fn invalid_source_is_an_error() {
+        assert!(Pattern::new("(a").is_err());
+    }
+
+    #[test]
+    fn anchored_matches_whole_input_only() {
+        let p = Pattern::new("a|ab").unwrap();
+        let anchored = p.anchored().unwrap();
+        assert!(anchored.is_match("ab"));
+        assert!(!anchored.is_match("abc"));
+    }
+
+    #[test]
+    fn anchored_is_compiled_once() {
+        let p = Pattern::new("x").unwrap();
+        let first: *const regex::Regex = p.anchored().unwrap();
+        let second: *const regex::Regex = p.anchored().unwrap();
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,838 @@
+use super::AnalyzeError;
+use crate::lexer::Span;
+use crate::parser::{Expr, ExprKind};
+
+// Threading and conditional macros are rewritten into core forms before
+// analysis. Generated forms take the span of the user form they stand for,
+// so errors point at the source rather than at the expansion. Temporaries
+// are named with a leading space, which the reader never produces.
+
+pub(super) fn expand(elems: &[Expr], span: Span) -> Result<Option<Expr>, AnalyzeError> {
+    let name = match elems.first().map(|e| &e.kind) {
+        Some(ExprKind::Symbol(name)) => name.as_str(),
+        _ => return Ok(None),
+    };
+    let expanded = match name {
+        "->" => expand_thread(elems, span, "->", false)?,
+        "->>" => expand_thread(elems, span, "->>", true)?,
+        "as->" => expand_as_thread(elems, span)?,
+        "some->" => expand_some_thread(elems, span)?,
+        "cond->" => expand_cond_thread(elems, span)?,
+        "when" => expand_when(elems, span)?,
+        "when-let" => expand_when_let(elems, span)?,
+        "if-let" => expand_if_let(elems, span)?,
+        "cond" => expand_cond(elems, span)?,
+        "condp" => expand_condp(elems, span)?,
+        "dotimes" => expand_dotimes(elems, span)?,
+        "while" => expand_while(elems, span)?,
+        "defmulti" => expand_defmulti(elems, span)?,
+        "defmethod" => expand_defmethod(elems, span)?,
+        "defprotocol" => expand_defprotocol(elems, span)?,
+        "defrecord" => expand_deftype(elems, span, "defrecord", true)?,
+        "deftype" => expand_deftype(elems, span, "deftype", false)?,
+        "extend-type" => expand_extend_type(elems, span)?,
+        "extend-protocol" => expand_extend_protocol(elems, span)?,
+        field if field.len() > 2 && field.starts_with(".-") => {
+            expand_field_access(elems, span, &field[2..])?
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(expanded))
+}
+
+fn list(elems: Vec<Expr>, span: Span) -> Expr {
+    Expr {
+        kind: ExprKind::List(elems),
+        span,
+    }
+}
+
+fn vector(elems: Vec<Expr>, span: Span) -> Expr {
+    Expr {
+        kind: ExprKind::Vector(elems),
+        span,
+    }
+}
+
+fn symbol(name: &str, span: Span) -> Expr {
+    Expr {
+        kind: ExprKind::Symbol(name.to_string()),
+        span,
+    }
+}
+
+fn nil(span: Span) -> Expr {
+    Expr {
+        kind: ExprKind::Nil,
+        span,
+    }
+}
+
+// `if` only accepts booleans and nil, while these macros test any value:
+// `(and test true)` keeps nil and false and turns everything else into true
+fn truthy(test: Expr) -> Expr {
+    let span = test.span;
+    let always = Expr {
+        kind: ExprKind::Bool(true),
+        span,
+    };
+    list(vec![symbol("and", span), test, always], span)
+}
+
+fn body(forms: &[Expr], span: Span) -> Expr {
+    let mut elems = vec![symbol("do", span)];
+    elems.extend(forms.iter().cloned());
+    list(elems, span)
+}
+
+fn thread_into(x: Expr, step: &Expr, last: bool) -> Expr {
+    match &step.kind {
+        ExprKind::List(items) if !items.is_empty() => {
+            let mut items = items.clone();
+            if last {
+                items.push(x);
+            } else {
+                items.insert(1, x);
+            }
+            list(items, step.span)
+        }
+        _ => list(vec![step.clone(), x], step.span),
+    }
+}
+
+fn expand_thread(
+    elems: &[Expr],
+    span: Span,
+    form: &'static str,
+    last: bool,
+) -> Result<Expr, AnalyzeError> {
+    // (-> x (f a) g) => (g (f x a))
+    if elems.len() < 2 {
+        return Err(AnalyzeError::InvalidArity { form, span });
+    }
+    Ok(elems[2..]
+        .iter()
+        .fold(elems[1].clone(), |x, step| thread_into(x, step, last)))
+}
+
+fn expand_as_thread(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (as-> x n (f n) (g 1 n)) => (let [n x n (f n) n (g 1 n)] n)
+    if elems.len() < 3 {
+        return Err(AnalyzeError::InvalidArity { form: "as->", span });
+    }
+    let name = &elems[2];
+    if !matches!(name.kind, ExprKind::Symbol(_)) {
+        return Err(AnalyzeError::InvalidBindingKey(name.span));
+    }
+    let mut bindings = vec![name.clone(), elems[1].clone()];
+    for step in &elems[3..] {
+        bindings.push(name.clone());
+        bindings.push(step.clone());
+    }
+    Ok(list(
+        vec![symbol("let", span), vector(bindings, span), name.clone()],
+        span,
+    ))
+}
+
+fn some_chain(x: Expr, steps: &[Expr], span: Span) -> Expr {
+    let Some((step, rest)) = steps.split_first() else {
+        return x;
+    };
+    let tmp = symbol(" some->", x.span);
+    let is_nil = list(
+        vec![internal("=", step.span), tmp.clone(), nil(step.span)],
+        step.span,
+    );
+    let next = some_chain(thread_into(tmp.clone(), step, false), rest, span);
+    list(
+        vec![
+            symbol("let", span),
+            vector(vec![tmp, x], span),
+            list(
+                vec![symbol("if", step.span), is_nil, nil(step.span), next],
+                step.span,
+            ),
+        ],
+        span,
+    )
+}
+
+fn expand_some_thread(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (some-> x f g) threads like -> but stops at the first nil
+    if elems.len() < 2 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "some->",
+            span,
+        });
+    }
+    Ok(some_chain(elems[1].clone(), &elems[2..], span))
+}
+
+fn expand_cond_thread(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (cond-> x t1 f1 t2 f2) threads x through each form whose test holds
+    if elems.len() < 2 || !(elems.len() - 2).is_multiple_of(2) {
+        return Err(AnalyzeError::InvalidArity {
+            form: "cond->",
+            span,
+        });
+    }
+    let tmp = symbol(" cond->", span);
+    let mut bindings = vec![tmp.clone(), elems[1].clone()];
+    for clause in elems[2..].chunks(2) {
+        let (test, step) = (&clause[0], &clause[1]);
+        bindings.push(tmp.clone());
+        bindings.push(list(
+            vec![
+                symbol("if", step.span),
+                truthy(test.clone()),
+                thread_into(tmp.clone(), step, false),
+                tmp.clone(),
+            ],
+            step.span,
+        ));
+    }
+    Ok(list(
+        vec![symbol("let", span), vector(bindings, span), tmp],
+        span,
+    ))
+}
+
+fn expand_when(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (when test body...) => (if test (do body...))
+    if elems.len() < 2 {
+        return Err(AnalyzeError::InvalidArity { form: "when", span });
+    }
+    Ok(list(
+        vec![
+            symbol("if", span),
+            truthy(elems[1].clone()),
+            body(&elems[2..], span),
+        ],
+        span,
+    ))
+}
+
+fn single_binding(bindings: &Expr) -> Result<(Expr, Expr), AnalyzeError> {
+    match &bindings.kind {
+        ExprKind::Vector(pair) if pair.len() == 2 => Ok((pair[0].clone(), pair[1].clone())),
+        ExprKind::Vector(_) => Err(AnalyzeError::OddBindings(bindings.span)),
+        _ => Err(AnalyzeError::InvalidBindings(bindings.span)),
+    }
+}
+
+// (let [tmp value] (if tmp (let [name tmp] then) else)), so that only the
+// taken branch sees the binding
+fn conditional_let(
+    bindings: &Expr,
+    then: Expr,
+    _else: Option<Expr>,
+    span: Span,
+) -> Result<Expr, AnalyzeError> {
+    let (name, value) = single_binding(bindings)?;
+    let tmp = symbol(" if-let", value.span);
+    let then = list(
+        vec![
+            symbol("let", span),
+            vector(vec![name, tmp.clone()], bindings.span),
+            then,
+        ],
+        span,
+    );
+    let mut branch = vec![symbol("if", span), truthy(tmp.clone()), then];
+    branch.extend(_else);
+    Ok(list(
+        vec![
+            symbol("let", span),
+            vector(vec![tmp, value], bindings.span),
+            list(branch, span),
+        ],
+        span,
+    ))
+}
+
+fn expand_when_let(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (when-let [x test] body...)
+    if elems.len() < 2 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "when-let",
+            span,
+        });
+    }
+    conditional_let(&elems[1], body(&elems[2..], span), None, span)
+}
+
+fn expand_if_let(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (if-let [x test] then else?)
+    if elems.len() < 3 || elems.len() > 4 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "if-let",
+            span,
+        });
+    }
+    conditional_let(&elems[1], elems[2].clone(), elems.get(3).cloned(), span)
+}
+
+fn expand_cond(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (cond t1 r1 t2 r2 :else r3) => nested ifs, nil when nothing matches
+    if !(elems.len() - 1).is_multiple_of(2) {
+        return Err(AnalyzeError::InvalidArity { form: "cond", span });
+    }
+    Ok(elems[1..].chunks(2).rev().fold(nil(span), |rest, clause| {
+        let (test, result) = (&clause[0], &clause[1]);
+        list(
+            vec![
+                symbol("if", test.span),
+                truthy(test.clone()),
+                result.clone(),
+                rest,
+            ],
+            test.span,
+        )
+    }))
+}
+
+fn condp_chain(pred: &Expr, value: &Expr, clauses: &[Expr], span: Span) -> Expr {
+    match clauses {
+        // Without a default the value falls through to an empty `case`,
+        // which reports it as unmatched
+        [] => list(vec![symbol("case", span), value.clone()], span),
+        [default] => default.clone(),
+        [test, arrow, f, rest @ ..] if matches!(&arrow.kind, ExprKind::Keyword(k) if k == ">>") => {
+            let tmp = symbol(" condp-result", test.span);
+            let call = list(vec![pred.clone(), test.clone(), value.clone()], test.span);
+            list(
+                vec![
+                    symbol("let", test.span),
+                    vector(vec![tmp.clone(), call], test.span),
+                    list(
+                        vec![
+                            symbol("if", test.span),
+                            truthy(tmp.clone()),
+                            list(vec![f.clone(), tmp], f.span),
+                            condp_chain(pred, value, rest, span),
+                        ],
+                        test.span,
+                    ),
+                ],
+                test.span,
+            )
+        }
+        [test, result, rest @ ..] => {
+            let call = list(vec![pred.clone(), test.clone(), value.clone()], test.span);
+            list(
+                vec![
+                    symbol("if", test.span),
+                    truthy(call),
+                    result.clone(),
+                    condp_chain(pred, value, rest, span),
+                ],
+                test.span,
+            )
+        }
+    }
+}
+
+fn expand_condp(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (condp pred expr test result ... default?) calls (pred test expr)
+    if elems.len() < 3 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "condp",
+            span,
+        });
+    }
+    let pred = symbol(" condp-pred", elems[1].span);
+    let value = symbol(" condp-value", elems[2].span);
+    let bindings = vec![
+        pred.clone(),
+        elems[1].clone(),
+        value.clone(),
+        elems[2].clone(),
+    ];
+    Ok(list(
+        vec![
+            symbol("let", span),
+            vector(bindings, span),
+            condp_chain(&pred, &value, &elems[3..], span),
+        ],
+        span,
+    ))
+}
+
+fn internal(name: &str, span: Span) -> Expr {
+    Expr {
+        kind: ExprKind::QualifiedSymbol {
+            ns: "risp.internal".to_string(),
+            name: name.to_string(),
+        },
+        span,
+    }
+}
+
+fn expand_dotimes(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (dotimes [i n] body...) =>
+    // (let [n' n] (loop [i 0] (if (< i n') (do body... (recur (+ i 1))))))
+    if elems.len() < 2 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "dotimes",
+            span,
+        });
+    }
+    let (name, count) = single_binding(&elems[1])?;
+    let limit = symbol(" dotimes", count.span);
+    let zero = Expr {
+        kind: ExprKind::Long(0),
+        span: name.span,
+    };
+    let one = Expr {
+        kind: ExprKind::Long(1),
+        span: name.span,
+    };
+    let next = list(vec![internal("+", name.span), name.clone(), one], name.span);
+    let mut forms = elems[2..].to_vec();
+    forms.push(list(vec![symbol("recur", span), next], span));
+    let test = list(
+        vec![internal("<", count.span), name.clone(), limit.clone()],
+        count.span,
+    );
+    let step = list(vec![symbol("if", span), test, body(&forms, span)], span);
+    let looped = list(
+        vec![
+            symbol("loop", span),
+            vector(vec![name, zero], elems[1].span),
+            step,
+        ],
+        span,
+    );
+    Ok(list(
+        vec![
+            symbol("let", span),
+            vector(vec![limit, count], elems[1].span),
+            looped,
+        ],
+        span,
+    ))
+}
+
+fn expand_while(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (while test body...) => (loop [] (if test (do body... (recur))))
+    if elems.len() < 2 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "while",
+            span,
+        });
+    }
+    let mut forms = elems[2..].to_vec();
+    forms.push(list(vec![symbol("recur", span)], span));
+    let step = list(
+        vec![
+            symbol("if", span),
+            truthy(elems[1].clone()),
+            body(&forms, span),
+        ],
+        span,
+    );
+    Ok(list(
+        vec![symbol("loop", span), vector(vec![], span), step],
+        span,
+    ))
+}
+
+fn expand_defmulti(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (defmulti name doc? dispatch-fn & options) =>
+    // (def name (risp.internal/multi-fn "name" dispatch-fn options...))
+    let name = match elems.get(1).map(|e| &e.kind) {
+        Some(ExprKind::Symbol(name)) => name.clone(),
+        _ => {
+            return Err(AnalyzeError::InvalidArity {
+                form: "defmulti",
+                span,
+            })
+        }
+    };
+    let rest = match &elems[2..] {
+        [doc, rest @ ..] if matches!(doc.kind, ExprKind::String(_)) && !rest.is_empty() => rest,
+        rest => rest,
+    };
+    if rest.is_empty() || !(rest.len() - 1).is_multiple_of(2) {
+        return Err(AnalyzeError::InvalidArity {
+            form: "defmulti",
+            span,
+        });
+    }
+    let mut call = vec![
+        internal("multi-fn", span),
+        Expr {
+            kind: ExprKind::String(name),
+            span: elems[1].span,
+        },
+    ];
+    call.extend(rest.iter().cloned());
+    Ok(list(
+        vec![symbol("def", span), elems[1].clone(), list(call, span)],
+        span,
+    ))
+}
+
+fn expand_defmethod(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (defmethod name dispatch-value params body) =>
+    // (risp.internal/add-method name dispatch-value (fn params body))
+    if elems.len() < 4 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "defmethod",
+            span,
+        });
+    }
+    let mut method = vec![symbol("fn", span)];
+    method.extend(elems[3..].iter().cloned());
+    Ok(list(
+        vec![
+            internal("add-method", span),
+            elems[1].clone(),
+            elems[2].clone(),
+            list(method, span),
+        ],
+        span,
+    ))
+}
+
+fn keyword(name: &str, span: Span) -> Expr {
+    Expr {
+        kind: ExprKind::Keyword(name.to_string()),
+        span,
+    }
+}
+
+fn string(value: &str, span: Span) -> Expr {
+    Expr {
+        kind: ExprKind::String(value.to_string()),
+        span,
+    }
+}
+
+fn symbol_name(expr: &Expr) -> Option<&str> {
+    match &expr.kind {
+        ExprKind::Symbol(name) => Some(name),
+        _ => None,
+    }
+}
+
+fn expand_field_access(elems: &[Expr], span: Span, field: &str) -> Result<Expr, AnalyzeError> {
+    // (.-x obj) => (risp.internal/field obj :x)
+    if elems.len() != 2 {
+        return Err(AnalyzeError::InvalidArity { form: ".-", span });
+    }
+    Ok(list(
+        vec![
+            internal("field", span),
+            elems[1].clone(),
+            keyword(field, elems[0].span),
+        ],
+        span,
+    ))
+}
+
+fn expand_defprotocol(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (defprotocol P doc? (m [this] doc?) ...) =>
+    // (do (def P (risp.internal/protocol "P" [:m ...]))
+    //     (def m (risp.internal/protocol-method P :m)) ...)
+    let (protocol, name) = match elems.get(1) {
+        Some(e) => match symbol_name(e) {
+            Some(name) => (e, name),
+            None => {
+                return Err(AnalyzeError::InvalidArity {
+                    form: "defprotocol",
+                    span,
+                })
+            }
+        },
+        None => {
+            return Err(AnalyzeError::InvalidArity {
+                form: "defprotocol",
+                span,
+            })
+        }
+    };
+    let mut methods = vec![];
+    for sig in &elems[2..] {
+        match &sig.kind {
+            ExprKind::String(_) => continue,
+            ExprKind::List(items) => match items.first() {
+                Some(method) if symbol_name(method).is_some() => methods.push(method),
+                _ => return Err(AnalyzeError::InvalidExpression(sig.span)),
+            },
+            _ => return Err(AnalyzeError::InvalidExpression(sig.span)),
+        }
+    }
+    let keys = methods
+        .iter()
+        .map(|m| keyword(symbol_name(m).unwrap_or_default(), m.span))
+        .collect();
+    let mut forms = vec![
+        symbol("do", span),
+        list(
+            vec![
+                symbol("def", span),
+                protocol.clone(),
+                list(
+                    vec![
+                        internal("protocol", span),
+                        string(name, protocol.span),
+                        vector(keys, span),
+                    ],
+                    span,
+                ),
+            ],
+            span,
+        ),
+    ];
+    for method in methods {
+        let key = keyword(symbol_name(method).unwrap_or_default(), method.span);
+        forms.push(list(
+            vec![
+                symbol("def", method.span),
+                method.clone(),
+                list(
+                    vec![
+                        internal("protocol-method", method.span),
+                        protocol.clone(),
+                        key,
+                    ],
+                    method.span,
+                ),
+            ],
+            method.span,
+        ));
+    }
+    Ok(list(forms, span))
+}
+
+type ImplGroup<'a> = (&'a Expr, Vec<&'a Expr>);
+
+// Splits `Head (method ...) (method ...) Head2 ...` into each head and the
+// method forms that follow it
+fn impl_groups(forms: &[Expr]) -> Result<Vec<ImplGroup<'_>>, AnalyzeError> {
+    let mut groups: Vec<ImplGroup> = vec![];
+    for form in forms {
+        match (&form.kind, groups.last_mut()) {
+            (ExprKind::List(_), Some((_, methods))) => methods.push(form),
+            (ExprKind::List(_), None) => return Err(AnalyzeError::InvalidExpression(form.span)),
+            _ => groups.push((form, vec![])),
+        }
+    }
+    Ok(groups)
+}
+
+// {:m (fn ([this a] body) ...)}, one entry per method name with an arity per
+// form. Inside a record's methods its fields are locals, unless a parameter
+// of the same name hides them.
+fn method_map(methods: &[&Expr], fields: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    let mut by_name: Vec<(&Expr, Vec<Expr>)> = vec![];
+    for method in methods {
+        let ExprKind::List(items) = &method.kind else {
+            return Err(AnalyzeError::InvalidExpression(method.span));
+        };
+        let (name, params) = match items.as_slice() {
+            [name, params, ..] if symbol_name(name).is_some() => (name, params),
+            _ => return Err(AnalyzeError::InvalidExpression(method.span)),
+        };
+        let this = match &params.kind {
+            ExprKind::Vector(ps) => match ps.first().and_then(symbol_name) {
+                Some(_) => &ps[0],
+                None => return Err(AnalyzeError::InvalidFnParams(params.span)),
+            },
+            _ => return Err(AnalyzeError::InvalidFnParams(params.span)),
+        };
+        let ExprKind::Vector(param_list) = &params.kind else {
+            unreachable!()
+        };
+        let mut bindings = vec![];
+        for field in fields {
+            if param_list.iter().any(|p| p == field) {
+                continue;
+            }
+            let name = symbol_name(field).unwrap_or_default();
+            bindings.push(field.clone());
+            bindings.push(list(
+                vec![
+                    internal("field", field.span),
+                    this.clone(),
+                    keyword(name, field.span),
+                ],
+                field.span,
+            ));
+        }
+        let mut method_body = body(&items[2..], method.span);
+        if !bindings.is_empty() {
+            method_body = list(
+                vec![
+                    symbol("let", method.span),
+                    vector(bindings, params.span),
+                    method_body,
+                ],
+                method.span,
+            );
+        }
+        let arity = list(vec![params.clone(), method_body], method.span);
+        match by_name.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, arities)) => arities.push(arity),
+            None => by_name.push((name, vec![arity])),
+        }
+    }
+    let pairs = by_name
+        .into_iter()
+        .map(|(name, arities)| {
+            let mut f = vec![symbol("fn", name.span)];
+            f.extend(arities);
+            (
+                keyword(symbol_name(name).unwrap_or_default(), name.span),
+                list(f, name.span),
+            )
+        })
+        .collect();
+    Ok(Expr {
+        kind: ExprKind::Map(pairs),
+        span,
+    })
+}
+
+fn expand_deftype(
+    elems: &[Expr],
+    span: Span,
+    form: &'static str,
+    is_record: bool,
+) -> Result<Expr, AnalyzeError> {
+    // (defrecord Point [x y] Proto (m [this] body) ...) =>
+    // (do (def Point (risp.internal/record-type "Point" [:x :y] true))
+    //     (def ->Point (fn [x y] (risp.internal/new-record Point [x y])))
+    //     (def map->Point (fn [m] (risp.internal/map->record Point m)))
+    //     (risp.internal/extend Point Proto {:m (fn ...)}) ...)
+    // deftype is the same without map->Point
+    let (rtype, name, fields) = match elems {
+        [_, rtype, fields, ..] => match (symbol_name(rtype), &fields.kind) {
+            (Some(name), ExprKind::Vector(fields)) => (rtype, name, fields),
+            _ => return Err(AnalyzeError::InvalidArity { form, span }),
+        },
+        _ => return Err(AnalyzeError::InvalidArity { form, span }),
+    };
+    if let Some(field) = fields.iter().find(|f| symbol_name(f).is_none()) {
+        return Err(AnalyzeError::InvalidBindingKey(field.span));
+    }
+    let keys = fields
+        .iter()
+        .map(|f| keyword(symbol_name(f).unwrap_or_default(), f.span))
+        .collect();
+    let fields_span = elems[2].span;
+    let def = |var: String, value: Expr| {
+        list(
+            vec![symbol("def", span), symbol(&var, rtype.span), value],
+            span,
+        )
+    };
+    let mut forms = vec![
+        symbol("do", span),
+        def(
+            name.to_string(),
+            list(
+                vec![
+                    internal("record-type", span),
+                    string(name, rtype.span),
+                    vector(keys, fields_span),
+                    Expr {
+                        kind: ExprKind::Bool(is_record),
+                        span,
+                    },
+                ],
+                span,
+            ),
+        ),
+        def(
+            format!("->{name}"),
+            list(
+                vec![
+                    symbol("fn", span),
+                    vector(fields.clone(), fields_span),
+                    list(
+                        vec![
+                            internal("new-record", span),
+                            rtype.clone(),
+                            vector(fields.clone(), fields_span),
+                        ],
+                        span,
+                    ),
+                ],
+                span,
+            ),
+        ),
+    ];
+    if is_record {
+        let map = symbol(" map", span);
+        forms.push(def(
+            format!("map->{name}"),
+            list(
+                vec![
+                    symbol("fn", span),
+                    vector(vec![map.clone()], span),
+                    list(
+                        vec![internal("map->record", span), rtype.clone(), map],
+                        span,
+                    ),
+                ],
+                span,
+            ),
+        ));
+    }
+    for (protocol, methods) in impl_groups(&elems[3..])? {
+        forms.push(list(
+            vec![
+                internal("extend", span),
+                rtype.clone(),
+                protocol.clone(),
+                method_map(&methods, fields, protocol.span)?,
+            ],
+            span,
+        ));
+    }
+    Ok(list(forms, span))
+}
+
+fn expand_extend_type(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (extend-type T P1 (m [x] ...) P2 ...) =>
+    // (risp.internal/extend T P1 {:m (fn ...)} P2 {...})
+    if elems.len() < 3 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "extend-type",
+            span,
+        });
+    }
+    let mut call = vec![internal("extend", span), elems[1].clone()];
+    for (protocol, methods) in impl_groups(&elems[2..])? {
+        call.push(protocol.clone());
+        call.push(method_map(&methods, &[], protocol.span)?);
+    }
+    Ok(list(call, span))
+}
+
+fn expand_extend_protocol(elems: &[Expr], span: Span) -> Result<Expr, AnalyzeError> {
+    // (extend-protocol P T1 (m [x] ...) T2 ...) =>
+    // (do (risp.internal/extend T1 P {:m (fn ...)}) (risp.internal/extend T2 P {...}))
+    if elems.len() < 3 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "extend-protocol",
+            span,
+        });
+    }
+    let mut forms = vec![symbol("do", span)];
+    for (rtype, methods) in impl_groups(&elems[2..])? {
+        forms.push(list(
+            vec![
+                internal("extend", rtype.span),
+                rtype.clone(),
+                elems[1].clone(),
+                method_map(&methods, &[], rtype.span)?,
+            ],
+            rtype.span,
+        ));
+    }
+    Ok(list(forms, span))
+}
@@ -1,13 +1,17 @@
 mod ast_scope;
+mod expand;
 mod node;
 
+use std::collections::HashMap;
 use std::rc::Rc;
 
 pub use self::ast_scope::LocalId;
 use self::ast_scope::Scope;
-pub use self::node::{AnalyzeError, AstNode, FnArity, Node};
+pub use self::node::{AnalyzeError, AstNode, CaseKey, FnArity, ForClause, Node};
+use crate::inst::Inst;
 use crate::lexer::Span;
 use crate::parser::{Expr, ExprKind};
+use crate::pattern::Pattern;
 
 #[cfg(test)]
 mod test_sema;
@@ -23,10 +27,16 @@ fn analyze_expr(expr: Expr, scope: &Scope) -> Result<AstNode, AnalyzeError> {
     let span = expr.span;
     match expr.kind {
         ExprKind::Long(n) => Ok(AstNode::new(Node::Long(n), span)),
+        ExprKind::BigInt(n) => Ok(AstNode::new(Node::BigInt(Rc::new(n)), span)),
+        ExprKind::Ratio(r) => Ok(AstNode::new(Node::Ratio(Rc::new(r)), span)),
         ExprKind::Double(n) => Ok(AstNode::new(Node::Double(n), span)),
+        ExprKind::Char(c) => Ok(AstNode::new(Node::Char(c), span)),
         ExprKind::Bool(b) => Ok(AstNode::new(Node::Bool(b), span)),
         ExprKind::Nil => Ok(AstNode::new(Node::Nil, span)),
         ExprKind::String(s) => Ok(AstNode::new(Node::String(s), span)),
+        ExprKind::Regex(s) => Pattern::new(&s)
+            .map(|re| AstNode::new(Node::Regex(Rc::new(re)), span))
+            .map_err(|_| AnalyzeError::InvalidRegex(span)),
         ExprKind::Keyword(s) => Ok(AstNode::new(Node::Keyword(s), span)),
         ExprKind::Symbol(s) => match scope.get_by_name(&s) {
             Some(id) => Ok(AstNode::new(Node::Var(id), span)),
@@ -58,6 +68,33 @@ fn analyze_expr(expr: Expr, scope: &Scope) -> Result<AstNode, AnalyzeError> {
             Ok(AstNode::new(Node::Set(nodes), span))
         }
         ExprKind::Quote(inner) => analyze_quoted(*inner, scope),
+        ExprKind::Tagged { tag, form } => analyze_tagged(&tag, *form, span, scope),
+    }
+}
+
+fn analyze_tagged(
+    tag: &str,
+    form: Expr,
+    span: Span,
+    scope: &Scope,
+) -> Result<AstNode, AnalyzeError> {
+    match (tag, form.kind) {
+        ("inst", ExprKind::String(s)) => Inst::parse(&s)
+            .map(|inst| AstNode::new(Node::Inst(inst), span))
+            .ok_or(AnalyzeError::InvalidInst(span)),
+        ("inst", _) => Err(AnalyzeError::InvalidInst(span)),
+        ("queue", ExprKind::Vector(elems)) => {
+            let nodes = elems
+                .into_iter()
+                .map(|e| analyze_expr(e, scope))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(AstNode::new(Node::Queue(nodes), span))
+        }
+        ("queue", _) => Err(AnalyzeError::InvalidQueue(span)),
+        _ => Err(AnalyzeError::UnknownTag {
+            tag: tag.to_string(),
+            span,
+        }),
     }
 }
 
@@ -89,6 +126,9 @@ fn is_symbol(expr: &Expr, name: &str) -> bool {
 }
 
 fn analyze_list(elems: Vec<Expr>, span: Span, scope: &Scope) -> Result<AstNode, AnalyzeError> {
+    if let Some(expanded) = expand::expand(&elems, span)? {
+        return analyze_expr(expanded, scope);
+    }
     match elems.first() {
         Some(head) if is_symbol(head, "if") => analyze_if(elems, span, scope),
         Some(head) if is_symbol(head, "let") => analyze_let(elems, span, scope),
@@ -100,6 +140,12 @@ fn analyze_list(elems: Vec<Expr>, span: Span, scope: &Scope) -> Result<AstNode,
         Some(head) if is_symbol(head, "recur") => analyze_recur(elems, span, scope),
         Some(head) if is_symbol(head, "and") => analyze_and(elems, span, scope),
         Some(head) if is_symbol(head, "or") => analyze_or(elems, span, scope),
+        Some(head) if is_symbol(head, "with-open") => analyze_with_open(elems, span, scope),
+        Some(head) if is_symbol(head, "binding") => analyze_binding(elems, span, scope),
+        Some(head) if is_symbol(head, "with-out-str") => analyze_with_out_str(elems, span, scope),
+        Some(head) if is_symbol(head, "case") => analyze_case(elems, span, scope),
+        Some(head) if is_symbol(head, "for") => analyze_for(elems, span, scope, true),
+        Some(head) if is_symbol(head, "doseq") => analyze_for(elems, span, scope, false),
         _ => analyze_call(elems, span, scope),
     }
 }
@@ -175,10 +221,18 @@ fn analyze_let(elems: Vec<Expr>, span: Span, scope: &Scope) -> Result<AstNode, A
     }
 
     // 0 is the let symbol
-    let bindings_expr = elems[1].clone();
-    let bindings_span = bindings_expr.span;
-
     let mut child_scope = scope.enter_scope();
+    let bindings = analyze_bindings(elems[1].clone(), &mut child_scope)?;
+    let body = Box::new(analyze_expr(elems[2].clone(), &child_scope)?);
+
+    Ok(AstNode::new(Node::Let { bindings, body }, span))
+}
+
+fn analyze_bindings(
+    bindings_expr: Expr,
+    scope: &mut Scope,
+) -> Result<Vec<(LocalId, AstNode)>, AnalyzeError> {
+    let bindings_span = bindings_expr.span;
     let bindings_array: Vec<Expr> = match bindings_expr.kind {
         ExprKind::Vector(l) => Ok(l),
         _ => Err(AnalyzeError::InvalidBindings(bindings_span)),
@@ -192,18 +246,16 @@ fn analyze_let(elems: Vec<Expr>, span: Span, scope: &Scope) -> Result<AstNode, A
     let mut bindings: Vec<(LocalId, AstNode)> = vec![];
 
     while let (Some(k), Some(v)) = (iter.next(), iter.next()) {
-        let val = analyze_expr(v, &child_scope)?;
+        let val = analyze_expr(v, scope)?;
         let key = match k.kind {
-            ExprKind::Symbol(name) => Ok(child_scope.bind(name)),
+            ExprKind::Symbol(name) => Ok(scope.bind(name)),
             _ => Err(AnalyzeError::InvalidBindingKey(k.span)),
         }?;
 
         bindings.push((key, val));
     }
 
-    let body = Box::new(analyze_expr(elems[2].clone(), &child_scope)?);
-
-    Ok(AstNode::new(Node::Let { bindings, body }, span))
+    Ok(bindings)
 }
 
 fn analyze_fn_params(
@@ -251,7 +303,9 @@ fn analyze_fn_params(
 fn frame_size(node: &AstNode) -> usize {
     match &node.node {
         Node::Var(id) => *id as usize + 1,
-        Node::Let { bindings, body } | Node::Loop { bindings, body } => {
+        Node::Let { bindings, body }
+        | Node::Loop { bindings, body }
+        | Node::WithOpen { bindings, body } => {
             let b = bindings
                 .iter()
                 .map(|(id, val)| (*id as usize + 1).max(frame_size(val)))
@@ -272,8 +326,29 @@ fn frame_size(node: &AstNode) -> usize {
             nodes.iter().map(frame_size).max().unwrap_or(0)
         }
         Node::Recur(args) => args.iter().map(frame_size).max().unwrap_or(0),
+        // The iterations run in frames of their own
+        Node::For { .. } => 0,
+        Node::Binding { bindings, body } => bindings
+            .iter()
+            .map(|(_, _, val)| frame_size(val))
+            .max()
+            .unwrap_or(0)
+            .max(frame_size(body)),
+        Node::WithOutStr(body) => frame_size(body),
+        Node::Case {
+            expr,
+            branches,
+            default,
+            ..
+        } => branches
+            .iter()
+            .chain(default.as_deref())
+            .map(frame_size)
+            .max()
+            .unwrap_or(0)
+            .max(frame_size(expr)),
         Node::Def { value, .. } => frame_size(value),
-        Node::Vector(nodes) | Node::List(nodes) | Node::Set(nodes) => {
+        Node::Vector(nodes) | Node::List(nodes) | Node::Set(nodes) | Node::Queue(nodes) => {
             nodes.iter().map(frame_size).max().unwrap_or(0)
         }
         Node::Map(pairs) => pairs
@@ -282,10 +357,15 @@ fn frame_size(node: &AstNode) -> usize {
             .max()
             .unwrap_or(0),
         Node::Long(_)
+        | Node::BigInt(_)
+        | Node::Ratio(_)
         | Node::Double(_)
+        | Node::Char(_)
         | Node::Bool(_)
         | Node::Nil
         | Node::String(_)
+        | Node::Regex(_)
+        | Node::Inst(_)
         | Node::Keyword(_)
         | Node::GlobalVar(_)
         | Node::QualifiedVar { .. }
@@ -403,10 +483,40 @@ fn analyze_loop(elems: Vec<Expr>, span: Span, scope: &Scope) -> Result<AstNode,
         return Err(AnalyzeError::InvalidArity { form: "loop", span });
     }
 
-    let bindings_expr = elems[1].clone();
-    let bindings_span = bindings_expr.span;
     let mut child_scope = scope.enter_scope();
+    let bindings = analyze_bindings(elems[1].clone(), &mut child_scope)?;
+
+    let body = Box::new(analyze_expr(elems[2].clone(), &child_scope)?);
+    Ok(AstNode::new(Node::Loop { bindings, body }, span))
+}
+
+fn analyze_with_open(elems: Vec<Expr>, span: Span, scope: &Scope) -> Result<AstNode, AnalyzeError> {
+    // (with-open [r (risp.io/reader "f")] body)
+    if elems.len() != 3 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "with-open",
+            span,
+        });
+    }
+
+    let mut child_scope = scope.enter_scope();
+    let bindings = analyze_bindings(elems[1].clone(), &mut child_scope)?;
+
+    let body = Box::new(analyze_expr(elems[2].clone(), &child_scope)?);
+    Ok(AstNode::new(Node::WithOpen { bindings, body }, span))
+}
+
+fn analyze_binding(elems: Vec<Expr>, span: Span, scope: &Scope) -> Result<AstNode, AnalyzeError> {
+    // (binding [*out* w] body)
+    if elems.len() != 3 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "binding",
+            span,
+        });
+    }
 
+    let bindings_expr = elems[1].clone();
+    let bindings_span = bindings_expr.span;
     let bindings_array: Vec<Expr> = match bindings_expr.kind {
         ExprKind::Vector(l) => Ok(l),
         _ => Err(AnalyzeError::InvalidBindings(bindings_span)),
@@ -417,19 +527,197 @@ fn analyze_loop(elems: Vec<Expr>, span: Span, scope: &Scope) -> Result<AstNode,
     }
 
     let mut iter = bindings_array.into_iter();
-    let mut bindings: Vec<(LocalId, AstNode)> = vec![];
+    let mut bindings: Vec<(String, Span, AstNode)> = vec![];
 
     while let (Some(k), Some(v)) = (iter.next(), iter.next()) {
-        let val = analyze_expr(v, &child_scope)?;
+        // Dynamic bindings rebind globals, so values see the enclosing scope
+        let val = analyze_expr(v, scope)?;
         let key = match k.kind {
-            ExprKind::Symbol(name) => Ok(child_scope.bind(name)),
+            ExprKind::Symbol(name) => Ok(name),
             _ => Err(AnalyzeError::InvalidBindingKey(k.span)),
         }?;
-        bindings.push((key, val));
+        bindings.push((key, k.span, val));
     }
 
-    let body = Box::new(analyze_expr(elems[2].clone(), &child_scope)?);
-    Ok(AstNode::new(Node::Loop { bindings, body }, span))
+    let body = Box::new(analyze_expr(elems[2].clone(), scope)?);
+    Ok(AstNode::new(Node::Binding { bindings, body }, span))
+}
+
+fn analyze_with_out_str(
+    elems: Vec<Expr>,
+    span: Span,
+    scope: &Scope,
+) -> Result<AstNode, AnalyzeError> {
+    // (with-out-str body)
+    if elems.len() != 2 {
+        return Err(AnalyzeError::InvalidArity {
+            form: "with-out-str",
+            span,
+        });
+    }
+    let body = analyze_expr(elems[1].clone(), scope)?;
+    Ok(AstNode::new(Node::WithOutStr(Box::new(body)), span))
+}
+
+fn case_key(expr: &Expr) -> Result<CaseKey, AnalyzeError> {
+    match &expr.kind {
+        ExprKind::Nil => Ok(CaseKey::Nil),
+        ExprKind::Bool(b) => Ok(CaseKey::Bool(*b)),
+        ExprKind::Long(n) => Ok(CaseKey::Long(*n)),
+        ExprKind::Char(c) => Ok(CaseKey::Char(*c)),
+        ExprKind::String(s) => Ok(CaseKey::String(s.clone())),
+        ExprKind::Keyword(s) => Ok(CaseKey::Keyword(s.clone())),
+        ExprKind::Symbol(s) => Ok(CaseKey::Symbol(s.clone())),
+        _ => Err(AnalyzeError::InvalidCaseKey(expr.span)),
+    }
+}
+
+fn analyze_case(elems: Vec<Expr>, span: Span, scope: &Scope) -> Result<AstNode, AnalyzeError> {
+    // (case expr key result (k1 k2) result default?)
+    if elems.len() < 2 {
+        return Err(AnalyzeError::InvalidArity { form: "case", span });
+    }
+
+    let expr = Box::new(analyze_expr(elems[1].clone(), scope)?);
+    let clauses = &elems[2..];
+    let (pairs, default) = if clauses.len().is_multiple_of(2) {
+        (clauses, None)
+    } else {
+        let (default, pairs) = clauses.split_last().unwrap();
+        (pairs, Some(Box::new(analyze_expr(default.clone(), scope)?)))
+    };
+
+    let mut table = HashMap::new();
+    let mut branches = vec![];
+    for pair in pairs.chunks(2) {
+        // A list groups several keys for the same result
+        let keys = match &pair[0].kind {
+            ExprKind::List(alternatives) => alternatives.iter().collect(),
+            _ => vec![&pair[0]],
+        };
+        for key in keys {
+            if table.insert(case_key(key)?, branches.len()).is_some() {
+                return Err(AnalyzeError::DuplicateCaseKey(key.span));
+            }
+        }
+        branches.push(analyze_expr(pair[1].clone(), scope)?);
+    }
+
+    Ok(AstNode::new(
+        Node::Case {
+            expr,
+            table: Rc::new(table),
+            branches,
+            default,
+        },
+        span,
+    ))
+}
+
+fn analyze_for(
+    elems: Vec<Expr>,
+    span: Span,
+    scope: &Scope,
+    collect: bool,
+) -> Result<AstNode, AnalyzeError> {
+    // (for [x xs :let [y (f x)] :when (p y) :while (q y)] body)
+    // (doseq [x xs] body...)
+    let form = if collect { "for" } else { "doseq" };
+    if elems.len() < 2 || (collect && elems.len() != 3) {
+        return Err(AnalyzeError::InvalidArity { form, span });
+    }
+
+    let bindings_expr = elems[1].clone();
+    let bindings_span = bindings_expr.span;
+    let mut child_scope = scope.enter_scope();
+
+    let bindings_array: Vec<Expr> = match bindings_expr.kind {
+        ExprKind::Vector(l) => Ok(l),
+        _ => Err(AnalyzeError::InvalidBindings(bindings_span)),
+    }?;
+
+    if bindings_array.is_empty() || !bindings_array.len().is_multiple_of(2) {
+        return Err(AnalyzeError::OddBindings(bindings_span));
+    }
+
+    let mut iter = bindings_array.into_iter();
+    let mut clauses: Vec<ForClause> = vec![];
+
+    while let (Some(k), Some(v)) = (iter.next(), iter.next()) {
+        let clause = match k.kind {
+            ExprKind::Symbol(name) => {
+                let coll = analyze_expr(v, &child_scope)?;
+                ForClause::Bind(child_scope.bind(name), coll)
+            }
+            // Modifiers only make sense once something is bound
+            ExprKind::Keyword(_) if clauses.is_empty() => {
+                return Err(AnalyzeError::InvalidBindingKey(k.span))
+            }
+            ExprKind::Keyword(modifier) if modifier == "when" => {
+                ForClause::When(analyze_expr(v, &child_scope)?)
+            }
+            ExprKind::Keyword(modifier) if modifier == "while" => {
+                ForClause::While(analyze_expr(v, &child_scope)?)
+            }
+            ExprKind::Keyword(modifier) if modifier == "let" => {
+                let let_span = v.span;
+                let pairs = match v.kind {
+                    ExprKind::Vector(l) => Ok(l),
+                    _ => Err(AnalyzeError::InvalidBindings(let_span)),
+                }?;
+                if !pairs.len().is_multiple_of(2) {
+                    return Err(AnalyzeError::OddBindings(let_span));
+                }
+                let mut pairs = pairs.into_iter();
+                let mut bindings = vec![];
+                while let (Some(name), Some(val)) = (pairs.next(), pairs.next()) {
+                    let val = analyze_expr(val, &child_scope)?;
+                    let key = match name.kind {
+                        ExprKind::Symbol(name) => Ok(child_scope.bind(name)),
+                        _ => Err(AnalyzeError::InvalidBindingKey(name.span)),
+                    }?;
+                    bindings.push((key, val));
+                }
+                ForClause::Let(bindings)
+            }
+            _ => return Err(AnalyzeError::InvalidBindingKey(k.span)),
+        };
+        clauses.push(clause);
+    }
+
+    let body = if collect {
+        analyze_expr(elems[2].clone(), &child_scope)?
+    } else {
+        let body = elems[2..]
+            .iter()
+            .map(|e| analyze_expr(e.clone(), &child_scope))
+            .collect::<Result<_, _>>()?;
+        AstNode::new(Node::Do(body), span)
+    };
+
+    let clause_size = clauses
+        .iter()
+        .map(|clause| match clause {
+            ForClause::Bind(id, coll) => (*id as usize + 1).max(frame_size(coll)),
+            ForClause::Let(bindings) => bindings
+                .iter()
+                .map(|(id, val)| (*id as usize + 1).max(frame_size(val)))
+                .max()
+                .unwrap_or(0),
+            ForClause::When(test) | ForClause::While(test) => frame_size(test),
+        })
+        .max()
+        .unwrap_or(0);
+
+    Ok(AstNode::new(
+        Node::For {
+            clauses,
+            frame_size: clause_size.max(frame_size(&body)),
+            body: Box::new(body),
+            collect,
+        },
+        span,
+    ))
 }
 
 fn analyze_recur(elems: Vec<Expr>, span: Span, scope: &Scope) -> Result<AstNode, AnalyzeError> {
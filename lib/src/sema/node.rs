@@ -1,6 +1,12 @@
+use std::collections::HashMap;
 use std::rc::Rc;
 
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+use crate::inst::Inst;
 use crate::lexer::Span;
+use crate::pattern::Pattern;
 use crate::sema::LocalId;
 
 #[derive(Debug)]
@@ -11,6 +17,12 @@ pub enum AnalyzeError {
     OddBindings(Span),
     InvalidBindingKey(Span),
     InvalidExpression(Span),
+    InvalidRegex(Span),
+    InvalidInst(Span),
+    InvalidQueue(Span),
+    UnknownTag { tag: String, span: Span },
+    InvalidCaseKey(Span),
+    DuplicateCaseKey(Span),
 }
 
 impl std::fmt::Display for AnalyzeError {
@@ -34,6 +46,24 @@ impl std::fmt::Display for AnalyzeError {
             AnalyzeError::InvalidExpression(span) => {
                 write!(f, "(invalid-expression :at {})", span.lo)
             }
+            AnalyzeError::InvalidRegex(span) => {
+                write!(f, "(invalid-regex :at {})", span.lo)
+            }
+            AnalyzeError::InvalidInst(span) => {
+                write!(f, "(invalid-inst :at {})", span.lo)
+            }
+            AnalyzeError::InvalidQueue(span) => {
+                write!(f, "(invalid-queue :at {})", span.lo)
+            }
+            AnalyzeError::UnknownTag { tag, span } => {
+                write!(f, "(unknown-tag :tag '{tag}' :at {})", span.lo)
+            }
+            AnalyzeError::InvalidCaseKey(span) => {
+                write!(f, "(invalid-case-key :at {})", span.lo)
+            }
+            AnalyzeError::DuplicateCaseKey(span) => {
+                write!(f, "(duplicate-case-key :at {})", span.lo)
+            }
         }
     }
 }
@@ -58,13 +88,40 @@ pub struct FnArity {
     pub frame_size: usize,
 }
 
+// Literal keys a `case` can dispatch on without evaluating them
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CaseKey {
+    Nil,
+    Bool(bool),
+    Long(i64),
+    Char(char),
+    String(String),
+    Keyword(String),
+    Symbol(String),
+}
+
+// One entry of a `for`/`doseq` binding vector
+#[derive(Debug, Clone)]
+pub enum ForClause {
+    Bind(LocalId, AstNode),
+    Let(Vec<(LocalId, AstNode)>),
+    When(AstNode),
+    While(AstNode),
+}
+
 #[derive(Debug, Clone)]
 pub enum Node {
     Long(i64),
+    BigInt(Rc<BigInt>),
+    Ratio(Rc<BigRational>),
     Double(f64),
+    Char(char),
     Bool(bool),
     Nil,
     String(String),
+    // Compiled once at analysis time so a literal in a loop is not rebuilt
+    Regex(Rc<Pattern>),
+    Inst(Inst),
     Keyword(String),
 
     Var(LocalId),
@@ -98,16 +155,43 @@ pub enum Node {
         _else: Option<Box<AstNode>>,
     },
     Do(Vec<AstNode>),
+    Case {
+        expr: Box<AstNode>,
+        // Maps each key to its branch, so dispatch does not scan the clauses
+        table: Rc<HashMap<CaseKey, usize>>,
+        branches: Vec<AstNode>,
+        default: Option<Box<AstNode>>,
+    },
 
     Loop {
         bindings: Vec<(LocalId, AstNode)>,
         body: Box<AstNode>,
     },
     Recur(Vec<AstNode>),
+    // `for` collects the body values, `doseq` only runs the body
+    For {
+        clauses: Vec<ForClause>,
+        body: Box<AstNode>,
+        collect: bool,
+        frame_size: usize,
+    },
+
+    WithOpen {
+        bindings: Vec<(LocalId, AstNode)>,
+        body: Box<AstNode>,
+    },
+    Binding {
+        // Each var's name, the span of that name and its new value
+        bindings: Vec<(String, Span, AstNode)>,
+        body: Box<AstNode>,
+    },
+    WithOutStr(Box<AstNode>),
 
     List(Vec<AstNode>),
     Vector(Vec<AstNode>),
     Map(Vec<(AstNode, AstNode)>),
     Set(Vec<AstNode>),
+    // `#queue [...]`, items evaluated like a vector's
+    Queue(Vec<AstNode>),
     Symbol(String),
 }
@@ -2,15 +2,15 @@
 mod tests {
     use crate::lexer::Lexer;
     use crate::parser::Parser;
-    use crate::sema::{analyze, AnalyzeError, AstNode, Node};
+    use crate::sema::{analyze, AnalyzeError, AstNode, CaseKey, Node};
 
     fn parse(input: &str) -> Vec<AstNode> {
-        let cst = Parser::parse(Lexer::tokenize(input)).unwrap();
+        let cst = Parser::parse(Lexer::tokenize(input).unwrap()).unwrap();
         analyze(cst).unwrap()
     }
 
     fn parse_err(input: &str) -> AnalyzeError {
-        let cst = Parser::parse(Lexer::tokenize(input)).unwrap();
+        let cst = Parser::parse(Lexer::tokenize(input).unwrap()).unwrap();
         analyze(cst).unwrap_err()
     }
 
@@ -50,6 +50,63 @@ mod tests {
         assert!(matches!(&result[0].node, Node::String(s) if s == "hello"));
     }
 
+    #[test]
+    fn analyzes_char() {
+        let result = parse("\\a");
+        assert!(matches!(&result[0].node, Node::Char('a')));
+    }
+
+    #[test]
+    fn analyzes_regex() {
+        let result = parse("#\"a+\"");
+        assert!(matches!(&result[0].node, Node::Regex(re) if re.as_str() == "a+"));
+    }
+
+    #[test]
+    fn analyzes_inst() {
+        let result = parse("#inst \"1970-01-01T00:00:01Z\"");
+        assert!(matches!(&result[0].node, Node::Inst(i) if i.millis == 1000));
+    }
+
+    #[test]
+    fn invalid_inst_is_an_error() {
+        assert!(matches!(
+            parse_err("#inst \"2026-13-01\""),
+            AnalyzeError::InvalidInst(_)
+        ));
+        assert!(matches!(parse_err("#inst 1"), AnalyzeError::InvalidInst(_)));
+    }
+
+    #[test]
+    fn analyzes_queue() {
+        let result = parse("#queue [1 :a]");
+        assert!(matches!(&result[0].node, Node::Queue(items) if items.len() == 2));
+    }
+
+    #[test]
+    fn queue_needs_a_vector() {
+        assert!(matches!(
+            parse_err("#queue (1 2)"),
+            AnalyzeError::InvalidQueue(_)
+        ));
+    }
+
+    #[test]
+    fn unknown_tag_is_an_error() {
+        assert!(matches!(
+            parse_err("#uuid \"x\""),
+            AnalyzeError::UnknownTag { tag, .. } if tag == "uuid"
+        ));
+    }
+
+    #[test]
+    fn invalid_regex_is_an_error() {
+        assert!(matches!(
+            parse_err("#\"(a\""),
+            AnalyzeError::InvalidRegex(_)
+        ));
+    }
+
     #[test]
     fn analyzes_keyword() {
         let result = parse(":foo");
@@ -496,6 +553,71 @@ mod tests {
         assert!(matches!(err, AnalyzeError::OddBindings(_)));
     }
 
+    #[test]
+    fn analyzes_with_open() {
+        let result = parse("(with-open [r (reader \"f\")] r)");
+        match &result[0].node {
+            Node::WithOpen { bindings, body } => {
+                assert_eq!(bindings.len(), 1);
+                assert!(matches!(body.node, Node::Var(_)));
+            }
+            _ => panic!("expected WithOpen"),
+        }
+    }
+
+    #[test]
+    fn error_with_open_wrong_arity() {
+        let err = parse_err("(with-open [r x])");
+        assert!(matches!(
+            err,
+            AnalyzeError::InvalidArity {
+                form: "with-open",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn error_with_open_odd_bindings() {
+        let err = parse_err("(with-open [r] r)");
+        assert!(matches!(err, AnalyzeError::OddBindings(_)));
+    }
+
+    #[test]
+    fn analyzes_binding_keeps_global_names() {
+        let result = parse("(let [x 1] (binding [*out* x] *out*))");
+        match &result[0].node {
+            Node::Let { body, .. } => match &body.node {
+                Node::Binding { bindings, body } => {
+                    assert_eq!(bindings[0].0, "*out*");
+                    assert_eq!(bindings[0].1.lo, 21);
+                    assert!(matches!(bindings[0].2.node, Node::Var(_)));
+                    assert!(matches!(&body.node, Node::GlobalVar(s) if s == "*out*"));
+                }
+                _ => panic!("expected Binding"),
+            },
+            _ => panic!("expected Let"),
+        }
+    }
+
+    #[test]
+    fn error_binding_non_symbol_key() {
+        let err = parse_err("(binding [1 2] 3)");
+        assert!(matches!(err, AnalyzeError::InvalidBindingKey(_)));
+    }
+
+    #[test]
+    fn error_with_out_str_wrong_arity() {
+        let err = parse_err("(with-out-str)");
+        assert!(matches!(
+            err,
+            AnalyzeError::InvalidArity {
+                form: "with-out-str",
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn analyzes_recur_no_args() {
         let result = parse("(recur)");
@@ -578,4 +700,176 @@ mod tests {
             _ => panic!("expected let"),
         }
     }
+
+    #[test]
+    fn thread_first_expands_to_nested_calls() {
+        // (-> x (f 1) g) is (g (f x 1))
+        let result = parse("(-> x (f 1) g)");
+        match &result[0].node {
+            Node::Call { callee, args } => {
+                assert!(matches!(&callee.node, Node::GlobalVar(n) if n == "g"));
+                match &args[0].node {
+                    Node::Call { args, .. } => {
+                        assert!(matches!(&args[0].node, Node::GlobalVar(n) if n == "x"));
+                        assert!(matches!(args[1].node, Node::Long(1)));
+                    }
+                    _ => panic!("expected inner call"),
+                }
+            }
+            _ => panic!("expected call"),
+        }
+    }
+
+    #[test]
+    fn thread_last_appends_argument() {
+        let result = parse("(->> x (f 1))");
+        match &result[0].node {
+            Node::Call { args, .. } => {
+                assert!(matches!(args[0].node, Node::Long(1)));
+                assert!(matches!(&args[1].node, Node::GlobalVar(n) if n == "x"));
+            }
+            _ => panic!("expected call"),
+        }
+    }
+
+    #[test]
+    fn threaded_forms_keep_user_spans() {
+        let source = "(-> 1 (f 2))";
+        let result = parse(source);
+        let step = source.find("(f").unwrap() as u32;
+        assert_eq!(result[0].span.lo, step);
+        match &result[0].node {
+            Node::Call { callee, .. } => assert_eq!(callee.span.lo, step + 1),
+            _ => panic!("expected call"),
+        }
+    }
+
+    #[test]
+    fn cond_expands_to_ifs() {
+        let result = parse("(cond a 1 :else 2)");
+        match &result[0].node {
+            Node::If { _else, .. } => {
+                assert!(matches!(
+                    _else.as_deref().map(|e| &e.node),
+                    Some(Node::If { .. })
+                ))
+            }
+            _ => panic!("expected if"),
+        }
+    }
+
+    #[test]
+    fn cond_odd_clauses_error() {
+        assert!(matches!(
+            parse_err("(cond a)"),
+            AnalyzeError::InvalidArity { form: "cond", .. }
+        ));
+    }
+
+    #[test]
+    fn if_let_requires_one_binding() {
+        assert!(matches!(
+            parse_err("(if-let [a 1 b 2] a)"),
+            AnalyzeError::OddBindings(_)
+        ));
+        assert!(matches!(
+            parse_err("(if-let a 1)"),
+            AnalyzeError::InvalidBindings(_)
+        ));
+    }
+
+    #[test]
+    fn case_builds_dispatch_table() {
+        let result = parse("(case x 1 :a (2 3) :b :c)");
+        match &result[0].node {
+            Node::Case {
+                table,
+                branches,
+                default,
+                ..
+            } => {
+                assert_eq!(table.len(), 3);
+                assert_eq!(table[&CaseKey::Long(3)], 1);
+                assert_eq!(branches.len(), 2);
+                assert!(default.is_some());
+            }
+            _ => panic!("expected case"),
+        }
+    }
+
+    #[test]
+    fn case_rejects_duplicate_and_non_literal_keys() {
+        assert!(matches!(
+            parse_err("(case x 1 :a (2 1) :b)"),
+            AnalyzeError::DuplicateCaseKey(_)
+        ));
+        assert!(matches!(
+            parse_err("(case x [1] :a)"),
+            AnalyzeError::InvalidCaseKey(_)
+        ));
+    }
+
+    #[test]
+    fn defmulti_expands_to_def() {
+        let result = parse("(defmulti area \"doc\" :type)");
+        assert!(matches!(&result[0].node, Node::Def { name, .. } if name == "area"));
+    }
+
+    #[test]
+    fn defmulti_and_defmethod_check_arity() {
+        assert!(matches!(
+            parse_err("(defmulti area)"),
+            AnalyzeError::InvalidArity {
+                form: "defmulti",
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse_err("(defmulti area :type :default)"),
+            AnalyzeError::InvalidArity {
+                form: "defmulti",
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse_err("(defmethod area :square)"),
+            AnalyzeError::InvalidArity {
+                form: "defmethod",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn defrecord_expands_to_do() {
+        let result = parse("(defrecord Point [x y])");
+        assert!(matches!(&result[0].node, Node::Do(forms) if forms.len() == 3));
+    }
+
+    #[test]
+    fn record_and_protocol_forms_check_shape() {
+        assert!(matches!(
+            parse_err("(defrecord Point)"),
+            AnalyzeError::InvalidArity {
+                form: "defrecord",
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse_err("(deftype Point [1])"),
+            AnalyzeError::InvalidBindingKey(_)
+        ));
+        assert!(matches!(
+            parse_err("(defprotocol P [m])"),
+            AnalyzeError::InvalidExpression(_)
+        ));
+        assert!(matches!(
+            parse_err("(extend-type String (m [s] s))"),
+            AnalyzeError::InvalidExpression(_)
+        ));
+        assert!(matches!(
+            parse_err("(.-x)"),
+            AnalyzeError::InvalidArity { form: ".-", .. }
+        ));
+    }
 }
@@ -1,4 +1,4 @@
-use lib::Interpreter;
+use lib::{Interpreter, Readable};
 use rustyline::completion::{Completer, Pair};
 use rustyline::error::ReadlineError;
 use rustyline::highlight::Highlighter;
@@ -79,7 +79,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 rl.add_history_entry(input)?;
 
                 match interpreter.run(input) {
-                    Ok(v) => println!("{v}"),
+                    Ok(v) => println!("{}", Readable(&v)),
                     Err(e) => println!("{e}"),
                 }
 